@@ -5,6 +5,8 @@ use std::process::ExitCode;
 use std::str::FromStr;
 
 use clap::{Parser, Subcommand};
+use m_bus_parser::user_data::unit_conversion::UnitSystem;
+use m_bus_parser::user_data::value_information::PlaintextVifOrder;
 use m_bus_parser::{render_hex, DecodeOptions, OutputFormat, RenderOptions};
 use terminal_size::{terminal_size, Width};
 
@@ -27,7 +29,8 @@ enum Command {
         #[arg(short = 'd', long, conflicts_with = "file")]
         data: Option<String>,
 
-        /// Output format: table, json, yaml, csv, mermaid, xml, annotated, annotated-text
+        /// Output format: table, json, yaml, csv, mermaid, xml, annotated, annotated-text,
+        /// history
         #[arg(short = 't', long, default_value = "table")]
         format: String,
 
@@ -42,6 +45,20 @@ enum Command {
         /// Omit bundled manufacturer enrichment from canonical outputs
         #[arg(long)]
         no_enrichment: bool,
+
+        /// Convert record values to units of a unit system: si, metric, us
+        #[arg(long)]
+        unit_system: Option<String>,
+
+        /// Fixed UTC offset of the meter's standard time, e.g. +01:00, to
+        /// render timestamps with offset (one hour more for summer time)
+        #[arg(long)]
+        utc_offset: Option<String>,
+
+        /// Position of plaintext VIF units: after (the VIFEs, as standardised),
+        /// before, or detect per record
+        #[arg(long)]
+        plaintext_vif: Option<String>,
     },
 }
 
@@ -65,6 +82,9 @@ fn run() -> Result<(), String> {
             key,
             width,
             no_enrichment,
+            unit_system,
+            utc_offset,
+            plaintext_vif,
         } => {
             let input = match (file, data) {
                 (Some(path), None) => fs::read_to_string(&path).map_err(|error| {
@@ -85,6 +105,22 @@ fn run() -> Result<(), String> {
                 .map(parse_key)
                 .transpose()
                 .map_err(|error| format!("[option.invalid] {error}"))?;
+            let unit_system = unit_system
+                .as_deref()
+                .map(parse_unit_system)
+                .transpose()
+                .map_err(|error| format!("[option.invalid] {error}"))?;
+            let utc_offset = utc_offset
+                .as_deref()
+                .map(parse_utc_offset)
+                .transpose()
+                .map_err(|error| format!("[option.invalid] {error}"))?;
+            let plaintext_vif_order = plaintext_vif
+                .as_deref()
+                .map(parse_plaintext_vif_order)
+                .transpose()
+                .map_err(|error| format!("[option.invalid] {error}"))?
+                .unwrap_or_default();
             let width = width.or_else(|| {
                 if io::stdout().is_terminal() {
                     terminal_size().map(|(Width(columns), _)| usize::from(columns))
@@ -96,10 +132,12 @@ fn run() -> Result<(), String> {
                 &input,
                 output_format,
                 &RenderOptions {
-                    decode: DecodeOptions {
-                        key,
-                        include_enrichment: !no_enrichment,
-                    },
+                    decode: DecodeOptions::default()
+                        .with_key(key)
+                        .with_enrichment(!no_enrichment)
+                        .with_unit_system(unit_system)
+                        .with_utc_offset(utc_offset)
+                        .with_plaintext_vif_order(plaintext_vif_order),
                     table_width: width,
                 },
             )
@@ -120,6 +158,45 @@ fn parse_key(value: &str) -> Result<[u8; 16], String> {
         .map_err(|_| "key must contain exactly 16 bytes".to_string())
 }
 
+fn parse_unit_system(value: &str) -> Result<UnitSystem, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "si" => Ok(UnitSystem::Si),
+        "metric" => Ok(UnitSystem::Metric),
+        "us" => Ok(UnitSystem::Us),
+        _ => Err(format!("unit system must be si, metric or us, not {value}")),
+    }
+}
+
+fn parse_plaintext_vif_order(value: &str) -> Result<PlaintextVifOrder, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "after" => Ok(PlaintextVifOrder::AfterExtensions),
+        "before" => Ok(PlaintextVifOrder::BeforeExtensions),
+        "detect" => Ok(PlaintextVifOrder::Detect),
+        _ => Err(format!(
+            "plaintext VIF order must be after, before or detect, not {value}"
+        )),
+    }
+}
+
+/// Parses `UTC`, `Z` or an offset like `+01:00` into minutes.
+fn parse_utc_offset(value: &str) -> Result<i16, String> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("utc") || value.eq_ignore_ascii_case("z") {
+        return Ok(0);
+    }
+    let invalid = || format!("UTC offset must be UTC or like +01:00, not {value}");
+    let (sign, offset) = match value.split_at_checked(1) {
+        Some(("+", offset)) => (1, offset),
+        Some(("-", offset)) => (-1, offset),
+        _ => return Err(invalid()),
+    };
+    let (hours, minutes) = offset.split_once(':').unwrap_or((offset, "0"));
+    match (hours.parse::<i16>(), minutes.parse::<i16>()) {
+        (Ok(hours @ 0..=14), Ok(minutes @ 0..=59)) => Ok(sign * (hours * 60 + minutes)),
+        _ => Err(invalid()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +207,34 @@ mod tests {
         assert!(parse_key("0011").is_err());
         assert!(parse_key("00112233445566778899AABBCCDDEEFG").is_err());
     }
+
+    #[test]
+    fn parses_unit_system() {
+        assert_eq!(parse_unit_system("SI"), Ok(UnitSystem::Si));
+        assert_eq!(parse_unit_system("us"), Ok(UnitSystem::Us));
+        assert!(parse_unit_system("imperial").is_err());
+    }
+
+    #[test]
+    fn parses_plaintext_vif_order() {
+        assert_eq!(
+            parse_plaintext_vif_order("Before"),
+            Ok(PlaintextVifOrder::BeforeExtensions)
+        );
+        assert_eq!(
+            parse_plaintext_vif_order("detect"),
+            Ok(PlaintextVifOrder::Detect)
+        );
+        assert!(parse_plaintext_vif_order("auto").is_err());
+    }
+
+    #[test]
+    fn parses_utc_offset() {
+        assert_eq!(parse_utc_offset("UTC"), Ok(0));
+        assert_eq!(parse_utc_offset("+01:00"), Ok(60));
+        assert_eq!(parse_utc_offset("-05:30"), Ok(-330));
+        assert_eq!(parse_utc_offset("+2"), Ok(120));
+        assert!(parse_utc_offset("Europe/Berlin").is_err());
+        assert!(parse_utc_offset("+01:75").is_err());
+    }
 }
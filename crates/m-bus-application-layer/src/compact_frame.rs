@@ -0,0 +1,510 @@
+//! OMS compact frames (CI `0x79` and `0x7B`).
+//!
+//! A compact frame leaves out the DIF/VIF headers of its data records. It
+//! references the header layout of an earlier full frame by its format
+//! signature, a CRC over the header bytes, and carries a second CRC over the
+//! records of the equivalent full frame. Receivers keep the layouts of full
+//! frames in a [`FormatSignatureCache`] and use it to rebuild later compact
+//! frames. Meters may also announce a layout on its own in a format frame,
+//! see [`RecordLayout`].
+
+use arrayvec::ArrayVec;
+
+use crate::data_record::{DataRecord, DataRecordHeader};
+use crate::parse_data_records;
+use crate::variable_user_data::DataRecordError;
+
+/// Maximum number of DIF/VIF header bytes in a single format.
+pub const MAX_FORMAT_SIZE: usize = 256;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum CompactFrameError {
+    /// No full frame with this format signature has been learned.
+    UnknownFormatSignature {
+        signature: u16,
+    },
+    /// The rebuilt records do not match the full-frame CRC of the compact frame.
+    FullFrameCrcMismatch {
+        expected: u16,
+        calculated: u16,
+    },
+    /// The signature of a format frame does not match its header bytes.
+    FormatSignatureMismatch {
+        expected: u16,
+        calculated: u16,
+    },
+    /// A format or rebuilt frame does not fit into the provided storage.
+    BufferTooSmall,
+    /// The payload is encrypted and must be decrypted first.
+    Encrypted,
+    /// The user data block carries no data records or record layout.
+    UnsupportedDataBlock,
+    DataRecordError(DataRecordError),
+    InsufficientData,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for CompactFrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompactFrameError::UnknownFormatSignature { signature } => {
+                write!(f, "Unknown format signature: {:04X}", signature)
+            }
+            CompactFrameError::FullFrameCrcMismatch {
+                expected,
+                calculated,
+            } => write!(
+                f,
+                "Full frame CRC mismatch: expected {:04X}, calculated {:04X}",
+                expected, calculated
+            ),
+            CompactFrameError::FormatSignatureMismatch {
+                expected,
+                calculated,
+            } => write!(
+                f,
+                "Format signature mismatch: expected {:04X}, calculated {:04X}",
+                expected, calculated
+            ),
+            CompactFrameError::BufferTooSmall => write!(f, "Buffer too small"),
+            CompactFrameError::Encrypted => write!(f, "Compact data is encrypted"),
+            CompactFrameError::UnsupportedDataBlock => {
+                write!(f, "User data block has no data records")
+            }
+            CompactFrameError::DataRecordError(e) => write!(f, "{}", e),
+            CompactFrameError::InsufficientData => write!(f, "Insufficient data"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompactFrameError {}
+
+impl From<DataRecordError> for CompactFrameError {
+    fn from(error: DataRecordError) -> Self {
+        Self::DataRecordError(error)
+    }
+}
+
+/// Stores DIF/VIF header layouts of full frames by format signature.
+pub trait FormatSignatureCache {
+    /// Returns the header bytes stored under `signature`.
+    fn lookup(&self, signature: u16) -> Option<&[u8]>;
+
+    /// Stores the header bytes of a full frame under `signature`.
+    fn insert(&mut self, signature: u16, format: &[u8]) -> Result<(), CompactFrameError>;
+
+    /// Learns the layout of the data records of a full frame and returns its
+    /// format signature.
+    fn learn(&mut self, data_records: &[u8]) -> Result<u16, CompactFrameError> {
+        let format = record_format(data_records)?;
+        let signature = format_signature(&format);
+        self.insert(signature, &format)?;
+        Ok(signature)
+    }
+}
+
+/// Fixed-capacity cache for `no_std` targets.
+///
+/// Holds up to `N` formats of at most `M` header bytes each. When full, the
+/// oldest format is replaced.
+#[derive(Debug, Clone)]
+pub struct StaticFormatCache<const N: usize, const M: usize = MAX_FORMAT_SIZE> {
+    entries: ArrayVec<(u16, ArrayVec<u8, M>), N>,
+    next: usize,
+}
+
+impl<const N: usize, const M: usize> Default for StaticFormatCache<N, M> {
+    fn default() -> Self {
+        Self {
+            entries: ArrayVec::new(),
+            next: 0,
+        }
+    }
+}
+
+impl<const N: usize, const M: usize> StaticFormatCache<N, M> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<const N: usize, const M: usize> FormatSignatureCache for StaticFormatCache<N, M> {
+    fn lookup(&self, signature: u16) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|(stored, _)| *stored == signature)
+            .map(|(_, format)| format.as_slice())
+    }
+
+    fn insert(&mut self, signature: u16, format: &[u8]) -> Result<(), CompactFrameError> {
+        let mut bytes = ArrayVec::new();
+        bytes
+            .try_extend_from_slice(format)
+            .map_err(|_| CompactFrameError::BufferTooSmall)?;
+
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|(stored, _)| *stored == signature)
+        {
+            entry.1 = bytes;
+        } else if !self.entries.is_full() {
+            self.entries.push((signature, bytes));
+        } else {
+            let entry = self
+                .entries
+                .get_mut(self.next)
+                .ok_or(CompactFrameError::BufferTooSmall)?;
+            *entry = (signature, bytes);
+            self.next = (self.next + 1) % N;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl FormatSignatureCache for std::collections::HashMap<u16, Vec<u8>> {
+    fn lookup(&self, signature: u16) -> Option<&[u8]> {
+        self.get(&signature).map(Vec::as_slice)
+    }
+
+    fn insert(&mut self, signature: u16, format: &[u8]) -> Result<(), CompactFrameError> {
+        std::collections::HashMap::insert(self, signature, format.to_vec());
+        Ok(())
+    }
+}
+
+/// Collects the DIF/VIF header bytes of all data records of a full frame.
+///
+/// Collection stops at the first special function, since manufacturer
+/// specific data and fillers carry no header of their own.
+pub fn record_format(
+    data_records: &[u8],
+) -> Result<ArrayVec<u8, MAX_FORMAT_SIZE>, CompactFrameError> {
+    let mut format = ArrayVec::new();
+    for record in parse_data_records(data_records) {
+        let record = record?;
+        let header = &record.data_record_header.raw_data_record_header;
+        if header
+            .data_information_block
+            .data_information_field
+            .is_special_function()
+        {
+            break;
+        }
+        let header_size = record.data_record_header.get_size();
+        format
+            .try_extend_from_slice(
+                record
+                    .raw_bytes
+                    .get(..header_size)
+                    .ok_or(CompactFrameError::InsufficientData)?,
+            )
+            .map_err(|_| CompactFrameError::BufferTooSmall)?;
+    }
+    Ok(format)
+}
+
+/// Calculates the format signature of the given DIF/VIF header bytes.
+#[must_use]
+pub fn format_signature(format: &[u8]) -> u16 {
+    crc16_en13757(format)
+}
+
+/// Data part of a compact frame following the transport header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CompactData<'a> {
+    pub format_signature: u16,
+    pub full_frame_crc: u16,
+    pub data: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for CompactData<'a> {
+    type Error = CompactFrameError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let format_signature = u16::from_le_bytes([
+            *data.first().ok_or(CompactFrameError::InsufficientData)?,
+            *data.get(1).ok_or(CompactFrameError::InsufficientData)?,
+        ]);
+        let full_frame_crc = u16::from_le_bytes([
+            *data.get(2).ok_or(CompactFrameError::InsufficientData)?,
+            *data.get(3).ok_or(CompactFrameError::InsufficientData)?,
+        ]);
+        Ok(Self {
+            format_signature,
+            full_frame_crc,
+            data: data.get(4..).ok_or(CompactFrameError::InsufficientData)?,
+        })
+    }
+}
+
+impl CompactData<'_> {
+    /// Rebuilds the full data records into `output` and returns their length.
+    ///
+    /// The rebuilt records are checked against the full-frame CRC and can be
+    /// parsed with [`parse_data_records`].
+    pub fn expand_into<C: FormatSignatureCache + ?Sized>(
+        &self,
+        cache: &C,
+        output: &mut [u8],
+    ) -> Result<usize, CompactFrameError> {
+        let format = cache.lookup(self.format_signature).ok_or(
+            CompactFrameError::UnknownFormatSignature {
+                signature: self.format_signature,
+            },
+        )?;
+        let layout = RecordLayout {
+            format_signature: self.format_signature,
+            format,
+        };
+        let written = layout.expand_into(self.data, output)?;
+
+        let calculated = crc16_en13757(output.get(..written).unwrap_or(&[]));
+        if calculated != self.full_frame_crc {
+            return Err(CompactFrameError::FullFrameCrcMismatch {
+                expected: self.full_frame_crc,
+                calculated,
+            });
+        }
+        Ok(written)
+    }
+}
+
+/// DIF/VIF layout of the data records of a full frame.
+///
+/// Format frames (CI `0x69`, `0x6A`, `0x6B`) transmit a layout on its own. It
+/// can be stored in a [`FormatSignatureCache`] or applied directly to record
+/// data sent without headers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecordLayout<'a> {
+    pub format_signature: u16,
+    pub format: &'a [u8],
+}
+
+impl<'a> RecordLayout<'a> {
+    /// Creates a layout from DIF/VIF header bytes and calculates its signature.
+    #[must_use]
+    pub fn new(format: &'a [u8]) -> Self {
+        Self {
+            format_signature: format_signature(format),
+            format,
+        }
+    }
+
+    /// Returns `true` if the signature matches the header bytes.
+    #[must_use]
+    pub fn is_signature_valid(&self) -> bool {
+        format_signature(self.format) == self.format_signature
+    }
+
+    /// Returns an iterator over the record headers of this layout.
+    #[must_use]
+    pub const fn headers(&self) -> RecordLayoutHeaders<'a> {
+        RecordLayoutHeaders {
+            offset: 0,
+            format: self.format,
+        }
+    }
+
+    /// Stores this layout in `cache` after checking its signature.
+    pub fn store<C: FormatSignatureCache + ?Sized>(
+        &self,
+        cache: &mut C,
+    ) -> Result<u16, CompactFrameError> {
+        let calculated = format_signature(self.format);
+        if calculated != self.format_signature {
+            return Err(CompactFrameError::FormatSignatureMismatch {
+                expected: self.format_signature,
+                calculated,
+            });
+        }
+        cache.insert(self.format_signature, self.format)?;
+        Ok(self.format_signature)
+    }
+
+    /// Combines record data sent without headers with this layout into
+    /// `output` and returns the length of the full records.
+    pub fn expand_into(&self, data: &[u8], output: &mut [u8]) -> Result<usize, CompactFrameError> {
+        let mut format_offset = 0;
+        let mut data_offset = 0;
+        let mut written = 0;
+        for header in self.headers() {
+            let header_size = header?.get_size();
+            let header = self
+                .format
+                .get(format_offset..format_offset + header_size)
+                .ok_or(CompactFrameError::InsufficientData)?;
+            let remaining = data
+                .get(data_offset..)
+                .ok_or(CompactFrameError::InsufficientData)?;
+
+            // The data size of a record can depend on its data (LVAR), so
+            // the record is parsed in place with all remaining data behind it.
+            let end = written + header_size + remaining.len();
+            let target = output
+                .get_mut(written..end)
+                .ok_or(CompactFrameError::BufferTooSmall)?;
+            let (target_header, target_data) = target.split_at_mut(header_size);
+            target_header.copy_from_slice(header);
+            target_data.copy_from_slice(remaining);
+
+            let record_size = DataRecord::try_from(
+                output
+                    .get(written..end)
+                    .ok_or(CompactFrameError::BufferTooSmall)?,
+            )?
+            .get_size();
+            format_offset += header_size;
+            data_offset += record_size.saturating_sub(header_size);
+            written += record_size;
+        }
+
+        // Anything behind the described records, such as manufacturer
+        // specific data, is part of the full frame as is.
+        let trailing = data
+            .get(data_offset..)
+            .ok_or(CompactFrameError::InsufficientData)?;
+        output
+            .get_mut(written..written + trailing.len())
+            .ok_or(CompactFrameError::BufferTooSmall)?
+            .copy_from_slice(trailing);
+        Ok(written + trailing.len())
+    }
+}
+
+/// Iterator over the record headers of a [`RecordLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordLayoutHeaders<'a> {
+    offset: usize,
+    format: &'a [u8],
+}
+
+impl<'a> Iterator for RecordLayoutHeaders<'a> {
+    type Item = Result<DataRecordHeader<'a>, DataRecordError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = self.format.get(self.offset..)?;
+        if remaining.is_empty() {
+            return None;
+        }
+        match DataRecordHeader::try_from(remaining) {
+            Ok(header) => {
+                self.offset += header.get_size();
+                Some(Ok(header))
+            }
+            Err(error) => {
+                self.offset = self.format.len();
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+fn crc16_en13757(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0x0000;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            if crc & 0x8000 != 0 {
+                crc = (crc << 1) ^ 0x3D65;
+            } else {
+                crc <<= 1;
+            }
+        }
+    }
+    crc ^ 0xFFFF
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::data_information::DataType;
+
+    // Volume (0C 13, 8 digit BCD) and flow temperature (02 5A, 16 bit).
+    const FULL_RECORDS: [u8; 10] = [0x0C, 0x13, 0x78, 0x56, 0x34, 0x12, 0x02, 0x5A, 0x2C, 0x01];
+
+    fn compact_payload(cache_signature: u16) -> Vec<u8> {
+        let crc = crc16_en13757(&FULL_RECORDS);
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&cache_signature.to_le_bytes());
+        payload.extend_from_slice(&crc.to_le_bytes());
+        payload.extend_from_slice(&[0x78, 0x56, 0x34, 0x12, 0x2C, 0x01]);
+        payload
+    }
+
+    #[test]
+    fn learns_record_format_of_full_frame() {
+        let format = record_format(&FULL_RECORDS).unwrap();
+        assert_eq!(format.as_slice(), &[0x0C, 0x13, 0x02, 0x5A]);
+        assert_eq!(
+            format_signature(&format),
+            crc16_en13757(&[0x0C, 0x13, 0x02, 0x5A])
+        );
+    }
+
+    #[test]
+    fn expands_compact_data_with_static_cache() {
+        let mut cache = StaticFormatCache::<2>::new();
+        let signature = cache.learn(&FULL_RECORDS).unwrap();
+        let payload = compact_payload(signature);
+
+        let compact = CompactData::try_from(payload.as_slice()).unwrap();
+        let mut output = [0u8; 64];
+        let len = compact.expand_into(&cache, &mut output).unwrap();
+        assert_eq!(&output[..len], &FULL_RECORDS);
+
+        let records: Vec<_> = parse_data_records(&output[..len])
+            .map(|record| record.unwrap())
+            .collect();
+        assert_eq!(records[0].value(), Some(&DataType::Integer(12_345_678)));
+        assert_eq!(records[1].value(), Some(&DataType::Integer(300)));
+    }
+
+    #[test]
+    fn rejects_unknown_signature_and_crc_mismatch() {
+        let mut cache = std::collections::HashMap::new();
+        let signature = cache.learn(&FULL_RECORDS).unwrap();
+        let mut output = [0u8; 64];
+
+        let payload = compact_payload(signature.wrapping_add(1));
+        let compact = CompactData::try_from(payload.as_slice()).unwrap();
+        assert_eq!(
+            compact.expand_into(&cache, &mut output),
+            Err(CompactFrameError::UnknownFormatSignature {
+                signature: signature.wrapping_add(1)
+            })
+        );
+
+        let mut payload = compact_payload(signature);
+        let last = payload.len() - 1;
+        payload[last] ^= 0xFF;
+        let compact = CompactData::try_from(payload.as_slice()).unwrap();
+        assert!(matches!(
+            compact.expand_into(&cache, &mut output),
+            Err(CompactFrameError::FullFrameCrcMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn static_cache_replaces_oldest_format() {
+        let mut cache = StaticFormatCache::<1, 8>::new();
+        cache.insert(1, &[0x04, 0x13]).unwrap();
+        cache.insert(2, &[0x02, 0x5A]).unwrap();
+        assert_eq!(cache.lookup(1), None);
+        assert_eq!(cache.lookup(2), Some([0x02, 0x5A].as_slice()));
+        assert_eq!(
+            cache.insert(3, &[0; 9]),
+            Err(CompactFrameError::BufferTooSmall)
+        );
+    }
+}
@@ -0,0 +1,738 @@
+//! Compact profiles of load profile meters (EN 13757-3, annex F).
+//!
+//! A compact profile packs readings of a quantity at a fixed spacing into one
+//! record with the VIFE `0x1F` (compact profile), `0x1E` (compact profile
+//! with register numbers) or `0x13` (inverse compact profile) and a variable
+//! length data field. The data starts with the spacing control byte: the
+//! increment mode in bits 6 and 7, the spacing unit in bits 4 and 5 and the
+//! coding of the elements, a DIF data field coding, in bits 0 to 3. The
+//! spacing value follows, then the elements.
+//!
+//! The profile starts at the base time, the date record with the storage
+//! number of the profile, and, unless the elements are absolute values, at
+//! the base value, the register of the same quantity with that storage
+//! number. Element `k` of a compact profile lies `k` spacings before the base
+//! time, element `k` of an inverse compact profile `k` spacings after it.
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+use crate::data_information::{
+    month, DataFieldCoding, DataType, Decimal, SingleEveryOrInvalid, TimeFlags,
+};
+use crate::data_record::DataRecord;
+use crate::history::{SeriesKey, StorageTimestamps};
+use crate::scaled_value::ScaledNumber;
+use crate::value_information::ValueLabel;
+use crate::DataRecords;
+
+/// Spacing value of elements a month apart.
+const MONTHLY_SPACING: u8 = 0xFE;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ProfileKind {
+    /// Elements step back in time from the base time.
+    Compact,
+    /// Elements step back in time and hold the registers with the storage
+    /// numbers following the one of the profile.
+    CompactWithRegisterNumbers,
+    /// Elements step forward in time from the base time.
+    Inverse,
+}
+
+impl ProfileKind {
+    /// Returns the kind of profile the VIFEs of a record announce.
+    #[must_use]
+    pub fn of(record: &DataRecord<'_>) -> Option<Self> {
+        record
+            .value_information()?
+            .labels
+            .iter()
+            .find_map(|label| match label {
+                ValueLabel::CompactProfile => Some(Self::Compact),
+                ValueLabel::CompactProfileWithRegisterNumbers => {
+                    Some(Self::CompactWithRegisterNumbers)
+                }
+                ValueLabel::InverseCompactProfile => Some(Self::Inverse),
+                _ => None,
+            })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IncrementMode {
+    /// Elements are readings.
+    Absolute,
+    /// Elements are unsigned increments of the reading per spacing.
+    Increments,
+    /// Elements are unsigned decrements of the reading per spacing.
+    Decrements,
+    /// Elements are signed differences of the reading per spacing.
+    SignedDifferences,
+}
+
+/// Time between the elements of a profile.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Spacing {
+    Seconds(u32),
+    Months(u8),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CompactProfileError {
+    /// The record has no compact profile VIFE or no variable length data.
+    NotAProfile,
+    /// The data ends before the spacing value or within an element.
+    Truncated,
+    /// Spacing values 251 to 255 other than 254 (monthly) are reserved.
+    ReservedSpacingValue(u8),
+    /// The element coding is no integer or BCD coding.
+    UnsupportedElementCoding(u8),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CompactProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAProfile => write!(f, "record is no compact profile"),
+            Self::Truncated => write!(f, "compact profile data is truncated"),
+            Self::ReservedSpacingValue(value) => {
+                write!(f, "reserved compact profile spacing value {value}")
+            }
+            Self::UnsupportedElementCoding(coding) => {
+                write!(f, "unsupported compact profile element coding 0x{coding:X}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompactProfileError {}
+
+/// Calendar date and time in the local time of the meter.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Timestamp {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl Timestamp {
+    /// Returns the timestamp `seconds` later, or earlier if negative.
+    #[must_use]
+    pub fn add_seconds(self, seconds: i64) -> Option<Self> {
+        let time =
+            i64::from(self.hour) * 3600 + i64::from(self.minute) * 60 + i64::from(self.second);
+        let total = days_from_civil(self.year, self.month, self.day)
+            .checked_mul(86_400)?
+            .checked_add(time)?
+            .checked_add(seconds)?;
+        let (year, month, day) = civil_from_days(total.div_euclid(86_400))?;
+        let time = total.rem_euclid(86_400);
+        Some(Self {
+            year,
+            month,
+            day,
+            hour: (time / 3600) as u8,
+            minute: (time % 3600 / 60) as u8,
+            second: (time % 60) as u8,
+        })
+    }
+
+    /// Returns the timestamp `months` later, or earlier if negative, on the
+    /// last day of the month if the month is shorter.
+    #[must_use]
+    pub fn add_months(self, months: i64) -> Option<Self> {
+        let index = i64::from(self.year) * 12 + i64::from(self.month) - 1 + months;
+        let year = u16::try_from(index.div_euclid(12)).ok()?;
+        let month = index.rem_euclid(12) as u8 + 1;
+        Some(Self {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+            ..self
+        })
+    }
+
+    /// Returns the timestamp as type I date and time without flags.
+    #[must_use]
+    pub const fn to_data_type(self) -> DataType<'static> {
+        DataType::DateTimeWithSeconds(
+            SingleEveryOrInvalid::Single(self.day),
+            month(self.month),
+            SingleEveryOrInvalid::Single(self.year),
+            SingleEveryOrInvalid::Single(self.hour),
+            SingleEveryOrInvalid::Single(self.minute),
+            SingleEveryOrInvalid::Single(self.second),
+            TimeFlags {
+                invalid: false,
+                summer_time: false,
+                leap_year: is_leap_year(self.year),
+                day_of_week: None,
+            },
+        )
+    }
+}
+
+impl TryFrom<&DataType<'_>> for Timestamp {
+    type Error = ();
+
+    /// Converts a complete date, which starts at midnight, or date and time.
+    fn try_from(value: &DataType<'_>) -> Result<Self, Self::Error> {
+        use SingleEveryOrInvalid::Single;
+        let (day, month, year, hour, minute, second) = match value {
+            DataType::Date(day, month, year) => {
+                (day, month, year, &Single(0), &Single(0), &Single(0))
+            }
+            DataType::DateTime(day, month, year, hour, minute, flags) if !flags.invalid => {
+                (day, month, year, hour, minute, &Single(0))
+            }
+            DataType::DateTimeWithSeconds(day, month, year, hour, minute, second, flags)
+                if !flags.invalid =>
+            {
+                (day, month, year, hour, minute, second)
+            }
+            DataType::DateTimeWithOffset(day, month, year, hour, minute, second, _, flags)
+                if !flags.invalid =>
+            {
+                (day, month, year, hour, minute, second)
+            }
+            _ => return Err(()),
+        };
+        match (day, month, year, hour, minute, second) {
+            (
+                Single(day),
+                Single(month),
+                Single(year),
+                Single(hour),
+                Single(minute),
+                Single(second),
+            ) => Ok(Self {
+                year: *year,
+                month: *month as u8 + 1,
+                day: *day,
+                hour: *hour,
+                minute: *minute,
+                second: *second,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One element of an expanded profile.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProfilePoint {
+    /// Position of the element, starting at 1.
+    pub index: usize,
+    /// Storage number of the register, for profiles with register numbers.
+    pub storage_number: Option<u64>,
+    pub timestamp: Option<Timestamp>,
+    /// Scaled element as transmitted: the reading or its change.
+    pub element: Option<ScaledNumber>,
+    /// Reading at the timestamp, `None` for invalid elements and, in the
+    /// increment modes, without base value.
+    pub value: Option<ScaledNumber>,
+}
+
+/// A compact profile record, see the [module documentation](self).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CompactProfile<'a> {
+    pub kind: ProfileKind,
+    pub increment_mode: IncrementMode,
+    pub spacing: Spacing,
+    /// Storage number of the profile, which selects base time and value.
+    pub storage_number: u64,
+    /// Decimal exponent of the elements from the value information.
+    pub scale_exponent: i16,
+    element_size: usize,
+    bcd: bool,
+    elements: &'a [u8],
+}
+
+impl<'a> TryFrom<&DataRecord<'a>> for CompactProfile<'a> {
+    type Error = CompactProfileError;
+
+    fn try_from(record: &DataRecord<'a>) -> Result<Self, Self::Error> {
+        let kind = ProfileKind::of(record).ok_or(CompactProfileError::NotAProfile)?;
+        let information = record
+            .data_information()
+            .filter(|information| information.data_field_coding == DataFieldCoding::VariableLength)
+            .ok_or(CompactProfileError::NotAProfile)?;
+        let scale_exponent = record
+            .value_information()
+            .and_then(|value| i16::try_from(value.decimal_scale_exponent).ok())
+            .ok_or(CompactProfileError::NotAProfile)?;
+        let data = record
+            .raw_bytes
+            .get(record.data_record_header.get_size()..)
+            .ok_or(CompactProfileError::Truncated)?;
+        let (&length, data) = data.split_first().ok_or(CompactProfileError::Truncated)?;
+        if length > 0xBF {
+            return Err(CompactProfileError::NotAProfile);
+        }
+        let data = data
+            .get(..usize::from(length))
+            .ok_or(CompactProfileError::Truncated)?;
+        let [control, spacing_value, elements @ ..] = data else {
+            return Err(CompactProfileError::Truncated);
+        };
+        let increment_mode = match control >> 6 {
+            0b00 => IncrementMode::Absolute,
+            0b01 => IncrementMode::Increments,
+            0b10 => IncrementMode::Decrements,
+            _ => IncrementMode::SignedDifferences,
+        };
+        let spacing = match *spacing_value {
+            MONTHLY_SPACING => Spacing::Months(1),
+            value @ 0..=250 => {
+                let unit = match (control >> 4) & 0b11 {
+                    0b00 => 1,
+                    0b01 => 60,
+                    0b10 => 3600,
+                    _ => 86_400,
+                };
+                Spacing::Seconds(u32::from(value) * unit)
+            }
+            value => return Err(CompactProfileError::ReservedSpacingValue(value)),
+        };
+        let (element_size, bcd) = match control & 0x0F {
+            coding @ 0x1..=0x4 => (usize::from(coding), false),
+            0x6 => (6, false),
+            0x7 => (8, false),
+            coding @ 0x9..=0xC => (usize::from(coding - 0x8), true),
+            0xE => (6, true),
+            coding => return Err(CompactProfileError::UnsupportedElementCoding(coding)),
+        };
+        if elements.len() % element_size != 0 {
+            return Err(CompactProfileError::Truncated);
+        }
+        Ok(Self {
+            kind,
+            increment_mode,
+            spacing,
+            storage_number: information.storage_number,
+            scale_exponent,
+            element_size,
+            bcd,
+            elements,
+        })
+    }
+}
+
+impl<'a> CompactProfile<'a> {
+    /// Returns the number of elements.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.elements.len() / self.element_size
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the unscaled elements, `None` for elements marked invalid:
+    /// the lowest value of signed elements, all bits set for unsigned
+    /// increments and decrements, and BCD elements with digits above 9.
+    pub fn elements(&self) -> impl Iterator<Item = Option<i64>> + 'a {
+        let (bcd, signed) = (
+            self.bcd,
+            matches!(
+                self.increment_mode,
+                IncrementMode::Absolute | IncrementMode::SignedDifferences
+            ),
+        );
+        self.elements
+            .chunks_exact(self.element_size)
+            .map(move |bytes| {
+                if bcd {
+                    bcd_element(bytes)
+                } else {
+                    integer_element(bytes, signed)
+                }
+            })
+    }
+
+    /// Expands the profile into readings with timestamps from `base_time`
+    /// and, for changes, values from `base_value`.
+    pub fn points(
+        &self,
+        base_time: Option<Timestamp>,
+        base_value: Option<ScaledNumber>,
+    ) -> impl Iterator<Item = ProfilePoint> + 'a {
+        let profile = *self;
+        let mut value = base_value;
+        self.elements().enumerate().map(move |(position, element)| {
+            let index = position + 1;
+            let element =
+                element.map(|element| Decimal::new(element.into(), profile.scale_exponent));
+            let steps = i64::try_from(index).unwrap_or(i64::MAX);
+            let steps = if profile.kind == ProfileKind::Inverse {
+                steps
+            } else {
+                -steps
+            };
+            let forward = profile.kind == ProfileKind::Inverse;
+            value = match (profile.increment_mode, element) {
+                (IncrementMode::Absolute, element) => element.map(ScaledNumber::Exact),
+                (_, None) => None,
+                (mode, Some(change)) => {
+                    // The reading grows by increments and signed differences
+                    // and shrinks by decrements as time goes on.
+                    let later = mode != IncrementMode::Decrements;
+                    let change = if later == forward {
+                        change
+                    } else {
+                        negate(change)
+                    };
+                    value.map(|value| value.plus(change))
+                }
+            };
+            ProfilePoint {
+                index,
+                storage_number: (profile.kind == ProfileKind::CompactWithRegisterNumbers)
+                    .then(|| profile.storage_number + position as u64 + 1),
+                timestamp: base_time.and_then(|time| match profile.spacing {
+                    Spacing::Seconds(seconds) => time.add_seconds(steps * i64::from(seconds)),
+                    Spacing::Months(months) => time.add_months(steps * i64::from(months)),
+                }),
+                element: element.map(ScaledNumber::Exact),
+                value,
+            }
+        })
+    }
+
+    /// Finds the base value of the profile: the scaled value of the register
+    /// with the quantity and storage number of `record`. Faulty records are
+    /// skipped.
+    pub fn base_value(
+        &self,
+        record: &DataRecord<'a>,
+        records: DataRecords<'a>,
+    ) -> Option<ScaledNumber> {
+        let mut key = SeriesKey::of(record)?;
+        key.labels.retain(|label| !is_profile_label(*label));
+        records
+            .recovering()
+            .filter_map(Result::ok)
+            .filter(|candidate| {
+                candidate
+                    .data_information()
+                    .is_some_and(|information| information.storage_number == self.storage_number)
+                    && ProfileKind::of(candidate).is_none()
+                    && SeriesKey::of(candidate).as_ref() == Some(&key)
+            })
+            .find_map(|candidate| candidate.scaled_value())
+            .map(|scaled| scaled.value)
+    }
+
+    /// Returns the base time of the profile from the dates of the frame.
+    #[must_use]
+    pub fn base_time(&self, timestamps: &StorageTimestamps<'_>) -> Option<Timestamp> {
+        timestamps
+            .timestamp(self.storage_number)
+            .and_then(|time| Timestamp::try_from(time).ok())
+    }
+}
+
+/// A compact profile with its base and expanded readings.
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpandedProfile<'a> {
+    pub record: DataRecord<'a>,
+    pub profile: CompactProfile<'a>,
+    pub base_time: Option<Timestamp>,
+    pub base_value: Option<ScaledNumber>,
+    pub points: Vec<ProfilePoint>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> ExpandedProfile<'a> {
+    /// Expands a compact profile record, with the base value from `records`
+    /// and the base time from `timestamps`.
+    pub fn new(
+        record: DataRecord<'a>,
+        records: DataRecords<'a>,
+        timestamps: &StorageTimestamps<'_>,
+    ) -> Result<Self, CompactProfileError> {
+        let profile = CompactProfile::try_from(&record)?;
+        let base_time = profile.base_time(timestamps);
+        let base_value = profile.base_value(&record, records);
+        Ok(Self {
+            points: profile.points(base_time, base_value).collect(),
+            record,
+            profile,
+            base_time,
+            base_value,
+        })
+    }
+
+    /// Expands the compact profiles among the records, skipping faulty
+    /// records, with an error for each profile that cannot be read.
+    pub fn collect(records: DataRecords<'a>) -> Vec<Result<Self, CompactProfileError>> {
+        let timestamps = StorageTimestamps::collect(records.clone());
+        records
+            .clone()
+            .recovering()
+            .filter_map(Result::ok)
+            .filter(|record| ProfileKind::of(record).is_some())
+            .map(|record| Self::new(record, records.clone(), &timestamps))
+            .collect()
+    }
+}
+
+const fn is_profile_label(label: ValueLabel) -> bool {
+    matches!(
+        label,
+        ValueLabel::CompactProfile
+            | ValueLabel::CompactProfileWithRegisterNumbers
+            | ValueLabel::InverseCompactProfile
+    )
+}
+
+fn integer_element(bytes: &[u8], signed: bool) -> Option<i64> {
+    let mut buffer = [0; 8];
+    buffer[..bytes.len()].copy_from_slice(bytes);
+    let bits = 8 * bytes.len() as u32;
+    let raw = u64::from_le_bytes(buffer);
+    let all_ones = u64::MAX >> (64 - bits);
+    if !signed {
+        return (raw != all_ones).then(|| i64::try_from(raw).ok()).flatten();
+    }
+    let sign = 1_u64 << (bits - 1);
+    if raw == sign {
+        return None;
+    }
+    // Sign-extend to 64 bits.
+    Some(((raw ^ sign).wrapping_sub(sign)) as i64)
+}
+
+fn bcd_element(bytes: &[u8]) -> Option<i64> {
+    bytes.iter().rev().try_fold(0_i64, |value, byte| {
+        let (high, low) = (byte >> 4, byte & 0x0F);
+        (high <= 9 && low <= 9).then(|| value * 100 + i64::from(high) * 10 + i64::from(low))
+    })
+}
+
+fn negate(value: Decimal) -> Decimal {
+    Decimal::new(-value.significand, value.exponent)
+}
+
+const fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date of a number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> Option<(u16, u8, u8)> {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u8;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u8;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    Some((u16::try_from(year).ok()?, month, day))
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn points(data: &[u8]) -> Vec<(Option<u64>, String, Option<String>)> {
+        let profiles = ExpandedProfile::collect(DataRecords::new(data, None));
+        assert_eq!(profiles.len(), 1);
+        let profile = profiles.into_iter().next().unwrap().unwrap();
+        profile
+            .points
+            .iter()
+            .map(|point| {
+                let time = point.timestamp.unwrap();
+                (
+                    point.storage_number,
+                    format!(
+                        "{:04}-{:02}-{:02}T{:02}:{:02}",
+                        time.year, time.month, time.day, time.hour, time.minute
+                    ),
+                    point.value.map(|value| value.to_string()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn increments_step_back_from_base_value() {
+        let data = [
+            0x04, 0x03, 0x10, 0x27, 0x00, 0x00, // 10000 Wh
+            0x04, 0x6D, 0x00, 0x00, 0x21, 0x31, // 1 January 2025 00:00
+            0x0D, 0x83, 0x1F, 0x06, // compact profile of Wh
+            0x62, 0x0F, // increments of 16 bits every 15 hours
+            0x64, 0x00, 0xFF, 0xFF, 0xC8, 0x00,
+        ];
+        assert_eq!(
+            points(&data),
+            [
+                (
+                    None,
+                    "2024-12-31T09:00".to_string(),
+                    Some("9900".to_string())
+                ),
+                (None, "2024-12-30T18:00".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn faulty_records_are_skipped() {
+        let data = [
+            0x0A, 0x13, 0xAB, 0x0C, // 4-digit BCD with non-decimal digits
+            0x04, 0x03, 0x10, 0x27, 0x00, 0x00, // 10000 Wh
+            0x04, 0x6D, 0x00, 0x00, 0x21, 0x31, // 1 January 2025 00:00
+            0x0D, 0x83, 0x1F, 0x04, // compact profile of Wh
+            0x62, 0x0F, 0x64, 0x00,
+        ];
+        assert_eq!(
+            points(&data),
+            [(
+                None,
+                "2024-12-31T09:00".to_string(),
+                Some("9900".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn inverse_profile_steps_forward() {
+        let data = [
+            0x04, 0x13, 0xE8, 0x03, 0x00, 0x00, // 1 m³
+            0x02, 0x6C, 0x3F, 0x3C, // 31 December 2025
+            0x0D, 0x93, 0x13, 0x04, // inverse compact profile of m³ / 1000
+            0xF1, 0xFE, // signed 8-bit differences, monthly
+            0x0A, 0xFB,
+        ];
+        assert_eq!(
+            points(&data),
+            [
+                (
+                    None,
+                    "2026-01-31T00:00".to_string(),
+                    Some("1.01".to_string())
+                ),
+                (
+                    None,
+                    "2026-02-28T00:00".to_string(),
+                    Some("1.005".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn absolute_values_with_register_numbers() {
+        let data = [
+            0x82, 0x01, 0x6C, 0x21, 0x3C, // 1 December 2025 at storage 2
+            0x8D, 0x01, 0x93, 0x1E, 0x0B, // compact profile with register numbers
+            0x3B, 0x01, // 6-digit BCD every day
+            0x00, 0x10, 0x00, 0x00, 0x09, 0x00, 0xF0, 0xFF, 0xFF,
+        ];
+        assert_eq!(
+            points(&data),
+            [
+                (
+                    Some(3),
+                    "2025-11-30T00:00".to_string(),
+                    Some("1".to_string())
+                ),
+                (
+                    Some(4),
+                    "2025-11-29T00:00".to_string(),
+                    Some("0.9".to_string())
+                ),
+                (Some(5), "2025-11-28T00:00".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_profiles_are_reported() {
+        let reserved_spacing = [0x0D, 0x93, 0x1F, 0x02, 0x01, 0xFB];
+        let odd_elements = [0x0D, 0x93, 0x1F, 0x03, 0x02, 0x01, 0x00];
+        for (data, error) in [
+            (
+                reserved_spacing.as_slice(),
+                CompactProfileError::ReservedSpacingValue(0xFB),
+            ),
+            (odd_elements.as_slice(), CompactProfileError::Truncated),
+        ] {
+            let record = DataRecord::try_from(data).unwrap();
+            assert_eq!(CompactProfile::try_from(&record), Err(error));
+        }
+    }
+
+    #[test]
+    fn calendar_arithmetic() {
+        let time = Timestamp {
+            year: 2024,
+            month: 3,
+            day: 31,
+            hour: 0,
+            minute: 30,
+            second: 0,
+        };
+        let earlier = time.add_seconds(-3600).unwrap();
+        assert_eq!((earlier.day, earlier.hour, earlier.minute), (30, 23, 30));
+        let february = time.add_months(-1).unwrap();
+        assert_eq!((february.month, february.day), (2, 29));
+        let next_year = time.add_seconds(366 * 86_400).unwrap();
+        assert_eq!(
+            (next_year.year, next_year.month, next_year.day),
+            (2025, 4, 1)
+        );
+    }
+}
@@ -0,0 +1,814 @@
+//! DLMS/COSEM payloads (CI `0x60`, `0x61`, `0x7C` and `0x7D`).
+//!
+//! The transport header is followed by a DLMS APDU whose values are encoded
+//! in A-XDR. [`AxdrValue`] decodes the common data types without allocating;
+//! arrays and structures are decoded lazily through [`AxdrSequence`].
+//! [`CosemRecords`] walks the body of a data notification or get response and
+//! yields every value that is preceded by an OBIS code.
+
+use arrayvec::ArrayVec;
+
+use crate::obis::ObisCode;
+use crate::value_information::{unit, Unit, UnitName};
+
+/// Maximum nesting of arrays and structures.
+pub const MAX_NESTING: usize = 16;
+
+const DATA_NOTIFICATION: u8 = 0x0F;
+const GET_RESPONSE: u8 = 0xC4;
+const GET_RESPONSE_NORMAL: u8 = 0x01;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum CosemError {
+    /// The A-XDR type tag is not supported.
+    UnsupportedDataType {
+        tag: u8,
+    },
+    /// Only data notifications and normal get responses carry decodable data.
+    UnsupportedApdu {
+        tag: u8,
+    },
+    /// The get response carries a data access result instead of data.
+    DataAccessResult {
+        result: u8,
+    },
+    /// A length field uses more bytes than supported.
+    InvalidLength,
+    /// Arrays and structures are nested deeper than [`MAX_NESTING`].
+    NestingTooDeep,
+    InsufficientData,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for CosemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CosemError::UnsupportedDataType { tag } => {
+                write!(f, "Unsupported A-XDR data type: {:02X}", tag)
+            }
+            CosemError::UnsupportedApdu { tag } => write!(f, "Unsupported APDU: {:02X}", tag),
+            CosemError::DataAccessResult { result } => {
+                write!(f, "Data access result: {}", result)
+            }
+            CosemError::InvalidLength => write!(f, "Invalid A-XDR length"),
+            CosemError::NestingTooDeep => write!(f, "A-XDR data nested too deep"),
+            CosemError::InsufficientData => write!(f, "Insufficient data"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CosemError {}
+
+/// COSEM date. Fields set to "not specified" are `None`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CosemDate {
+    pub year: Option<u16>,
+    /// Month, or `0xFD`/`0xFE` for the end/begin of daylight saving time.
+    pub month: Option<u8>,
+    /// Day of month, or `0xFD`/`0xFE` for the second last/last day.
+    pub day: Option<u8>,
+    /// Day of week, Monday is 1.
+    pub day_of_week: Option<u8>,
+}
+
+/// COSEM time. Fields set to "not specified" are `None`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CosemTime {
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub second: Option<u8>,
+    pub hundredths: Option<u8>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CosemDateTime {
+    pub date: CosemDate,
+    pub time: CosemTime,
+    /// Minutes from local time to UTC, i.e. UTC = local time + deviation.
+    pub deviation: Option<i16>,
+    pub clock_status: Option<u8>,
+}
+
+const fn specified(value: u8) -> Option<u8> {
+    if value == 0xFF {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+impl CosemDate {
+    fn parse(data: &[u8]) -> Result<Self, CosemError> {
+        match data {
+            [year_high, year_low, month, day, day_of_week, ..] => {
+                let year = u16::from_be_bytes([*year_high, *year_low]);
+                Ok(Self {
+                    year: if year == 0xFFFF { None } else { Some(year) },
+                    month: specified(*month),
+                    day: specified(*day),
+                    day_of_week: specified(*day_of_week),
+                })
+            }
+            _ => Err(CosemError::InsufficientData),
+        }
+    }
+}
+
+impl CosemTime {
+    fn parse(data: &[u8]) -> Result<Self, CosemError> {
+        match data {
+            [hour, minute, second, hundredths, ..] => Ok(Self {
+                hour: specified(*hour),
+                minute: specified(*minute),
+                second: specified(*second),
+                hundredths: specified(*hundredths),
+            }),
+            _ => Err(CosemError::InsufficientData),
+        }
+    }
+}
+
+impl CosemDateTime {
+    /// Parses the 12-byte date-time encoding, also used in octet strings.
+    pub fn parse(data: &[u8]) -> Result<Self, CosemError> {
+        let date = CosemDate::parse(data)?;
+        let time = CosemTime::parse(data.get(5..).ok_or(CosemError::InsufficientData)?)?;
+        let deviation = i16::from_be_bytes([
+            *data.get(9).ok_or(CosemError::InsufficientData)?,
+            *data.get(10).ok_or(CosemError::InsufficientData)?,
+        ]);
+        let clock_status = *data.get(11).ok_or(CosemError::InsufficientData)?;
+        Ok(Self {
+            date,
+            time,
+            deviation: if deviation == i16::MIN {
+                None
+            } else {
+                Some(deviation)
+            },
+            clock_status: specified(clock_status),
+        })
+    }
+}
+
+/// A decoded A-XDR value.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum AxdrValue<'a> {
+    Null,
+    Array(AxdrSequence<'a>),
+    Structure(AxdrSequence<'a>),
+    Boolean(bool),
+    BitString { bits: usize, data: &'a [u8] },
+    DoubleLong(i32),
+    DoubleLongUnsigned(u32),
+    OctetString(&'a [u8]),
+    VisibleString(&'a [u8]),
+    Utf8String(&'a [u8]),
+    Bcd(u8),
+    Integer(i8),
+    Long(i16),
+    Unsigned(u8),
+    LongUnsigned(u16),
+    Long64(i64),
+    Long64Unsigned(u64),
+    Enum(u8),
+    Float32(f32),
+    Float64(f64),
+    DateTime(CosemDateTime),
+    Date(CosemDate),
+    Time(CosemTime),
+}
+
+/// Elements of an A-XDR array or structure.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AxdrSequence<'a> {
+    pub count: usize,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    data: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    depth: usize,
+}
+
+impl<'a> AxdrSequence<'a> {
+    #[must_use]
+    pub const fn iter(&self) -> AxdrSequenceIter<'a> {
+        AxdrSequenceIter {
+            remaining: self.count,
+            data: self.data,
+            depth: self.depth,
+        }
+    }
+}
+
+impl<'a> IntoIterator for AxdrSequence<'a> {
+    type Item = Result<AxdrValue<'a>, CosemError>;
+    type IntoIter = AxdrSequenceIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AxdrSequenceIter<'a> {
+    remaining: usize,
+    data: &'a [u8],
+    depth: usize,
+}
+
+impl<'a> AxdrSequenceIter<'a> {
+    /// Returns the next element together with its encoded bytes.
+    fn next_encoded(&mut self) -> Option<Result<(AxdrValue<'a>, &'a [u8]), CosemError>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        match AxdrValue::parse_nested(self.data, self.depth) {
+            Ok((value, size)) => {
+                let (encoded, rest) = self.data.split_at(size);
+                self.data = rest;
+                Some(Ok((value, encoded)))
+            }
+            Err(error) => {
+                self.remaining = 0;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for AxdrSequenceIter<'a> {
+    type Item = Result<AxdrValue<'a>, CosemError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_encoded()
+            .map(|result| result.map(|(value, _)| value))
+    }
+}
+
+/// Decodes an A-XDR length: one byte below `0x80`, otherwise `0x8N`
+/// followed by N big-endian bytes. Returns the length and its size.
+fn parse_length(data: &[u8]) -> Result<(usize, usize), CosemError> {
+    let first = *data.first().ok_or(CosemError::InsufficientData)?;
+    if first & 0x80 == 0 {
+        return Ok((first as usize, 1));
+    }
+    let size = (first & 0x7F) as usize;
+    if size == 0 || size > core::mem::size_of::<u32>() {
+        return Err(CosemError::InvalidLength);
+    }
+    let length = data
+        .get(1..=size)
+        .ok_or(CosemError::InsufficientData)?
+        .iter()
+        .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+    Ok((length, 1 + size))
+}
+
+fn byte(data: &[u8]) -> Result<u8, CosemError> {
+    data.get(1).copied().ok_or(CosemError::InsufficientData)
+}
+
+fn fixed<const N: usize>(data: &[u8]) -> Result<[u8; N], CosemError> {
+    data.get(1..=N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(CosemError::InsufficientData)
+}
+
+impl<'a> AxdrValue<'a> {
+    /// Decodes one value and returns it with the number of bytes it occupies.
+    pub fn parse(data: &'a [u8]) -> Result<(Self, usize), CosemError> {
+        Self::parse_nested(data, 0)
+    }
+
+    fn parse_nested(data: &'a [u8], depth: usize) -> Result<(Self, usize), CosemError> {
+        let tag = *data.first().ok_or(CosemError::InsufficientData)?;
+        let value = match tag {
+            0x00 => (Self::Null, 1),
+            0x01 | 0x02 => {
+                if depth >= MAX_NESTING {
+                    return Err(CosemError::NestingTooDeep);
+                }
+                let (count, length_size) =
+                    parse_length(data.get(1..).ok_or(CosemError::InsufficientData)?)?;
+                let start = 1 + length_size;
+                let elements = data.get(start..).ok_or(CosemError::InsufficientData)?;
+                let mut size = 0;
+                for _ in 0..count {
+                    let element = elements.get(size..).ok_or(CosemError::InsufficientData)?;
+                    size += Self::parse_nested(element, depth + 1)?.1;
+                }
+                let sequence = AxdrSequence {
+                    count,
+                    data: elements.get(..size).ok_or(CosemError::InsufficientData)?,
+                    depth: depth + 1,
+                };
+                let value = if tag == 0x01 {
+                    Self::Array(sequence)
+                } else {
+                    Self::Structure(sequence)
+                };
+                (value, start + size)
+            }
+            0x03 => (Self::Boolean(byte(data)? != 0), 2),
+            0x04 => {
+                let (bits, length_size) =
+                    parse_length(data.get(1..).ok_or(CosemError::InsufficientData)?)?;
+                let start = 1 + length_size;
+                let end = start
+                    .checked_add(bits.div_ceil(8))
+                    .ok_or(CosemError::InvalidLength)?;
+                let bytes = data.get(start..end).ok_or(CosemError::InsufficientData)?;
+                (Self::BitString { bits, data: bytes }, end)
+            }
+            0x05 => (Self::DoubleLong(i32::from_be_bytes(fixed(data)?)), 5),
+            0x06 => (
+                Self::DoubleLongUnsigned(u32::from_be_bytes(fixed(data)?)),
+                5,
+            ),
+            0x09 | 0x0A | 0x0C => {
+                let (length, length_size) =
+                    parse_length(data.get(1..).ok_or(CosemError::InsufficientData)?)?;
+                let start = 1 + length_size;
+                let end = start.checked_add(length).ok_or(CosemError::InvalidLength)?;
+                let bytes = data.get(start..end).ok_or(CosemError::InsufficientData)?;
+                let value = match tag {
+                    0x09 => Self::OctetString(bytes),
+                    0x0A => Self::VisibleString(bytes),
+                    _ => Self::Utf8String(bytes),
+                };
+                (value, end)
+            }
+            0x0D => (Self::Bcd(byte(data)?), 2),
+            0x0F => (Self::Integer(i8::from_be_bytes(fixed(data)?)), 2),
+            0x10 => (Self::Long(i16::from_be_bytes(fixed(data)?)), 3),
+            0x11 => (Self::Unsigned(byte(data)?), 2),
+            0x12 => (Self::LongUnsigned(u16::from_be_bytes(fixed(data)?)), 3),
+            0x14 => (Self::Long64(i64::from_be_bytes(fixed(data)?)), 9),
+            0x15 => (Self::Long64Unsigned(u64::from_be_bytes(fixed(data)?)), 9),
+            0x16 => (Self::Enum(byte(data)?), 2),
+            0x17 => (Self::Float32(f32::from_be_bytes(fixed(data)?)), 5),
+            0x18 => (Self::Float64(f64::from_be_bytes(fixed(data)?)), 9),
+            0x19 => (
+                Self::DateTime(CosemDateTime::parse(&fixed::<12>(data)?)?),
+                13,
+            ),
+            0x1A => (Self::Date(CosemDate::parse(&fixed::<5>(data)?)?), 6),
+            0x1B => (Self::Time(CosemTime::parse(&fixed::<4>(data)?)?), 5),
+            _ => return Err(CosemError::UnsupportedDataType { tag }),
+        };
+        Ok(value)
+    }
+
+    /// Returns the value as a signed integer if it is an integer type.
+    #[must_use]
+    pub fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Self::DoubleLong(value) => Some(value.into()),
+            Self::DoubleLongUnsigned(value) => Some(value.into()),
+            Self::Integer(value) => Some(value.into()),
+            Self::Long(value) => Some(value.into()),
+            Self::Unsigned(value) | Self::Enum(value) => Some(value.into()),
+            Self::LongUnsigned(value) => Some(value.into()),
+            Self::Long64(value) => Some(value.into()),
+            Self::Long64Unsigned(value) => Some(value.into()),
+            _ => None,
+        }
+    }
+
+    /// Interprets an octet string of 12 bytes as a date-time, as is common
+    /// for clock values.
+    #[must_use]
+    pub fn as_date_time(&self) -> Option<CosemDateTime> {
+        match *self {
+            Self::DateTime(date_time) => Some(date_time),
+            Self::OctetString(bytes) if bytes.len() == 12 => CosemDateTime::parse(bytes).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Scaler and unit of a COSEM register: value × 10^scaler in `unit`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScalerUnit {
+    pub scaler: i8,
+    pub unit: u8,
+}
+
+impl ScalerUnit {
+    fn from_value(value: &AxdrValue<'_>) -> Option<Self> {
+        let AxdrValue::Structure(sequence) = value else {
+            return None;
+        };
+        let mut elements = sequence.iter();
+        match (elements.next(), elements.next(), elements.next()) {
+            (Some(Ok(AxdrValue::Integer(scaler))), Some(Ok(AxdrValue::Enum(unit))), None) => {
+                Some(Self { scaler, unit })
+            }
+            _ => None,
+        }
+    }
+
+    /// Units of the DLMS unit enumeration, see [`units`].
+    #[must_use]
+    pub const fn units(&self) -> Option<&'static [Unit]> {
+        units(self.unit)
+    }
+
+    /// Symbol of a DLMS unit without counterpart in [`units`], `None` for
+    /// unitless counts and reserved codes.
+    #[must_use]
+    pub const fn unit_symbol(&self) -> Option<&'static str> {
+        unit_symbol(self.unit)
+    }
+}
+
+/// Maps the DLMS unit enumeration to the units of the value information
+/// block, e.g. `m3/h` to cubic meters per hour, so that they are rendered like
+/// M-Bus units.
+#[must_use]
+pub const fn units(unit: u8) -> Option<&'static [Unit]> {
+    Some(match unit {
+        1 => &[unit!(Year)],
+        2 => &[unit!(Month)],
+        3 => &[unit!(Week)],
+        4 => &[unit!(Day)],
+        5 => &[unit!(Hour)],
+        6 => &[unit!(Minute)],
+        7 => &[unit!(Second)],
+        9 => &[unit!(Celsius)],
+        11 => &[unit!(Meter)],
+        12 => &[unit!(Meter), unit!(Second ^ -1)],
+        13 | 14 => &[unit!(Meter ^ 3)],
+        15 | 16 => &[unit!(Meter ^ 3), unit!(Hour ^ -1)],
+        17 | 18 => &[unit!(Meter ^ 3), unit!(Day ^ -1)],
+        19 => &[unit!(Liter)],
+        20 => &[unit!(Kilogram)],
+        23 => &[unit!(Pascal)],
+        24 => &[unit!(Bar)],
+        25 => &[unit!(Joul)],
+        26 => &[unit!(Joul), unit!(Hour ^ -1)],
+        27 => &[unit!(Watt)],
+        30 => &[unit!(Watt), unit!(Hour)],
+        33 => &[unit!(Ampere)],
+        35 => &[unit!(Volt)],
+        36 => &[unit!(Volt), unit!(Meter ^ -1)],
+        42 => &[unit!(Ampere), unit!(Meter ^ -1)],
+        44 => &[unit!(Hertz)],
+        50 => &[unit!(Kilogram), unit!(Second ^ -1)],
+        52 => &[unit!(Kelvin)],
+        56 => &[unit!(Percent)],
+        _ => return None,
+    })
+}
+
+/// Maps DLMS units without counterpart in [`units`] to UCUM symbols.
+#[must_use]
+pub const fn unit_symbol(unit: u8) -> Option<&'static str> {
+    Some(match unit {
+        8 => "deg",
+        21 => "N",
+        22 => "N.m",
+        28 => "V.A",
+        29 => "var",
+        31 => "V.A.h",
+        32 => "var.h",
+        34 => "C",
+        37 => "F",
+        38 => "Ohm",
+        40 => "Wb",
+        41 => "T",
+        43 => "H",
+        51 => "S",
+        57 => "A.h",
+        _ => return None,
+    })
+}
+
+/// A data notification pushed by the meter.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DataNotification<'a> {
+    pub long_invoke_id_and_priority: u32,
+    pub date_time: Option<CosemDateTime>,
+    pub body: AxdrValue<'a>,
+}
+
+/// The DLMS APDU following the transport header.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CosemPayload<'a> {
+    pub apdu: &'a [u8],
+}
+
+impl<'a> CosemPayload<'a> {
+    #[must_use]
+    pub fn apdu_tag(&self) -> Option<u8> {
+        self.apdu.first().copied()
+    }
+
+    pub fn data_notification(&self) -> Result<DataNotification<'a>, CosemError> {
+        let tag = self.apdu_tag().ok_or(CosemError::InsufficientData)?;
+        if tag != DATA_NOTIFICATION {
+            return Err(CosemError::UnsupportedApdu { tag });
+        }
+        let long_invoke_id_and_priority = u32::from_be_bytes(fixed(self.apdu)?);
+        let (date_time, body_offset) = match self.apdu.get(5) {
+            Some(0x00) => (None, 6),
+            Some(12) => (
+                Some(CosemDateTime::parse(
+                    self.apdu.get(6..18).ok_or(CosemError::InsufficientData)?,
+                )?),
+                18,
+            ),
+            Some(_) => return Err(CosemError::InvalidLength),
+            None => return Err(CosemError::InsufficientData),
+        };
+        let body = self
+            .apdu
+            .get(body_offset..)
+            .ok_or(CosemError::InsufficientData)?;
+        Ok(DataNotification {
+            long_invoke_id_and_priority,
+            date_time,
+            body: AxdrValue::parse(body)?.0,
+        })
+    }
+
+    /// Returns the data of a data notification or a normal get response.
+    pub fn body(&self) -> Result<AxdrValue<'a>, CosemError> {
+        match self.apdu {
+            [DATA_NOTIFICATION, ..] => Ok(self.data_notification()?.body),
+            [GET_RESPONSE, GET_RESPONSE_NORMAL, _invoke_id, 0x00, data @ ..] => {
+                Ok(AxdrValue::parse(data)?.0)
+            }
+            [GET_RESPONSE, GET_RESPONSE_NORMAL, _invoke_id, 0x01, result, ..] => {
+                Err(CosemError::DataAccessResult { result: *result })
+            }
+            [tag, ..] => Err(CosemError::UnsupportedApdu { tag: *tag }),
+            [] => Err(CosemError::InsufficientData),
+        }
+    }
+
+    #[must_use]
+    pub fn records(&self) -> CosemRecords<'a> {
+        CosemRecords::new(self.body())
+    }
+}
+
+/// A value identified by an OBIS code.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CosemRecord<'a> {
+    pub obis: ObisCode,
+    pub value: AxdrValue<'a>,
+    /// Scaler and unit following the value, if present.
+    pub scaler_unit: Option<ScalerUnit>,
+    /// A-XDR encoding of the value.
+    pub data: &'a [u8],
+}
+
+#[derive(Debug, Clone)]
+struct Level<'a> {
+    elements: AxdrSequenceIter<'a>,
+    pending: Option<(AxdrValue<'a>, &'a [u8])>,
+}
+
+impl<'a> Level<'a> {
+    fn next(&mut self) -> Option<Result<(AxdrValue<'a>, &'a [u8]), CosemError>> {
+        match self.pending.take() {
+            Some(element) => Some(Ok(element)),
+            None => self.elements.next_encoded(),
+        }
+    }
+}
+
+/// Iterates over the OBIS-coded values of a COSEM payload.
+///
+/// An octet string of six bytes is taken as an OBIS code for the element
+/// following it in the same array or structure. A structure of scaler and
+/// unit directly after the value is attached to the record.
+#[derive(Debug, Clone)]
+pub struct CosemRecords<'a> {
+    levels: ArrayVec<Level<'a>, MAX_NESTING>,
+    error: Option<CosemError>,
+}
+
+impl<'a> CosemRecords<'a> {
+    fn new(body: Result<AxdrValue<'a>, CosemError>) -> Self {
+        let mut records = Self {
+            levels: ArrayVec::new(),
+            error: None,
+        };
+        match body {
+            Ok(AxdrValue::Array(sequence) | AxdrValue::Structure(sequence)) => {
+                records.levels.push(Level {
+                    elements: sequence.iter(),
+                    pending: None,
+                });
+            }
+            Ok(_) => {}
+            Err(error) => records.error = Some(error),
+        }
+        records
+    }
+
+    fn fail(&mut self, error: CosemError) -> Option<Result<CosemRecord<'a>, CosemError>> {
+        self.levels.clear();
+        Some(Err(error))
+    }
+}
+
+impl<'a> Iterator for CosemRecords<'a> {
+    type Item = Result<CosemRecord<'a>, CosemError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+        loop {
+            let level = self.levels.last_mut()?;
+            let value = match level.next() {
+                None => {
+                    self.levels.pop();
+                    continue;
+                }
+                Some(Err(error)) => return self.fail(error),
+                Some(Ok((value, _))) => value,
+            };
+            match value {
+                AxdrValue::OctetString(code) if code.len() == 6 => {
+                    let Ok(obis) = ObisCode::try_from(code) else {
+                        continue;
+                    };
+                    let (value, data) = match level.next() {
+                        None => continue,
+                        Some(Err(error)) => return self.fail(error),
+                        Some(Ok(element)) => element,
+                    };
+                    let scaler_unit = match level.next() {
+                        None => None,
+                        Some(Err(error)) => return self.fail(error),
+                        Some(Ok(element)) => {
+                            let scaler_unit = ScalerUnit::from_value(&element.0);
+                            if scaler_unit.is_none() {
+                                level.pending = Some(element);
+                            }
+                            scaler_unit
+                        }
+                    };
+                    return Some(Ok(CosemRecord {
+                        obis,
+                        value,
+                        scaler_unit,
+                        data,
+                    }));
+                }
+                AxdrValue::Array(sequence) | AxdrValue::Structure(sequence) => {
+                    // Nesting is bounded by the parser, so this cannot overflow.
+                    let _ = self.levels.try_push(Level {
+                        elements: sequence.iter(),
+                        pending: None,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_axdr_types() {
+        let (value, size) = AxdrValue::parse(&[0x06, 0x00, 0x01, 0x02, 0x03]).unwrap();
+        assert_eq!(value, AxdrValue::DoubleLongUnsigned(0x0001_0203));
+        assert_eq!(size, 5);
+
+        let (value, size) = AxdrValue::parse(&[0x10, 0xFF, 0xFE]).unwrap();
+        assert_eq!(value, AxdrValue::Long(-2));
+        assert_eq!(size, 3);
+
+        let (value, _) = AxdrValue::parse(&[0x09, 0x81, 0x02, 0xAB, 0xCD]).unwrap();
+        assert_eq!(value, AxdrValue::OctetString(&[0xAB, 0xCD]));
+
+        let date_time = [
+            0x19, 0x07, 0xE8, 0x03, 0x0F, 0x05, 0x0C, 0x1E, 0x00, 0xFF, 0xFF, 0xC4, 0x00,
+        ];
+        let (value, size) = AxdrValue::parse(&date_time).unwrap();
+        assert_eq!(size, 13);
+        let date_time = value.as_date_time().unwrap();
+        assert_eq!(date_time.date.year, Some(2024));
+        assert_eq!(date_time.time.hour, Some(12));
+        assert_eq!(date_time.time.hundredths, None);
+        assert_eq!(date_time.deviation, Some(-60));
+
+        let (value, size) =
+            AxdrValue::parse(&[0x02, 0x02, 0x11, 0x05, 0x01, 0x01, 0x16, 0x1E]).unwrap();
+        assert_eq!(size, 8);
+        let AxdrValue::Structure(sequence) = value else {
+            panic!("expected structure, got {value:?}");
+        };
+        let elements: Vec<_> = sequence.iter().map(Result::unwrap).collect();
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0], AxdrValue::Unsigned(5));
+
+        assert_eq!(
+            AxdrValue::parse(&[0x13, 0x00]),
+            Err(CosemError::UnsupportedDataType { tag: 0x13 })
+        );
+        assert_eq!(
+            AxdrValue::parse(&[0x02, 0x02, 0x11]),
+            Err(CosemError::InsufficientData)
+        );
+    }
+
+    #[test]
+    fn rejects_excessive_nesting() {
+        let mut data = Vec::new();
+        for _ in 0..=MAX_NESTING {
+            data.extend_from_slice(&[0x02, 0x01]);
+        }
+        data.push(0x00);
+        assert_eq!(AxdrValue::parse(&data), Err(CosemError::NestingTooDeep));
+    }
+
+    #[test]
+    fn rejects_lengths_beyond_the_data() {
+        let data = [0x09, 0x84, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+        assert_eq!(AxdrValue::parse(&data), Err(CosemError::InsufficientData));
+        let data = [0x04, 0x84, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+        assert_eq!(AxdrValue::parse(&data), Err(CosemError::InsufficientData));
+        let data = [0x09, 0x85, 0x01, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(AxdrValue::parse(&data), Err(CosemError::InvalidLength));
+    }
+
+    #[test]
+    fn data_notification_yields_obis_records() {
+        let apdu = [
+            0x0F, 0x00, 0x00, 0x00, 0x01, 0x00, // data notification without date-time
+            0x01, 0x02, // array of two registers
+            0x02, 0x03, // structure: OBIS, value, scaler/unit
+            0x09, 0x06, 0x01, 0x00, 0x01, 0x08, 0x00, 0xFF, //
+            0x06, 0x00, 0x00, 0x30, 0x39, //
+            0x02, 0x02, 0x0F, 0x00, 0x16, 0x1E, //
+            0x02, 0x02, // structure: OBIS, value
+            0x09, 0x06, 0x00, 0x00, 0x60, 0x01, 0x00, 0xFF, //
+            0x0A, 0x03, 0x41, 0x42, 0x43,
+        ];
+        let payload = CosemPayload { apdu: &apdu };
+        let notification = payload.data_notification().unwrap();
+        assert_eq!(notification.long_invoke_id_and_priority, 1);
+        assert_eq!(notification.date_time, None);
+
+        let records: Vec<_> = payload.records().map(Result::unwrap).collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].obis, ObisCode::new(1, 0, 1, 8, 0, 255));
+        assert_eq!(records[0].obis.to_string(), "1-0:1.8.0*255");
+        assert_eq!(records[0].value, AxdrValue::DoubleLongUnsigned(12345));
+        assert_eq!(records[0].data, &[0x06, 0x00, 0x00, 0x30, 0x39]);
+        let scaler_unit = records[0].scaler_unit.unwrap();
+        assert_eq!(scaler_unit.scaler, 0);
+        assert_eq!(
+            scaler_unit.units(),
+            Some([unit!(Watt), unit!(Hour)].as_slice())
+        );
+        assert_eq!(scaler_unit.unit_symbol(), None);
+        assert_eq!(records[1].value, AxdrValue::VisibleString(b"ABC"));
+        assert_eq!(records[1].scaler_unit, None);
+    }
+
+    #[test]
+    fn get_response_with_access_error() {
+        let payload = CosemPayload {
+            apdu: &[0xC4, 0x01, 0xC1, 0x01, 0x04],
+        };
+        assert_eq!(
+            payload.records().next(),
+            Some(Err(CosemError::DataAccessResult { result: 4 }))
+        );
+    }
+}
@@ -217,7 +217,7 @@ impl TryFrom<&DataInformationBlock<'_>> for DataInformation {
                     return Err(DataInformationError::DataTooLong);
                 }
                 let dife = dife.data;
-                storage_number += u64::from(dife & 0x0f) << ((extension_index * 4) + 1);
+                storage_number += u64::from(dife & 0x0f) << (((extension_index - 1) * 4) + 1);
                 tariff |= u64::from((dife & 0x30) >> 4) << (tariff_index);
                 tariff_index += 2;
                 device |= u64::from((dife & 0x40) >> 6) << device_index;
@@ -281,6 +281,12 @@ impl<'a> TextUnit<'a> {
     pub const fn new(input: &'a [u8]) -> Self {
         Self(input)
     }
+
+    /// Returns the transmitted bytes, last character first.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
 }
 
 impl PartialEq<str> for TextUnit<'_> {
@@ -367,14 +373,95 @@ pub enum SingleEveryOrInvalid<T> {
     Invalid(),
 }
 
+/// Flags of type F and I date and time values.
+///
+/// Type F carries the time invalid (IV) flag in bit 7 of the minute byte and
+/// the summer time (SU) flag in bit 7 of the hour byte. Type I carries IV in
+/// bit 7 of the minute byte, the leap year flag in bit 6 of the second byte,
+/// the day of the week in bits 5 to 7 of the hour byte and SU in bit 7 of the
+/// week byte.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimeFlags {
+    /// The clock of the meter is not set, the time is not valid.
+    pub invalid: bool,
+    /// The time is summer (daylight saving) time.
+    pub summer_time: bool,
+    /// The year is a leap year, type I only.
+    pub leap_year: bool,
+    /// Day of the week, 1 for Monday to 7 for Sunday, type I only.
+    pub day_of_week: Option<u8>,
+}
+
+/// Type K daylight saving period.
+///
+/// Bits 0 to 4 of the first byte hold the hour summer time begins at, bits 6
+/// and 7 the signed deviation in hours. The following bytes hold the day the
+/// period begins, the months it begins (bits 0 to 3) and ends (bits 4 to 7)
+/// and the day it ends. The clocks are set back at the same UTC time they
+/// were put forward, so the period ends at the begin hour plus the deviation.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DaylightSaving {
+    pub begin_day: DayOfMonth,
+    pub begin_month: SingleEveryOrInvalid<Month>,
+    pub begin_hour: Hour,
+    pub end_day: DayOfMonth,
+    pub end_month: SingleEveryOrInvalid<Month>,
+    /// Deviation of summer time from standard time in hours.
+    pub deviation: i8,
+}
+
+impl DaylightSaving {
+    /// Hour of standard time the period ends at.
+    #[must_use]
+    pub const fn end_hour(&self) -> Hour {
+        // begin hours past 23 are invalid but must not underflow
+        (self.begin_hour % 24 + 24).wrapping_add_signed(self.deviation) % 24
+    }
+}
+
+/// Type L listening window of a wireless meter.
+///
+/// The first three bytes hold the start of the window as type J time,
+/// followed by the window length in seconds (16 bits) and a mask of the days
+/// of the week the window is open, bit 0 for Monday to bit 6 for Sunday.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ListeningWindow {
+    pub start_second: SingleEveryOrInvalid<Second>,
+    pub start_minute: SingleEveryOrInvalid<Minute>,
+    pub start_hour: SingleEveryOrInvalid<Hour>,
+    pub duration_seconds: u16,
+    pub weekdays: u8,
+}
+
+impl ListeningWindow {
+    /// Returns whether the window is open on an ISO 8601 day of the week,
+    /// 1 for Monday to 7 for Sunday.
+    #[must_use]
+    pub const fn is_open_on(&self, weekday: u8) -> bool {
+        matches!(weekday, 1..=7) && self.weekdays & (1 << (weekday - 1)) != 0
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum DataType<'a> {
     Text(TextUnit<'a>),
+    /// Type H real value.
     Number(f64),
+    /// Integer too wide for [`DataType::LargeInteger`].
     LossyNumber(f64),
+    /// Type A BCD or type B integer value of up to 64 bits.
+    Integer(i64),
+    /// Variable length integer value of up to 128 bits.
+    LargeInteger(i128),
     Date(
         SingleEveryOrInvalid<DayOfMonth>,
         SingleEveryOrInvalid<Month>,
@@ -391,6 +478,7 @@ pub enum DataType<'a> {
         SingleEveryOrInvalid<Year>,
         SingleEveryOrInvalid<Hour>,
         SingleEveryOrInvalid<Minute>,
+        TimeFlags,
     ),
     DateTimeWithSeconds(
         SingleEveryOrInvalid<DayOfMonth>,
@@ -399,7 +487,21 @@ pub enum DataType<'a> {
         SingleEveryOrInvalid<Hour>,
         SingleEveryOrInvalid<Minute>,
         SingleEveryOrInvalid<Second>,
+        TimeFlags,
     ),
+    /// Type M date and time with the offset of local time to UTC in minutes.
+    DateTimeWithOffset(
+        SingleEveryOrInvalid<DayOfMonth>,
+        SingleEveryOrInvalid<Month>,
+        SingleEveryOrInvalid<Year>,
+        SingleEveryOrInvalid<Hour>,
+        SingleEveryOrInvalid<Minute>,
+        SingleEveryOrInvalid<Second>,
+        i16,
+        TimeFlags,
+    ),
+    DaylightSaving(DaylightSaving),
+    ListeningWindow(ListeningWindow),
     ManufacturerSpecific(
         #[cfg_attr(
             feature = "serde",
@@ -408,6 +510,180 @@ pub enum DataType<'a> {
         &'a [u8],
     ),
 }
+impl DataType<'_> {
+    /// Returns the exact value of an integer, `None` for other values.
+    #[must_use]
+    pub const fn decimal(&self) -> Option<Decimal> {
+        match self {
+            Self::Integer(value) => Some(Decimal::new(*value as i128, 0)),
+            Self::LargeInteger(value) => Some(Decimal::new(*value, 0)),
+            _ => None,
+        }
+    }
+}
+
+/// Exact decimal number `significand * 10^exponent`.
+///
+/// Values are compared by representation, `10e0` and `1e1` are not equal.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Decimal {
+    pub significand: i128,
+    pub exponent: i16,
+}
+
+impl Decimal {
+    #[must_use]
+    pub const fn new(significand: i128, exponent: i16) -> Self {
+        Self {
+            significand,
+            exponent,
+        }
+    }
+
+    /// Multiplies the value by `10^exponent`.
+    #[must_use]
+    pub const fn scaled(self, exponent: i16) -> Self {
+        Self::new(self.significand, self.exponent.saturating_add(exponent))
+    }
+
+    /// Returns the same value with the given exponent, or `None` if it is
+    /// not representable.
+    #[must_use]
+    pub fn with_exponent(self, exponent: i16) -> Option<Self> {
+        let difference = u32::from(self.exponent.abs_diff(exponent));
+        let factor = 10i128.checked_pow(difference);
+        let significand = if exponent <= self.exponent {
+            self.significand.checked_mul(factor?)?
+        } else if self.significand == 0 {
+            0
+        } else {
+            let factor = factor?;
+            if self.significand % factor != 0 {
+                return None;
+            }
+            self.significand / factor
+        };
+        Some(Self::new(significand, exponent))
+    }
+
+    #[must_use]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let exponent = self.exponent.min(other.exponent);
+        let left = self.with_exponent(exponent)?;
+        let right = other.with_exponent(exponent)?;
+        Some(Self::new(
+            left.significand.checked_add(right.significand)?,
+            exponent,
+        ))
+    }
+
+    #[must_use]
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.significand.checked_mul(other.significand)?,
+            self.exponent.checked_add(other.exponent)?,
+        ))
+    }
+
+    /// Divides by `other`, or returns `None` if the quotient has no exact
+    /// decimal representation with up to 18 additional fractional digits.
+    #[must_use]
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.significand == 0 {
+            return None;
+        }
+        let mut significand = self.significand;
+        let mut exponent = self.exponent.checked_sub(other.exponent)?;
+        for _ in 0..=18 {
+            if significand % other.significand == 0 {
+                return Some(Self::new(significand / other.significand, exponent));
+            }
+            significand = significand.checked_mul(10)?;
+            exponent = exponent.checked_sub(1)?;
+        }
+        None
+    }
+
+    /// Returns the same value with trailing zeros of the significand moved
+    /// into the exponent.
+    #[must_use]
+    pub const fn normalized(self) -> Self {
+        if self.significand == 0 {
+            return Self::new(0, 0);
+        }
+        let mut value = self;
+        while value.significand % 10 == 0 && value.exponent < i16::MAX {
+            value.significand /= 10;
+            value.exponent += 1;
+        }
+        value
+    }
+
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        let mut value = self.significand as f64;
+        let mut exponent = self.exponent;
+        while exponent > 0 {
+            value *= 10.0;
+            exponent -= 1;
+        }
+        while exponent < 0 {
+            value /= 10.0;
+            exponent += 1;
+        }
+        value
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Self::new(i128::from(value), 0)
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(value: u64) -> Self {
+        Self::new(i128::from(value), 0)
+    }
+}
+
+impl From<i128> for Decimal {
+    fn from(value: i128) -> Self {
+        Self::new(value, 0)
+    }
+}
+
+/// Plain decimal notation without exponent and trailing fractional zeros.
+#[cfg(feature = "std")]
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.normalized();
+        if value.significand < 0 {
+            write!(f, "-")?;
+        }
+        let magnitude = value.significand.unsigned_abs();
+        if value.exponent >= 0 {
+            write!(f, "{magnitude}")?;
+            for _ in 0..value.exponent {
+                write!(f, "0")?;
+            }
+            return Ok(());
+        }
+        let places = usize::from(value.exponent.unsigned_abs());
+        match 10u128.checked_pow(u32::from(value.exponent.unsigned_abs())) {
+            Some(divisor) => write!(
+                f,
+                "{}.{:0>places$}",
+                magnitude / divisor,
+                magnitude % divisor
+            ),
+            None => write!(f, "0.{magnitude:0>places$}"),
+        }
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -434,20 +710,69 @@ impl std::fmt::Display for Data<'_> {
             Some(value) => match value {
                 DataType::Number(value) => write!(f, "{}", value),
                 DataType::LossyNumber(value) => write!(f, "{}", value),
+                DataType::Integer(value) => write!(f, "{}", value),
+                DataType::LargeInteger(value) => write!(f, "{}", value),
                 DataType::Date(day, month, year) => write!(f, "{}/{}/{}", day, month, year),
-                DataType::DateTime(day, month, year, hour, minute) => {
-                    write!(f, "{}/{}/{} {}:{}:00", day, month, year, hour, minute)
+                DataType::DateTime(day, month, year, hour, minute, flags) => {
+                    write!(f, "{}/{}/{} {}:{}:00", day, month, year, hour, minute)?;
+                    write!(f, "{}", flags)
                 }
-                DataType::DateTimeWithSeconds(day, month, year, hour, minute, second) => {
+                DataType::DateTimeWithSeconds(day, month, year, hour, minute, second, flags) => {
                     write!(
                         f,
                         "{}/{}/{} {}:{}:{}",
                         day, month, year, hour, minute, second
-                    )
+                    )?;
+                    write!(f, "{}", flags)
+                }
+                DataType::DateTimeWithOffset(
+                    day,
+                    month,
+                    year,
+                    hour,
+                    minute,
+                    second,
+                    offset,
+                    flags,
+                ) => {
+                    write!(
+                        f,
+                        "{}/{}/{} {}:{}:{} {}{:02}:{:02}",
+                        day,
+                        month,
+                        year,
+                        hour,
+                        minute,
+                        second,
+                        if *offset < 0 { '-' } else { '+' },
+                        offset.unsigned_abs() / 60,
+                        offset.unsigned_abs() % 60
+                    )?;
+                    write!(f, "{}", flags)
                 }
                 DataType::Time(seconds, minutes, hours) => {
                     write!(f, "{}:{}:{}", hours, minutes, seconds)
                 }
+                DataType::DaylightSaving(period) => write!(
+                    f,
+                    "{}/{} {}:00 - {}/{} {}:00 ({:+}h)",
+                    period.begin_day,
+                    period.begin_month,
+                    period.begin_hour,
+                    period.end_day,
+                    period.end_month,
+                    period.end_hour(),
+                    period.deviation
+                ),
+                DataType::ListeningWindow(window) => write!(
+                    f,
+                    "{}:{}:{} for {}s (days {:07b})",
+                    window.start_hour,
+                    window.start_minute,
+                    window.start_second,
+                    window.duration_seconds,
+                    window.weekdays
+                ),
                 DataType::Text(text_unit) => {
                     let text: String = (*text_unit).into();
                     write!(f, "{}", text)
@@ -461,6 +786,20 @@ impl std::fmt::Display for Data<'_> {
     }
 }
 
+/// Appends the summer time and invalid flags, nothing if both are clear.
+#[cfg(feature = "std")]
+impl std::fmt::Display for TimeFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.summer_time {
+            write!(f, " (summer time)")?;
+        }
+        if self.invalid {
+            write!(f, " (invalid)")?;
+        }
+        Ok(())
+    }
+}
+
 impl Data<'_> {
     #[must_use]
     pub const fn get_size(&self) -> usize {
@@ -480,24 +819,29 @@ macro_rules! parse_single_or_every {
 
 macro_rules! parse_month {
     ($input:expr) => {
-        match $input & 0xF {
-            0x1 => SingleEveryOrInvalid::Single(Month::January),
-            0x2 => SingleEveryOrInvalid::Single(Month::February),
-            0x3 => SingleEveryOrInvalid::Single(Month::March),
-            0x4 => SingleEveryOrInvalid::Single(Month::April),
-            0x5 => SingleEveryOrInvalid::Single(Month::May),
-            0x6 => SingleEveryOrInvalid::Single(Month::June),
-            0x7 => SingleEveryOrInvalid::Single(Month::July),
-            0x8 => SingleEveryOrInvalid::Single(Month::August),
-            0x9 => SingleEveryOrInvalid::Single(Month::September),
-            0xA => SingleEveryOrInvalid::Single(Month::October),
-            0xB => SingleEveryOrInvalid::Single(Month::November),
-            0xC => SingleEveryOrInvalid::Single(Month::December),
-            _ => SingleEveryOrInvalid::Invalid(),
-        }
+        month($input & 0xF)
     };
 }
 
+/// Returns the month numbered 1 to 12.
+pub(crate) const fn month(number: u8) -> SingleEveryOrInvalid<Month> {
+    match number {
+        0x1 => SingleEveryOrInvalid::Single(Month::January),
+        0x2 => SingleEveryOrInvalid::Single(Month::February),
+        0x3 => SingleEveryOrInvalid::Single(Month::March),
+        0x4 => SingleEveryOrInvalid::Single(Month::April),
+        0x5 => SingleEveryOrInvalid::Single(Month::May),
+        0x6 => SingleEveryOrInvalid::Single(Month::June),
+        0x7 => SingleEveryOrInvalid::Single(Month::July),
+        0x8 => SingleEveryOrInvalid::Single(Month::August),
+        0x9 => SingleEveryOrInvalid::Single(Month::September),
+        0xA => SingleEveryOrInvalid::Single(Month::October),
+        0xB => SingleEveryOrInvalid::Single(Month::November),
+        0xC => SingleEveryOrInvalid::Single(Month::December),
+        _ => SingleEveryOrInvalid::Invalid(),
+    }
+}
+
 macro_rules! parse_year {
     ($input:expr, $mask_byte1:expr, $mask_byte2:expr, $all_value:expr) => {{
         let byte1 = u16::from($input.get(1).copied().unwrap_or(0) & $mask_byte1);
@@ -510,6 +854,22 @@ macro_rules! parse_year {
         }
     }};
 }
+/// Expands the two-digit year of a date with the hundred-year field. Without
+/// hundred-year field, years 81 to 99 are read as 1981 to 1999 and earlier
+/// years as 2000 to 2080 for compatibility with older meters.
+fn calendar_year(year: SingleEveryOrInvalid<Year>, hundred_year: u8) -> SingleEveryOrInvalid<Year> {
+    match year {
+        SingleEveryOrInvalid::Single(year) if year > 99 => SingleEveryOrInvalid::Invalid(),
+        SingleEveryOrInvalid::Single(year) if hundred_year == 0 && year <= 80 => {
+            SingleEveryOrInvalid::Single(2000 + year)
+        }
+        SingleEveryOrInvalid::Single(year) => {
+            SingleEveryOrInvalid::Single(1900 + 100 * u16::from(hundred_year) + year)
+        }
+        other => other,
+    }
+}
+
 fn bcd_to_value_internal(
     data: &[u8],
     num_digits: usize,
@@ -520,8 +880,8 @@ fn bcd_to_value_internal(
         return Err(DataRecordError::InsufficientData);
     }
 
-    let mut data_value = 0.0;
-    let mut current_weight = 1.0;
+    let mut data_value = 0i64;
+    let mut current_weight = 1i64;
 
     for i in 0..num_digits {
         let index = if lsb_order {
@@ -543,37 +903,39 @@ fn bcd_to_value_internal(
             ));
         }
 
-        data_value += f64::from(digit) * current_weight;
-        current_weight *= 10.0;
+        data_value = data_value.saturating_add(i64::from(digit).saturating_mul(current_weight));
+        current_weight = current_weight.saturating_mul(10);
     }
 
-    let signed_value = data_value * sign as f64;
-
     Ok(Data {
-        value: Some(DataType::Number(signed_value)),
+        value: Some(DataType::Integer(data_value * i64::from(sign))),
         size: num_digits.div_ceil(2),
     })
 }
 
 fn integer_to_value_internal(data: &[u8], byte_size: usize) -> Data<'_> {
-    let mut data_value = 0i64;
-    let mut shift = 0;
-    for byte in data.iter().take(if byte_size > 8 { 8 } else { byte_size }) {
-        data_value |= (*byte as i64) << shift;
-        shift += 8;
-    }
-
-    let msb = (data_value >> (shift - 1)) & 1;
-    let data_value = if byte_size < 8 && msb == 1 {
-        -((data_value ^ (2i64.pow(shift) - 1)) + 1)
-    } else {
-        data_value
-    };
-
-    let output = if byte_size > 8 {
-        DataType::LossyNumber(data_value as f64)
+    let bytes = data.get(..byte_size).unwrap_or(data);
+    let negative = bytes.last().is_some_and(|byte| byte & 0x80 != 0);
+
+    let output = if byte_size > 16 {
+        let magnitude = bytes.iter().rev().fold(0.0, |value, byte| {
+            value * 256.0 + f64::from(if negative { !byte } else { *byte })
+        });
+        DataType::LossyNumber(if negative {
+            -(magnitude + 1.0)
+        } else {
+            magnitude
+        })
     } else {
-        DataType::Number(data_value as f64)
+        let initial: i128 = if negative { -1 } else { 0 };
+        let value = bytes
+            .iter()
+            .rev()
+            .fold(initial, |value, byte| (value << 8) | i128::from(*byte));
+        match i64::try_from(value) {
+            Ok(value) if byte_size <= 8 => DataType::Integer(value),
+            _ => DataType::LargeInteger(value),
+        }
     };
     Data {
         value: Some(output),
@@ -582,6 +944,47 @@ fn integer_to_value_internal(data: &[u8], byte_size: usize) -> Data<'_> {
 }
 
 impl DataFieldCoding {
+    /// Returns the length of the data field, read from the LVAR byte for
+    /// variable length data. Returns `None` for special functions, whose data
+    /// extends to the end of the records, and for reserved LVAR values.
+    #[must_use]
+    pub fn data_length(&self, input: &[u8]) -> Option<usize> {
+        Some(match self {
+            Self::NoData | Self::SelectionForReadout => 0,
+            Self::Integer8Bit | Self::BCD2Digit => 1,
+            Self::Integer16Bit | Self::BCD4Digit | Self::DateTypeG => 2,
+            Self::Integer24Bit | Self::BCD6Digit | Self::DateTimeTypeJ => 3,
+            Self::Integer32Bit
+            | Self::Real32Bit
+            | Self::BCD8Digit
+            | Self::DateTimeTypeF
+            | Self::DaylightSavingTypeK => 4,
+            Self::Integer48Bit
+            | Self::BCDDigit12
+            | Self::DateTimeTypeI
+            | Self::ListeningWindowTypeL => 6,
+            Self::Integer64Bit => 8,
+            Self::DateTimeTypeM => match *input.first()? {
+                length @ 0x00..=0xBF => 1 + usize::from(length),
+                _ => return None,
+            },
+            Self::VariableLength => {
+                let length = *input.first()?;
+                1 + usize::from(match length {
+                    0x00..=0xBF => length,
+                    0xC0..=0xC9 => length - 0xC0,
+                    0xD0..=0xD9 => length - 0xD0,
+                    0xE0..=0xEF => length - 0xE0,
+                    0xF0..=0xF4 => 4 * (length - 0xEC),
+                    0xF5 => 48,
+                    0xF6 => 64,
+                    _ => return None,
+                })
+            }
+            Self::SpecialFunctions(_) => return None,
+        })
+    }
+
     pub fn parse<'a>(
         &self,
         input: &'a [u8],
@@ -772,7 +1175,7 @@ impl DataFieldCoding {
                     0
                 );
                 let month = parse_month!(input.get(1).ok_or(DataRecordError::InsufficientData)?);
-                let year = parse_year!(input, 0xF0, 0xE0, 0x7F);
+                let year = calendar_year(parse_year!(input, 0xF0, 0xE0, 0x7F), 0);
 
                 Ok(Data {
                     value: Some(DataType::Date(day, month, year)),
@@ -799,10 +1202,19 @@ impl DataFieldCoding {
                     0
                 );
                 let month = parse_month!(input.get(3).ok_or(DataRecordError::InsufficientData)?);
-                let year = parse_year!(input, 0xF0, 0xE0, 0x7F);
+                let hour_byte = input.get(1).copied().unwrap_or_default();
+                let year = calendar_year(
+                    parse_year!(input.get(2..).unwrap_or_default(), 0xF0, 0xE0, 0x7F),
+                    (hour_byte >> 5) & 0x03,
+                );
+                let flags = TimeFlags {
+                    invalid: input.first().is_some_and(|minute| minute & 0x80 != 0),
+                    summer_time: hour_byte & 0x80 != 0,
+                    ..TimeFlags::default()
+                };
 
                 Ok(Data {
-                    value: Some(DataType::DateTime(day, month, year, hour, minutes)),
+                    value: Some(DataType::DateTime(day, month, year, hour, minutes, flags)),
                     size: 4,
                 })
             }
@@ -832,9 +1244,8 @@ impl DataFieldCoding {
                 })
             }
             Self::DateTimeTypeI => {
-                // note: more information can be extracted from the data,
-                // however, because this data can be derived from the other data that is
-                // that is extracted, it is not necessary to extract it.
+                // note: the week number in the last byte can be derived from the
+                // date and is not extracted.
 
                 let seconds = parse_single_or_every!(
                     input.first().ok_or(DataRecordError::InsufficientData)?,
@@ -863,12 +1274,99 @@ impl DataFieldCoding {
                     0
                 );
                 let months = parse_month!(input.get(4).ok_or(DataRecordError::InsufficientData)?);
-                let year = parse_year!(input, 0xF0, 0xE0, 0x7F);
+                let year = calendar_year(
+                    parse_year!(input.get(3..).unwrap_or_default(), 0xF0, 0xE0, 0x7F),
+                    0,
+                );
+                let byte = |index: usize| input.get(index).copied().unwrap_or_default();
+                let flags = TimeFlags {
+                    invalid: byte(1) & 0x80 != 0,
+                    summer_time: byte(5) & 0x80 != 0,
+                    leap_year: byte(0) & 0x40 != 0,
+                    day_of_week: Some(byte(2) >> 5).filter(|day| *day != 0),
+                };
 
                 Ok(Data {
                     value: Some(DataType::DateTimeWithSeconds(
-                        days, months, year, hours, minutes, seconds,
+                        days, months, year, hours, minutes, seconds, flags,
+                    )),
+                    size: 6,
+                })
+            }
+            Self::DateTimeTypeM => {
+                // variable length field: the LVAR byte is followed by a type I
+                // date and time and the offset to UTC in quarter hours
+                let length = match *input.first().ok_or(DataRecordError::InsufficientData)? {
+                    length @ 0x00..=0xBF => usize::from(length),
+                    // numeric LVAR codings do not hold a date and time
+                    _ => {
+                        return Err(DataRecordError::DataInformationError(
+                            DataInformationError::InvalidValueInformation,
+                        ))
+                    }
+                };
+                let content = input
+                    .get(1..1 + length)
+                    .ok_or(DataRecordError::InsufficientData)?;
+                if length < 7 {
+                    return Err(DataRecordError::InsufficientData);
+                }
+                let Some(DataType::DateTimeWithSeconds(
+                    day,
+                    month,
+                    year,
+                    hour,
+                    minute,
+                    second,
+                    flags,
+                )) = Self::DateTimeTypeI.parse(content, fixed_data_header)?.value
+                else {
+                    return Err(DataRecordError::InsufficientData);
+                };
+                let offset = content
+                    .get(6)
+                    .map(|offset| i16::from(*offset as i8) * 15)
+                    .ok_or(DataRecordError::InsufficientData)?;
+
+                Ok(Data {
+                    value: Some(DataType::DateTimeWithOffset(
+                        day, month, year, hour, minute, second, offset, flags,
                     )),
+                    size: length + 1,
+                })
+            }
+            Self::DaylightSavingTypeK => {
+                let bytes = input.get(..4).ok_or(DataRecordError::InsufficientData)?;
+                let [hour, begin_day, months, end_day] = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+                Ok(Data {
+                    value: Some(DataType::DaylightSaving(DaylightSaving {
+                        begin_day: begin_day & 0x1F,
+                        begin_month: parse_month!(months),
+                        begin_hour: hour & 0x1F,
+                        end_day: end_day & 0x1F,
+                        end_month: parse_month!(months >> 4),
+                        deviation: (hour as i8) >> 6,
+                    })),
+                    size: 4,
+                })
+            }
+            Self::ListeningWindowTypeL => {
+                let bytes = input.get(..6).ok_or(DataRecordError::InsufficientData)?;
+                let Some(DataType::Time(start_second, start_minute, start_hour)) =
+                    Self::DateTimeTypeJ.parse(bytes, fixed_data_header)?.value
+                else {
+                    return Err(DataRecordError::InsufficientData);
+                };
+
+                Ok(Data {
+                    value: Some(DataType::ListeningWindow(ListeningWindow {
+                        start_second,
+                        start_minute,
+                        start_hour,
+                        duration_seconds: u16::from_le_bytes([bytes[3], bytes[4]]),
+                        weekdays: bytes[5] & 0x7F,
+                    })),
                     size: 6,
                 })
             }
@@ -883,7 +1381,7 @@ impl DataInformation {
     }
 }
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum FunctionField {
@@ -947,6 +1445,9 @@ pub enum DataFieldCoding {
     DateTimeTypeF,
     DateTimeTypeJ,
     DateTimeTypeI,
+    DaylightSavingTypeK,
+    ListeningWindowTypeL,
+    DateTimeTypeM,
 }
 
 #[cfg(feature = "std")]
@@ -972,6 +1473,9 @@ impl std::fmt::Display for DataFieldCoding {
             DataFieldCoding::DateTimeTypeF => write!(f, "Date Time Type F"),
             DataFieldCoding::DateTimeTypeJ => write!(f, "Date Time Type J"),
             DataFieldCoding::DateTimeTypeI => write!(f, "Date Time Type I"),
+            DataFieldCoding::DaylightSavingTypeK => write!(f, "Daylight Saving Type K"),
+            DataFieldCoding::ListeningWindowTypeL => write!(f, "Listening Window Type L"),
+            DataFieldCoding::DateTimeTypeM => write!(f, "Date Time Type M"),
             DataFieldCoding::SpecialFunctions(code) => write!(f, "Special Functions ({:?})", code),
         }
     }
@@ -1019,6 +1523,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn storage_number_continues_in_data_information_extensions() {
+        let storage_number = |data: &[u8]| {
+            let block = DataInformationBlock::try_from(data).unwrap();
+            DataInformation::try_from(&block).unwrap().storage_number
+        };
+        assert_eq!(storage_number(&[0x84, 0x01]), 2);
+        assert_eq!(storage_number(&[0xC4, 0x0F]), 31);
+        assert_eq!(storage_number(&[0x84, 0x81, 0x01]), 34);
+        // each further DIFE adds the next four bits
+        assert_eq!(storage_number(&[0x84, 0x81, 0x81, 0x01]), 546);
+        assert_eq!(storage_number(&[0xC4, 0x8F, 0x8F, 0x0F]), 0x1FFF);
+        // tariff and subunit bits of the DIFEs do not change it
+        assert_eq!(storage_number(&[0x84, 0xF1, 0x71]), 34);
+    }
+
     #[test]
     fn reverse_text_unit() {
         let original_value = [0x6c, 0x61, 0x67, 0x69];
@@ -1093,7 +1613,7 @@ mod tests {
         assert_eq!(
             result.unwrap(),
             Data {
-                value: Some(DataType::Number(987654.0)),
+                value: Some(DataType::Integer(987654)),
                 size: 3
             }
         );
@@ -1118,7 +1638,7 @@ mod tests {
         assert_eq!(
             result,
             Data {
-                value: Some(DataType::Number(127.0)),
+                value: Some(DataType::Integer(127)),
                 size: 1
             }
         );
@@ -1131,7 +1651,7 @@ mod tests {
         assert_eq!(
             result,
             Data {
-                value: Some(DataType::Number(-1.0)),
+                value: Some(DataType::Integer(-1)),
                 size: 1
             }
         );
@@ -1144,7 +1664,7 @@ mod tests {
         assert_eq!(
             result,
             Data {
-                value: Some(DataType::Number(250.0)),
+                value: Some(DataType::Integer(250)),
                 size: 8
             }
         );
@@ -1157,9 +1677,43 @@ mod tests {
         assert_eq!(
             result,
             Data {
-                value: Some(DataType::Number(-1.0)),
+                value: Some(DataType::Integer(-1)),
                 size: 8
             }
         );
     }
+
+    #[test]
+    fn test_integer_to_value_64_bit_is_exact() {
+        let data = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F];
+        let result = integer_to_value_internal(&data, 8);
+        assert_eq!(result.value, Some(DataType::Integer(i64::MAX)));
+
+        let data = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
+        let result = integer_to_value_internal(&data, 9);
+        assert_eq!(result.value, Some(DataType::LargeInteger(1 << 64)));
+    }
+
+    #[test]
+    fn test_bcd_12_digit_is_exact() {
+        let data = [0x89, 0x67, 0x45, 0x23, 0x01, 0x99];
+        let result = DataFieldCoding::BCDDigit12.parse(&data, None).unwrap();
+        assert_eq!(result.value, Some(DataType::Integer(990_123_456_789)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_decimal_arithmetic_and_display() {
+        let value = Decimal::from(876_543i64).scaled(-3);
+        assert_eq!(value.to_string(), "876.543");
+        assert_eq!(Decimal::new(-10, -1).to_string(), "-1");
+        assert_eq!(Decimal::new(5, -3).to_string(), "0.005");
+        assert_eq!(Decimal::new(12, 2).to_string(), "1200");
+        assert_eq!(
+            Decimal::new(-10, -1).checked_add(Decimal::new(1, -1)),
+            Some(Decimal::new(-9, -1))
+        );
+        assert_eq!(Decimal::new(15, -1).with_exponent(0), None);
+        assert_eq!(Decimal::new(1500, -2).normalized(), Decimal::new(15, 0));
+    }
 }
@@ -1,6 +1,9 @@
 use super::{
-    data_information::{Data, DataFieldCoding, DataInformation, DataInformationBlock, DataType},
-    value_information::{ValueInformation, ValueInformationBlock, ValueLabel},
+    data_information::{
+        Data, DataFieldCoding, DataInformation, DataInformationBlock, DataType, Decimal,
+    },
+    scaled_value::{ScaledNumber, ScaledValue},
+    value_information::{PlaintextVifOrder, ValueInformation, ValueInformationBlock, ValueLabel},
     variable_user_data::DataRecordError,
     LongTplHeader,
 };
@@ -57,6 +60,51 @@ impl DataRecord<'_> {
             .as_ref()
     }
 
+    /// Returns the exact value of an integer record. Binary integers wider
+    /// than 128 bits are read from the record bytes if their value fits.
+    #[must_use]
+    pub fn decimal(&self) -> Option<Decimal> {
+        match self.value()? {
+            DataType::LossyNumber(_) => {
+                // The data of these integers follows the LVAR byte.
+                let data = self
+                    .raw_bytes
+                    .get(self.data_record_header.get_size() + 1..)?;
+                let (low, high) = (data.get(..16)?, data.get(16..)?);
+                let value = i128::from_le_bytes(low.try_into().ok()?);
+                let extension = if value < 0 { 0xFF } else { 0x00 };
+                high.iter()
+                    .all(|byte| *byte == extension)
+                    .then(|| Decimal::from(value))
+            }
+            value => value.decimal(),
+        }
+    }
+
+    /// Returns the physical quantity `value * 10^scale + 10^offset` of a
+    /// numeric record with its units and labels.
+    #[must_use]
+    pub fn scaled_value(&self) -> Option<ScaledValue> {
+        let value_information = self.value_information()?;
+        let value = match (self.value()?, self.decimal()) {
+            (_, Some(decimal)) => ScaledNumber::Exact(decimal),
+            (DataType::Number(value) | DataType::LossyNumber(value), None) => {
+                ScaledNumber::Real(*value)
+            }
+            (_, None) => return None,
+        };
+        let value = value.scaled(i16::try_from(value_information.decimal_scale_exponent).ok()?);
+        let value = match value_information.decimal_offset_exponent {
+            Some(exponent) => value.plus(Decimal::new(1, i16::try_from(exponent).ok()?)),
+            None => value,
+        };
+        Some(ScaledValue {
+            value,
+            units: value_information.units.clone(),
+            labels: value_information.labels.clone(),
+        })
+    }
+
     /// Returns all raw bytes consumed by this record.
     #[must_use]
     pub fn raw_bytes(&self) -> &[u8] {
@@ -98,11 +146,74 @@ impl DataRecord<'_> {
 }
 
 impl<'a> DataRecord<'a> {
-    fn parse(
+    /// Returns whether the record holds manufacturer specific content: the
+    /// data after the DIF `0x0F`/`0x1F`, or a value with the VIF `0x7F`/`0xFF`
+    /// or the VIFE `0x7F`.
+    #[must_use]
+    pub fn is_manufacturer_specific(&self) -> bool {
+        matches!(self.data.value, Some(DataType::ManufacturerSpecific(_)))
+            || self.value_information().is_some_and(|value_information| {
+                value_information.labels.iter().any(|label| {
+                    matches!(
+                        label,
+                        ValueLabel::ManufacturerSpecific
+                            | ValueLabel::NextVIFEAndDataOfThisBlockAreManufacturerSpecific
+                    )
+                })
+            })
+    }
+
+    /// Returns the data bytes of a manufacturer specific record, see
+    /// [`DataRecord::is_manufacturer_specific`].
+    #[must_use]
+    pub fn manufacturer_data(&self) -> Option<&'a [u8]> {
+        if !self.is_manufacturer_specific() {
+            return None;
+        }
+        self.raw_bytes.get(self.data_record_header.get_size()..)
+    }
+
+    /// Returns the first VIFE after the manufacturer specific VIF `0x7F`/`0xFF`,
+    /// which manufacturers use to tell their records apart.
+    #[must_use]
+    pub fn manufacturer_vife(&self) -> Option<u8> {
+        let block = self
+            .data_record_header
+            .raw_data_record_header
+            .value_information_block
+            .as_ref()?;
+        if block.value_information.data & 0x7F != 0x7F {
+            return None;
+        }
+        Some(block.value_information_extension.as_ref()?.first()?.data)
+    }
+
+    /// Returns the length of the record at the start of `data` from its data
+    /// and value information blocks and the data field coding of the DIF,
+    /// without interpreting the value information. Used to skip records that
+    /// fail to parse.
+    pub(crate) fn raw_length(data: &[u8], plaintext_vif_order: PlaintextVifOrder) -> Option<usize> {
+        let header = RawDataRecordHeader::parse(data, plaintext_vif_order).ok()?;
+        let header_size = header.data_information_block.get_size()
+            + header
+                .value_information_block
+                .as_ref()
+                .map_or(0, ValueInformationBlock::get_size);
+        let coding = DataInformation::try_from(&header.data_information_block)
+            .ok()?
+            .data_field_coding;
+        let length = header_size + coding.data_length(data.get(header_size..)?)?;
+        (length <= data.len()).then_some(length)
+    }
+
+    /// Parses a record with the long transport header, if any, and the
+    /// position of plaintext units. `try_from` uses the default order.
+    pub fn parse(
         data: &'a [u8],
         fixed_data_header: Option<&'a LongTplHeader>,
+        plaintext_vif_order: PlaintextVifOrder,
     ) -> Result<Self, DataRecordError> {
-        let data_record_header = DataRecordHeader::try_from(data)?;
+        let data_record_header = DataRecordHeader::parse(data, plaintext_vif_order)?;
         let header_size = data_record_header.get_size();
         if data.len() < header_size {
             return Err(DataRecordError::InsufficientData);
@@ -169,15 +280,25 @@ impl DataRecordHeader<'_> {
 impl<'a> TryFrom<&'a [u8]> for RawDataRecordHeader<'a> {
     type Error = DataRecordError;
     fn try_from(data: &[u8]) -> Result<RawDataRecordHeader<'_>, DataRecordError> {
+        RawDataRecordHeader::parse(data, PlaintextVifOrder::default())
+    }
+}
+
+impl RawDataRecordHeader<'_> {
+    pub fn parse(
+        data: &[u8],
+        plaintext_vif_order: PlaintextVifOrder,
+    ) -> Result<RawDataRecordHeader<'_>, DataRecordError> {
         let difb = DataInformationBlock::try_from(data)?;
         let offset = difb.get_size();
 
         let mut vifb = None;
 
         if !difb.data_information_field.is_special_function() {
-            vifb = Some(ValueInformationBlock::try_from(
+            vifb = Some(ValueInformationBlock::parse(
                 data.get(offset..)
                     .ok_or(DataRecordError::InsufficientData)?,
+                plaintext_vif_order,
             )?);
         }
 
@@ -206,16 +327,29 @@ impl TryFrom<&RawDataRecordHeader<'_>> for ProcessedDataRecordHeader {
                 d.data_field_coding = DataFieldCoding::DateTypeG;
             } else if v.labels.contains(&ValueLabel::DateTime) {
                 // VIF 0x6D with a 6-byte data field is a type I date and time
-                // (EN 13757-3), only the 4-byte variant is type F.
-                d.data_field_coding = if d.data_field_coding == DataFieldCoding::Integer48Bit {
-                    DataFieldCoding::DateTimeTypeI
-                } else {
-                    DataFieldCoding::DateTimeTypeF
+                // and with a variable length field type M (EN 13757-3), only
+                // the 4-byte variant is type F.
+                d.data_field_coding = match d.data_field_coding {
+                    DataFieldCoding::Integer48Bit => DataFieldCoding::DateTimeTypeI,
+                    DataFieldCoding::VariableLength => DataFieldCoding::DateTimeTypeM,
+                    _ => DataFieldCoding::DateTimeTypeF,
                 };
             } else if v.labels.contains(&ValueLabel::Time) {
                 d.data_field_coding = DataFieldCoding::DateTimeTypeJ;
             } else if v.labels.contains(&ValueLabel::DateTimeWithSeconds) {
                 d.data_field_coding = DataFieldCoding::DateTimeTypeI;
+            } else if v
+                .labels
+                .contains(&ValueLabel::DaylightSavingBeginningEndingDeviation)
+                && d.data_field_coding == DataFieldCoding::Integer32Bit
+            {
+                d.data_field_coding = DataFieldCoding::DaylightSavingTypeK;
+            } else if v
+                .labels
+                .contains(&ValueLabel::ListeningWindowManagementData)
+                && d.data_field_coding == DataFieldCoding::Integer48Bit
+            {
+                d.data_field_coding = DataFieldCoding::ListeningWindowTypeL;
             }
 
             value_information = Some(v);
@@ -240,7 +374,16 @@ impl TryFrom<&RawDataRecordHeader<'_>> for ProcessedDataRecordHeader {
 impl<'a> TryFrom<&'a [u8]> for DataRecordHeader<'a> {
     type Error = DataRecordError;
     fn try_from(data: &'a [u8]) -> Result<Self, DataRecordError> {
-        let raw_data_record_header = RawDataRecordHeader::try_from(data)?;
+        Self::parse(data, PlaintextVifOrder::default())
+    }
+}
+
+impl<'a> DataRecordHeader<'a> {
+    pub fn parse(
+        data: &'a [u8],
+        plaintext_vif_order: PlaintextVifOrder,
+    ) -> Result<Self, DataRecordError> {
+        let raw_data_record_header = RawDataRecordHeader::parse(data, plaintext_vif_order)?;
         let processed_data_record_header =
             ProcessedDataRecordHeader::try_from(&raw_data_record_header)?;
         Ok(Self {
@@ -255,14 +398,14 @@ impl<'a> TryFrom<(&'a [u8], &'a LongTplHeader)> for DataRecord<'a> {
     fn try_from(
         (data, fixed_data_header): (&'a [u8], &'a LongTplHeader),
     ) -> Result<Self, Self::Error> {
-        Self::parse(data, Some(fixed_data_header))
+        Self::parse(data, Some(fixed_data_header), PlaintextVifOrder::default())
     }
 }
 
 impl<'a> TryFrom<&'a [u8]> for DataRecord<'a> {
     type Error = DataRecordError;
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        Self::parse(data, None)
+        Self::parse(data, None, PlaintextVifOrder::default())
     }
 }
 
@@ -275,6 +418,145 @@ mod tests {
         let data = &[0x03, 0x13, 0x15, 0x31, 0x00];
         let _result = DataRecordHeader::try_from(data.as_slice());
     }
+    #[test]
+    fn test_scaled_value() {
+        use crate::value_information::UnitName;
+
+        // 16-bit volume flow of 2500 * 10^-3 m³/h
+        let data = [0x02, 0x3B, 0xC4, 0x09];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        let scaled = record.scaled_value().unwrap();
+        assert_eq!(scaled.value, ScaledNumber::Exact(Decimal::new(2_500, -3)));
+        assert_eq!(scaled.label(), Some(ValueLabel::VolumeFlow));
+
+        let si = scaled.to_si();
+        assert_eq!(si.value.to_f64(), 2.5 / 3_600.0);
+        assert_eq!(si.units[0].name, UnitName::Meter);
+        assert_eq!(si.units[0].exponent, 3);
+        assert_eq!(si.units[1].name, UnitName::Second);
+        assert_eq!(si.units[1].exponent, -1);
+
+        // 8-bit energy of 10 Wh with an additive offset of 10^0 Wh
+        let data = [0x01, 0x83, 0x7B, 0x0A];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        let scaled = record.scaled_value().unwrap();
+        assert_eq!(scaled.value, ScaledNumber::Exact(Decimal::new(11, 0)));
+        let si = scaled.to_si();
+        assert_eq!(si.value, ScaledNumber::Exact(Decimal::new(39_600, 0)));
+        assert_eq!(si.units.len(), 1);
+        assert_eq!(si.units[0].name, UnitName::Joul);
+
+        // 16-bit flow temperature of 452 * 10^-1 °C
+        let data = [0x02, 0x5A, 0xC4, 0x01];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        let si = record.scaled_value().unwrap().to_si();
+        assert_eq!(si.value, ScaledNumber::Exact(Decimal::new(31_835, -2)));
+        assert_eq!(si.units[0].name, UnitName::Kelvin);
+    }
+
+    #[test]
+    fn test_decimal_of_wide_integers() {
+        // 48 byte binary integers of -1 and of 2^128
+        let mut data = [0xFF; 51];
+        data[..3].copy_from_slice(&[0x0D, 0x13, 0xF5]);
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        assert_eq!(record.value(), Some(&DataType::LossyNumber(-1.0)));
+        assert_eq!(record.decimal(), Some(Decimal::new(-1, 0)));
+
+        let mut data = [0x00; 51];
+        data[..3].copy_from_slice(&[0x0D, 0x13, 0xF5]);
+        data[19] = 0x01;
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        assert_eq!(record.decimal(), None);
+        assert!(matches!(
+            record.scaled_value().map(|scaled| scaled.value),
+            Some(ScaledNumber::Real(_))
+        ));
+    }
+
+    #[test]
+    fn test_date_time_types_k_l_m() {
+        use crate::data_information::{
+            DataType, DaylightSaving, Month, SingleEveryOrInvalid, TimeFlags,
+        };
+
+        // daylight saving from 31 March 02:00 to 27 October, deviation +1 h
+        let data = [0x04, 0xFD, 0x72, 0x42, 0x1F, 0xA3, 0x1B];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        let period = DaylightSaving {
+            begin_day: 31,
+            begin_month: SingleEveryOrInvalid::Single(Month::March),
+            begin_hour: 2,
+            end_day: 27,
+            end_month: SingleEveryOrInvalid::Single(Month::October),
+            deviation: 1,
+        };
+        assert_eq!(period.end_hour(), 3);
+        let midnight = DaylightSaving {
+            begin_hour: 0,
+            deviation: -1,
+            ..period.clone()
+        };
+        assert_eq!(midnight.end_hour(), 23);
+        assert_eq!(record.value(), Some(&DataType::DaylightSaving(period)));
+
+        // listening window at 08:30:00 for 30 s, Monday to Friday
+        let data = [0x06, 0xFD, 0x73, 0x00, 0x1E, 0x08, 0x1E, 0x00, 0x1F];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        let Some(DataType::ListeningWindow(window)) = record.value() else {
+            panic!("expected a listening window, got {:?}", record.value());
+        };
+        assert_eq!(window.start_hour, SingleEveryOrInvalid::Single(8));
+        assert_eq!(window.start_minute, SingleEveryOrInvalid::Single(30));
+        assert_eq!(window.duration_seconds, 30);
+        assert!(window.is_open_on(5));
+        assert!(!window.is_open_on(6));
+
+        // 15.03.24 12:30:45 at UTC+01:00
+        let data = [0x0D, 0x6D, 0x07, 0x2D, 0x1E, 0x0C, 0x0F, 0x33, 0x00, 0x04];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        assert_eq!(record.get_size(), data.len());
+        assert_eq!(
+            record.value(),
+            Some(&DataType::DateTimeWithOffset(
+                SingleEveryOrInvalid::Single(15),
+                SingleEveryOrInvalid::Single(Month::March),
+                SingleEveryOrInvalid::Single(2024),
+                SingleEveryOrInvalid::Single(12),
+                SingleEveryOrInvalid::Single(30),
+                SingleEveryOrInvalid::Single(45),
+                60,
+                TimeFlags::default(),
+            ))
+        );
+
+        // 15.03.24 12:30:45 in summer time at UTC-00:30, clock not set
+        let data = [0x0D, 0x6D, 0x07, 0x2D, 0x9E, 0x0C, 0x0F, 0x33, 0x80, 0xFE];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        let Some(DataType::DateTimeWithOffset(.., offset, flags)) = record.value() else {
+            panic!("expected a type M date and time, got {:?}", record.value());
+        };
+        assert_eq!(*offset, -30);
+        assert!(flags.invalid);
+        assert!(flags.summer_time);
+
+        // a numeric LVAR coding is not a type M date and time
+        let data = [0x0D, 0x6D, 0xC7, 0x2D, 0x1E, 0x0C, 0x0F, 0x33, 0x00, 0x04];
+        assert!(DataRecord::try_from(data.as_slice()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_date_time_type_m_display() {
+        let data = [0x0D, 0x6D, 0x07, 0x2D, 0x1E, 0x0C, 0x0F, 0x33, 0x00, 0x04];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        assert_eq!(record.data.to_string(), "15/Mar/2024 12:30:45 +01:00");
+
+        let data = [0x0D, 0x6D, 0x07, 0x2D, 0x1E, 0x0C, 0x0F, 0x33, 0x00, 0xFE];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        assert_eq!(record.data.to_string(), "15/Mar/2024 12:30:45 -00:30");
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn test_manufacturer_specific_block() {
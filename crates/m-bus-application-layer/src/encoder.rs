@@ -0,0 +1,635 @@
+//! Encoding of data records from typed values.
+//!
+//! A [`RecordDescription`] names the quantity, units and decimal exponent of a
+//! value, its register (function, storage number, tariff and subunit) and the
+//! data field coding. [`RecordDescription::encode_into`] writes the DIF, the
+//! DIFEs needed for the register, and the shortest VIF whose decoding yields
+//! the quantity, units and exponent, followed by the data field. The VIF is
+//! looked up in the same tables the parser uses, so the bytes parse back into
+//! the described record with [`DataRecord::try_from`].
+//!
+//! Combinable VIFEs, plaintext VIFs and manufacturer specific records are not
+//! encoded. Integers are written in two's complement and BCD values with the
+//! least significant byte first.
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+use arrayvec::ArrayVec;
+
+use crate::data_information::{
+    DataFieldCoding, DataType, FunctionField, SingleEveryOrInvalid, TimeFlags,
+};
+use crate::data_record::{DataRecord, DataRecordHeader};
+use crate::value_information::{
+    Unit, ValueInformation, ValueInformationBlock, ValueInformationField,
+    ValueInformationFieldExtension, ValueLabel,
+};
+
+/// Maximum number of DIFEs the parser accepts.
+const MAX_DIFES: usize = 10;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EncodeError {
+    /// The output buffer cannot hold the record.
+    BufferTooSmall,
+    /// Storage number, tariff or subunit need more than 10 DIFEs.
+    RegisterOutOfRange,
+    /// No VIF decodes to the quantity, units and exponent.
+    NoValueInformation,
+    /// The coding is a special function or is decided by the VIF, e.g. a
+    /// date coding for a quantity other than a date.
+    UnsupportedCoding,
+    /// The value is of another type than the coding or does not fit it, e.g.
+    /// a real that single precision cannot hold exactly.
+    InvalidValue,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall => write!(f, "output buffer is too small for the record"),
+            Self::RegisterOutOfRange => {
+                write!(f, "storage number, tariff or subunit need too many DIFEs")
+            }
+            Self::NoValueInformation => {
+                write!(f, "no VIF encodes the quantity, units and exponent")
+            }
+            Self::UnsupportedCoding => write!(f, "data field coding cannot be encoded"),
+            Self::InvalidValue => write!(f, "value does not fit the data field coding"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+/// Typed description of a data record, see the [module documentation](self).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordDescription<'a> {
+    pub quantity: ValueLabel,
+    /// Units the VIF decodes to, e.g. Wh or J for [`ValueLabel::Energy`].
+    /// Empty to take the first VIF of the quantity with the exponent.
+    pub units: ArrayVec<Unit, 10>,
+    /// Decimal exponent the value is scaled with, e.g. 3 for kWh.
+    pub exponent: isize,
+    pub function: FunctionField,
+    pub storage_number: u64,
+    pub tariff: u64,
+    pub subunit: u64,
+    pub coding: DataFieldCoding,
+    /// `None` for [`DataFieldCoding::NoData`] and
+    /// [`DataFieldCoding::SelectionForReadout`].
+    pub value: Option<DataType<'a>>,
+}
+
+impl<'a> RecordDescription<'a> {
+    /// Describes an instantaneous value of storage number 0 without tariff
+    /// and subunit.
+    #[must_use]
+    pub fn new(
+        quantity: ValueLabel,
+        exponent: isize,
+        coding: DataFieldCoding,
+        value: Option<DataType<'a>>,
+    ) -> Self {
+        Self {
+            quantity,
+            units: ArrayVec::new(),
+            exponent,
+            function: FunctionField::InstantaneousValue,
+            storage_number: 0,
+            tariff: 0,
+            subunit: 0,
+            coding,
+            value,
+        }
+    }
+
+    /// Describes a parsed record with a single quantity and no combinable
+    /// VIFEs, so that it can be encoded again.
+    #[must_use]
+    pub fn of(record: &DataRecord<'a>) -> Option<Self> {
+        let information = record.data_information()?;
+        let value_information = record.value_information()?;
+        let [quantity] = value_information.labels.as_slice() else {
+            return None;
+        };
+        Some(Self {
+            quantity: *quantity,
+            units: value_information.units.clone(),
+            exponent: value_information.decimal_scale_exponent,
+            function: information.function_field,
+            storage_number: information.storage_number,
+            tariff: information.tariff,
+            subunit: information.device,
+            coding: information.data_field_coding,
+            value: record.data.value.clone(),
+        })
+    }
+
+    /// Writes the record into `output` and returns its length.
+    pub fn encode_into(&self, output: &mut [u8]) -> Result<usize, EncodeError> {
+        let mut header = ArrayVec::<u8, 13>::new();
+        let (dif_coding, data_length) = dif_coding(self.coding)?;
+        header
+            .push(dif_coding | function_bits(self.function) | (self.storage_number as u8 & 1) << 6);
+        let mut storage_number = self.storage_number >> 1;
+        let mut tariff = self.tariff;
+        let mut subunit = self.subunit;
+        while storage_number != 0 || tariff != 0 || subunit != 0 {
+            if header.len() > MAX_DIFES {
+                return Err(EncodeError::RegisterOutOfRange);
+            }
+            if let Some(last) = header.last_mut() {
+                *last |= 0x80;
+            }
+            header.push(
+                (storage_number & 0x0F) as u8
+                    | ((tariff & 0x03) as u8) << 4
+                    | ((subunit & 1) as u8) << 6,
+            );
+            storage_number >>= 4;
+            tariff >>= 2;
+            subunit >>= 1;
+        }
+        let value_information = self.value_information()?;
+        header.push(value_information.value_information.data);
+        for extension in value_information
+            .value_information_extension
+            .iter()
+            .flatten()
+        {
+            header.push(extension.data);
+        }
+
+        // The VIF decides the coding of dates, check that the parser reads
+        // the header back with the described coding.
+        let parsed = DataRecordHeader::try_from(header.as_slice())
+            .map_err(|_| EncodeError::NoValueInformation)?;
+        let coding = parsed
+            .processed_data_record_header
+            .data_information
+            .map(|information| information.data_field_coding);
+        if coding != Some(self.coding) {
+            return Err(EncodeError::UnsupportedCoding);
+        }
+
+        let (header_output, data_output) = output
+            .split_at_mut_checked(header.len())
+            .ok_or(EncodeError::BufferTooSmall)?;
+        header_output.copy_from_slice(&header);
+        let written = self.encode_data(data_length, data_output)?;
+        Ok(header.len() + written)
+    }
+
+    /// Returns the record as bytes.
+    #[cfg(feature = "std")]
+    pub fn encode(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut output = [0; 256];
+        let length = self.encode_into(&mut output)?;
+        Ok(output.get(..length).unwrap_or_default().to_vec())
+    }
+
+    /// Returns the shortest VIF, or VIF and VIFE of the first or second
+    /// extension table, that decodes to the quantity, units and exponent.
+    fn value_information(&self) -> Result<ValueInformationBlock, EncodeError> {
+        let primary = (0x00..=0x7A).map(|vif| (vif, None));
+        let extended = [0xFD, 0xFB]
+            .into_iter()
+            .flat_map(|vif| (0x00..=0x7F).map(move |vife| (vif, Some(vife))));
+        primary
+            .chain(extended)
+            .map(|(vif, vife)| ValueInformationBlock {
+                value_information: ValueInformationField { data: vif },
+                value_information_extension: vife.map(|data| {
+                    let mut extensions = ArrayVec::new();
+                    extensions.push(ValueInformationFieldExtension { data });
+                    extensions
+                }),
+                plaintext_vife: None,
+            })
+            .find(|block| {
+                ValueInformation::try_from(block)
+                    .is_ok_and(|information| self.matches(&information))
+            })
+            .ok_or(EncodeError::NoValueInformation)
+    }
+
+    fn matches(&self, information: &ValueInformation) -> bool {
+        information.labels.as_slice() == [self.quantity]
+            && information.decimal_scale_exponent == self.exponent
+            && information.decimal_offset_exponent.is_none()
+            && (self.units.is_empty() || information.units == self.units)
+    }
+
+    fn encode_data(&self, length: usize, output: &mut [u8]) -> Result<usize, EncodeError> {
+        let bytes: ArrayVec<u8, 192> = match (self.coding, &self.value) {
+            (DataFieldCoding::NoData | DataFieldCoding::SelectionForReadout, None) => {
+                ArrayVec::new()
+            }
+            (
+                DataFieldCoding::Integer8Bit
+                | DataFieldCoding::Integer16Bit
+                | DataFieldCoding::Integer24Bit
+                | DataFieldCoding::Integer32Bit
+                | DataFieldCoding::Integer48Bit
+                | DataFieldCoding::Integer64Bit,
+                Some(DataType::Integer(value)),
+            ) => integer_bytes(*value, length)?,
+            (DataFieldCoding::Real32Bit, Some(DataType::Number(value))) => {
+                // reject values that would be rounded to single precision
+                let real = *value as f32;
+                if f64::from(real) != *value && !value.is_nan() {
+                    return Err(EncodeError::InvalidValue);
+                }
+                real.to_le_bytes().into_iter().collect()
+            }
+            (
+                DataFieldCoding::BCD2Digit
+                | DataFieldCoding::BCD4Digit
+                | DataFieldCoding::BCD6Digit
+                | DataFieldCoding::BCD8Digit
+                | DataFieldCoding::BCDDigit12,
+                Some(DataType::Integer(value)),
+            ) => bcd_bytes(*value, length)?,
+            (DataFieldCoding::VariableLength, Some(DataType::Text(text))) => {
+                let text = text.as_bytes();
+                let length = u8::try_from(text.len())
+                    .ok()
+                    .filter(|length| *length <= 0xBF)
+                    .ok_or(EncodeError::InvalidValue)?;
+                core::iter::once(length)
+                    .chain(text.iter().copied())
+                    .collect()
+            }
+            (DataFieldCoding::DateTypeG, Some(DataType::Date(day, month, year))) => {
+                let year = two_digit_year(year)?;
+                [
+                    field(day, 0x1F, 0)? | (year & 0x07) << 5,
+                    month_field(month)? | (year & 0x78) << 1,
+                ]
+                .into_iter()
+                .collect()
+            }
+            (
+                DataFieldCoding::DateTimeTypeF,
+                Some(DataType::DateTime(day, month, year, hour, minute, flags)),
+            ) => {
+                if flags.leap_year || flags.day_of_week.is_some() {
+                    return Err(EncodeError::InvalidValue);
+                }
+                let (hundred_year, year) = hundred_year_and_year(year)?;
+                [
+                    field(minute, 0x3F, 0x3F)? | u8::from(flags.invalid) << 7,
+                    field(hour, 0x1F, 0x1F)? | hundred_year << 5 | u8::from(flags.summer_time) << 7,
+                    field(day, 0x1F, 0x1F)? | (year & 0x07) << 5,
+                    month_field(month)? | (year & 0x78) << 1,
+                ]
+                .into_iter()
+                .collect()
+            }
+            (
+                DataFieldCoding::DateTimeTypeI,
+                Some(DataType::DateTimeWithSeconds(day, month, year, hour, minute, second, flags)),
+            ) => {
+                let TimeFlags {
+                    invalid,
+                    summer_time,
+                    leap_year,
+                    day_of_week,
+                } = *flags;
+                let day_of_week = day_of_week.unwrap_or(0);
+                if day_of_week > 7 {
+                    return Err(EncodeError::InvalidValue);
+                }
+                let year = two_digit_year(year)?;
+                [
+                    field(second, 0x3F, 0x3F)? | u8::from(leap_year) << 6,
+                    field(minute, 0x3F, 0x3F)? | u8::from(invalid) << 7,
+                    field(hour, 0x1F, 0x1F)? | day_of_week << 5,
+                    field(day, 0x1F, 0x1F)? | (year & 0x07) << 5,
+                    month_field(month)? | (year & 0x78) << 1,
+                    u8::from(summer_time) << 7,
+                ]
+                .into_iter()
+                .collect()
+            }
+            _ => return Err(EncodeError::InvalidValue),
+        };
+        output
+            .get_mut(..bytes.len())
+            .ok_or(EncodeError::BufferTooSmall)?
+            .copy_from_slice(&bytes);
+        Ok(bytes.len())
+    }
+}
+
+/// Returns the DIF bits of a coding and the length of its data field. Dates
+/// use the integer coding of the same length, the VIF selects the type.
+const fn dif_coding(coding: DataFieldCoding) -> Result<(u8, usize), EncodeError> {
+    Ok(match coding {
+        DataFieldCoding::NoData => (0x0, 0),
+        DataFieldCoding::Integer8Bit => (0x1, 1),
+        DataFieldCoding::Integer16Bit | DataFieldCoding::DateTypeG => (0x2, 2),
+        DataFieldCoding::Integer24Bit => (0x3, 3),
+        DataFieldCoding::Integer32Bit | DataFieldCoding::DateTimeTypeF => (0x4, 4),
+        DataFieldCoding::Real32Bit => (0x5, 4),
+        DataFieldCoding::Integer48Bit | DataFieldCoding::DateTimeTypeI => (0x6, 6),
+        DataFieldCoding::Integer64Bit => (0x7, 8),
+        DataFieldCoding::SelectionForReadout => (0x8, 0),
+        DataFieldCoding::BCD2Digit => (0x9, 1),
+        DataFieldCoding::BCD4Digit => (0xA, 2),
+        DataFieldCoding::BCD6Digit => (0xB, 3),
+        DataFieldCoding::BCD8Digit => (0xC, 4),
+        DataFieldCoding::VariableLength => (0xD, 0),
+        DataFieldCoding::BCDDigit12 => (0xE, 6),
+        _ => return Err(EncodeError::UnsupportedCoding),
+    })
+}
+
+const fn function_bits(function: FunctionField) -> u8 {
+    match function {
+        FunctionField::InstantaneousValue => 0x00,
+        FunctionField::MaximumValue => 0x10,
+        FunctionField::MinimumValue => 0x20,
+        FunctionField::ValueDuringErrorState => 0x30,
+    }
+}
+
+fn integer_bytes(value: i64, length: usize) -> Result<ArrayVec<u8, 192>, EncodeError> {
+    let bits = 8 * length as u32;
+    if bits < 64 && !(-(1 << (bits - 1))..1 << (bits - 1)).contains(&value) {
+        return Err(EncodeError::InvalidValue);
+    }
+    Ok(value.to_le_bytes().into_iter().take(length).collect())
+}
+
+fn bcd_bytes(value: i64, length: usize) -> Result<ArrayVec<u8, 192>, EncodeError> {
+    let mut remaining = u64::try_from(value).map_err(|_| EncodeError::InvalidValue)?;
+    let mut bytes = ArrayVec::new();
+    for _ in 0..length {
+        let low = (remaining % 10) as u8;
+        let high = (remaining / 10 % 10) as u8;
+        bytes.push(high << 4 | low);
+        remaining /= 100;
+    }
+    if remaining != 0 {
+        return Err(EncodeError::InvalidValue);
+    }
+    Ok(bytes)
+}
+
+/// Encodes a date or time component the parser reads with `mask`, where
+/// `every` stands for every value.
+fn field(value: &SingleEveryOrInvalid<u8>, mask: u8, every: u8) -> Result<u8, EncodeError> {
+    match value {
+        SingleEveryOrInvalid::Single(value) if value & !mask == 0 && *value != every => Ok(*value),
+        SingleEveryOrInvalid::Every() => Ok(every),
+        _ => Err(EncodeError::InvalidValue),
+    }
+}
+
+fn month_field(
+    month: &SingleEveryOrInvalid<crate::data_information::Month>,
+) -> Result<u8, EncodeError> {
+    match month {
+        SingleEveryOrInvalid::Single(month) => Ok(*month as u8 + 1),
+        SingleEveryOrInvalid::Invalid() => Ok(0),
+        SingleEveryOrInvalid::Every() => Err(EncodeError::InvalidValue),
+    }
+}
+
+/// Returns the two-digit year of a date without hundred-year field, which
+/// covers 1981 to 2080.
+fn two_digit_year(year: &SingleEveryOrInvalid<u16>) -> Result<u8, EncodeError> {
+    match year {
+        SingleEveryOrInvalid::Single(year @ 1981..=1999) => Ok((year - 1900) as u8),
+        SingleEveryOrInvalid::Single(year @ 2000..=2080) => Ok((year - 2000) as u8),
+        SingleEveryOrInvalid::Every() => Ok(0x7F),
+        _ => Err(EncodeError::InvalidValue),
+    }
+}
+
+/// Returns the hundred-year field and two-digit year of a type F date, which
+/// covers 1981 to 2299.
+fn hundred_year_and_year(year: &SingleEveryOrInvalid<u16>) -> Result<(u8, u8), EncodeError> {
+    match year {
+        SingleEveryOrInvalid::Single(year @ 2081..=2299) => {
+            Ok((((year - 1900) / 100) as u8, ((year - 1900) % 100) as u8))
+        }
+        year => Ok((0, two_digit_year(year)?)),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::data_information::{Month, TextUnit};
+    use crate::value_information::UnitName;
+    use crate::DataRecords;
+
+    fn round_trip(description: &RecordDescription<'_>) -> Vec<u8> {
+        let bytes = description.encode().unwrap();
+        let record = DataRecord::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(record.get_size(), bytes.len());
+        let mut described = RecordDescription::of(&record).unwrap();
+        if description.units.is_empty() {
+            described.units.clear();
+        }
+        assert_eq!(&described, description);
+        bytes
+    }
+
+    #[test]
+    fn chooses_vif_and_difes() {
+        let mut energy = RecordDescription::new(
+            ValueLabel::Energy,
+            3,
+            DataFieldCoding::Integer32Bit,
+            Some(DataType::Integer(12_345)),
+        );
+        assert_eq!(round_trip(&energy), [0x04, 0x06, 0x39, 0x30, 0x00, 0x00]);
+
+        energy.units = [Unit {
+            name: UnitName::Joul,
+            exponent: 1,
+        }]
+        .into_iter()
+        .collect();
+        energy.exponent = 6;
+        energy.storage_number = 5;
+        energy.tariff = 1;
+        energy.subunit = 1;
+        energy.coding = DataFieldCoding::BCD8Digit;
+        assert_eq!(
+            round_trip(&energy),
+            [0xCC, 0x52, 0x0E, 0x45, 0x23, 0x01, 0x00]
+        );
+
+        let mut flow = RecordDescription::new(
+            ValueLabel::VolumeFlow,
+            -6,
+            DataFieldCoding::Integer16Bit,
+            Some(DataType::Integer(-2)),
+        );
+        flow.function = FunctionField::MaximumValue;
+        assert_eq!(round_trip(&flow), [0x12, 0x38, 0xFE, 0xFF]);
+
+        let error_flags = RecordDescription::new(
+            ValueLabel::ErrorFlags,
+            0,
+            DataFieldCoding::Integer16Bit,
+            Some(DataType::Integer(3)),
+        );
+        assert_eq!(round_trip(&error_flags), [0x02, 0xFD, 0x17, 0x03, 0x00]);
+    }
+
+    #[test]
+    fn encodes_dates_text_and_reals() {
+        let date = RecordDescription::new(
+            ValueLabel::Date,
+            0,
+            DataFieldCoding::DateTypeG,
+            Some(DataType::Date(
+                SingleEveryOrInvalid::Single(31),
+                SingleEveryOrInvalid::Single(Month::December),
+                SingleEveryOrInvalid::Single(2024),
+            )),
+        );
+        round_trip(&date);
+        let date_time = RecordDescription::new(
+            ValueLabel::DateTime,
+            0,
+            DataFieldCoding::DateTimeTypeF,
+            Some(DataType::DateTime(
+                SingleEveryOrInvalid::Single(1),
+                SingleEveryOrInvalid::Single(Month::July),
+                SingleEveryOrInvalid::Single(2090),
+                SingleEveryOrInvalid::Single(13),
+                SingleEveryOrInvalid::Single(45),
+                TimeFlags {
+                    summer_time: true,
+                    ..TimeFlags::default()
+                },
+            )),
+        );
+        round_trip(&date_time);
+        let with_seconds = RecordDescription::new(
+            ValueLabel::DateTime,
+            0,
+            DataFieldCoding::DateTimeTypeI,
+            Some(DataType::DateTimeWithSeconds(
+                SingleEveryOrInvalid::Single(29),
+                SingleEveryOrInvalid::Single(Month::February),
+                SingleEveryOrInvalid::Single(2024),
+                SingleEveryOrInvalid::Single(23),
+                SingleEveryOrInvalid::Single(59),
+                SingleEveryOrInvalid::Single(58),
+                TimeFlags {
+                    leap_year: true,
+                    day_of_week: Some(4),
+                    ..TimeFlags::default()
+                },
+            )),
+        );
+        round_trip(&with_seconds);
+        let text = RecordDescription::new(
+            ValueLabel::FabricationNumber,
+            0,
+            DataFieldCoding::VariableLength,
+            Some(DataType::Text(TextUnit::new(b"4321"))),
+        );
+        assert_eq!(
+            round_trip(&text),
+            [0x0D, 0x78, 0x04, b'4', b'3', b'2', b'1']
+        );
+        let pressure = RecordDescription::new(
+            ValueLabel::Pressure,
+            -3,
+            DataFieldCoding::Real32Bit,
+            Some(DataType::Number(1.5)),
+        );
+        round_trip(&pressure);
+    }
+
+    #[test]
+    fn parsed_records_encode_to_their_bytes() {
+        let data = [
+            0x04, 0x13, 0x10, 0x27, 0x00, 0x00, // volume
+            0x84, 0x10, 0x06, 0x10, 0x27, 0x00, 0x00, // energy of tariff 1
+            0xC4, 0x40, 0x13, 0x20, 0x03, 0x00, 0x00, // storage 1, subunit 1
+            0x42, 0x6C, 0x1F, 0x3C, // date
+            0x0B, 0x3B, 0x56, 0x34, 0x12, // BCD volume flow
+            0x02, 0xFD, 0x17, 0x01, 0x00, // error flags
+        ];
+        let mut encoded = Vec::new();
+        for record in DataRecords::new(&data, None) {
+            let description = RecordDescription::of(&record.unwrap()).unwrap();
+            encoded.extend(description.encode().unwrap());
+        }
+        assert_eq!(encoded, data);
+    }
+
+    #[test]
+    fn rejects_what_cannot_be_encoded() {
+        let encode = |quantity, exponent, coding, value| {
+            RecordDescription::new(quantity, exponent, coding, Some(value)).encode()
+        };
+        assert_eq!(
+            encode(
+                ValueLabel::Volume,
+                5,
+                DataFieldCoding::Integer8Bit,
+                DataType::Integer(1)
+            ),
+            Err(EncodeError::NoValueInformation)
+        );
+        assert_eq!(
+            encode(
+                ValueLabel::Volume,
+                -3,
+                DataFieldCoding::Integer8Bit,
+                DataType::Integer(128)
+            ),
+            Err(EncodeError::InvalidValue)
+        );
+        assert_eq!(
+            encode(
+                ValueLabel::Volume,
+                -3,
+                DataFieldCoding::BCD2Digit,
+                DataType::Integer(-1)
+            ),
+            Err(EncodeError::InvalidValue)
+        );
+        assert_eq!(
+            encode(
+                ValueLabel::Volume,
+                -3,
+                DataFieldCoding::DateTypeG,
+                DataType::Integer(1)
+            ),
+            Err(EncodeError::UnsupportedCoding)
+        );
+        // 0.1 has no exact single precision representation
+        assert_eq!(
+            encode(
+                ValueLabel::Pressure,
+                -3,
+                DataFieldCoding::Real32Bit,
+                DataType::Number(0.1)
+            ),
+            Err(EncodeError::InvalidValue)
+        );
+        let mut storage =
+            RecordDescription::new(ValueLabel::Volume, -3, DataFieldCoding::NoData, None);
+        storage.storage_number = 1 << 41;
+        assert_eq!(storage.encode(), Err(EncodeError::RegisterOutOfRange));
+    }
+}
@@ -0,0 +1,313 @@
+//! Interpretation of the fixed data structure (CI `0x73`).
+//!
+//! The fixed structure carries two 4-byte counters. The two medium/unit bytes
+//! hold the medium in their upper two bits each and the unit of one counter in
+//! their lower six bits. The status field selects BCD or signed binary coding
+//! and whether the counters are actual values or stored at a fixed date.
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+use arrayvec::ArrayVec;
+
+use crate::data_information::{DataFieldCoding, Decimal};
+use crate::scaled_value::{ScaledNumber, ScaledValue};
+use crate::value_information::{Unit, UnitName, ValueInformation, ValueLabel};
+use crate::{Counter, StatusField};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum FixedMedium {
+    Other,
+    Oil,
+    Electricity,
+    Gas,
+    Heat,
+    Steam,
+    HotWater,
+    Water,
+    HeatCostAllocator,
+    GasMode2,
+    HeatMode2,
+    HotWaterMode2,
+    WaterMode2,
+    HeatCostAllocatorMode2,
+    Reserved(u8),
+}
+
+impl FixedMedium {
+    /// Decodes the medium from the big-endian medium/unit field.
+    #[must_use]
+    pub const fn from_medium_and_unit(device_type_and_unit: u16) -> Self {
+        let first = (device_type_and_unit >> 8) as u8;
+        let second = device_type_and_unit as u8;
+        match ((first & 0xC0) >> 6) | ((second & 0xC0) >> 4) {
+            0x00 => Self::Other,
+            0x01 => Self::Oil,
+            0x02 => Self::Electricity,
+            0x03 => Self::Gas,
+            0x04 => Self::Heat,
+            0x05 => Self::Steam,
+            0x06 => Self::HotWater,
+            0x07 => Self::Water,
+            0x08 => Self::HeatCostAllocator,
+            0x0A => Self::GasMode2,
+            0x0B => Self::HeatMode2,
+            0x0C => Self::HotWaterMode2,
+            0x0D => Self::WaterMode2,
+            0x0E => Self::HeatCostAllocatorMode2,
+            code => Self::Reserved(code),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for FixedMedium {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixedMedium::Other => write!(f, "Other"),
+            FixedMedium::Oil => write!(f, "Oil"),
+            FixedMedium::Electricity => write!(f, "Electricity"),
+            FixedMedium::Gas => write!(f, "Gas"),
+            FixedMedium::Heat => write!(f, "Heat"),
+            FixedMedium::Steam => write!(f, "Steam"),
+            FixedMedium::HotWater => write!(f, "Hot water"),
+            FixedMedium::Water => write!(f, "Water"),
+            FixedMedium::HeatCostAllocator => write!(f, "Heat cost allocator"),
+            FixedMedium::GasMode2 => write!(f, "Gas (mode 2)"),
+            FixedMedium::HeatMode2 => write!(f, "Heat (mode 2)"),
+            FixedMedium::HotWaterMode2 => write!(f, "Hot water (mode 2)"),
+            FixedMedium::WaterMode2 => write!(f, "Water (mode 2)"),
+            FixedMedium::HeatCostAllocatorMode2 => write!(f, "Heat cost allocator (mode 2)"),
+            FixedMedium::Reserved(code) => write!(f, "Reserved ({code:#03X})"),
+        }
+    }
+}
+
+/// One counter of a fixed data structure with its unit.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FixedDataRecord {
+    /// Counter value before scaling.
+    pub value: i64,
+    pub data_field_coding: DataFieldCoding,
+    /// Six-bit unit code from the medium/unit field.
+    pub unit_code: u8,
+    /// Quantity, unit and scale, or `None` for reserved and date/time units.
+    pub value_information: Option<ValueInformation>,
+    /// The counter was stored at a fixed date instead of being an actual value.
+    pub historic: bool,
+    /// Counter bytes as transmitted.
+    pub raw_bytes: [u8; 4],
+}
+
+impl FixedDataRecord {
+    /// Returns the counter scaled by the unit code with its units and labels.
+    #[must_use]
+    pub fn scaled_value(&self) -> Option<ScaledValue> {
+        let value_information = self.value_information.as_ref()?;
+        let scale = i16::try_from(value_information.decimal_scale_exponent).ok()?;
+        Some(ScaledValue {
+            value: ScaledNumber::Exact(Decimal::from(self.value).scaled(scale)),
+            units: value_information.units.clone(),
+            labels: value_information.labels.clone(),
+        })
+    }
+}
+
+/// Unit code `0x3E`: the second counter is a historic value in the unit of
+/// the first counter.
+const SAME_UNIT_HISTORIC: u8 = 0x3E;
+
+/// Decodes both counters of a fixed data structure.
+#[must_use]
+pub fn fixed_data_records(
+    status: StatusField,
+    device_type_and_unit: u16,
+    counter1: &Counter,
+    counter2: &Counter,
+) -> [FixedDataRecord; 2] {
+    let historic = status.contains(StatusField::COUNTER_FIXED_DATE);
+    let unit1 = ((device_type_and_unit >> 8) as u8) & 0x3F;
+    let unit2 = (device_type_and_unit as u8) & 0x3F;
+
+    let first = fixed_data_record(status, unit1, counter1, historic);
+    let second = if unit2 == SAME_UNIT_HISTORIC {
+        let mut record = fixed_data_record(status, unit1, counter2, true);
+        record.unit_code = unit2;
+        record
+    } else {
+        fixed_data_record(status, unit2, counter2, historic)
+    };
+    [first, second]
+}
+
+fn fixed_data_record(
+    status: StatusField,
+    unit_code: u8,
+    counter: &Counter,
+    historic: bool,
+) -> FixedDataRecord {
+    let count = counter.count();
+    let (value, data_field_coding, raw_bytes) =
+        if status.contains(StatusField::COUNTER_BINARY_SIGNED) {
+            (
+                i64::from(count as i32),
+                DataFieldCoding::Integer32Bit,
+                count.to_le_bytes(),
+            )
+        } else {
+            (
+                i64::from(count),
+                DataFieldCoding::BCD8Digit,
+                bcd_bytes(count),
+            )
+        };
+    FixedDataRecord {
+        value,
+        data_field_coding,
+        unit_code,
+        value_information: fixed_value_information(unit_code),
+        historic,
+        raw_bytes,
+    }
+}
+
+fn bcd_bytes(mut value: u32) -> [u8; 4] {
+    let mut bytes = [0u8; 4];
+    for byte in &mut bytes {
+        let low = (value % 10) as u8;
+        value /= 10;
+        let high = (value % 10) as u8;
+        value /= 10;
+        *byte = (high << 4) | low;
+    }
+    bytes
+}
+
+/// Maps a six-bit unit code of the fixed data structure to value information.
+#[must_use]
+pub fn fixed_value_information(unit_code: u8) -> Option<ValueInformation> {
+    let mut labels = ArrayVec::<ValueLabel, 10>::new();
+    let mut units = ArrayVec::<Unit, 10>::new();
+    let unit = |name, exponent| Unit { name, exponent };
+    let decimal_scale_exponent = match unit_code {
+        0x02..=0x0A => {
+            labels.push(ValueLabel::Energy);
+            units.push(unit(UnitName::Watt, 1));
+            units.push(unit(UnitName::Hour, 1));
+            unit_code as isize - 0x02
+        }
+        0x0B..=0x13 => {
+            labels.push(ValueLabel::Energy);
+            units.push(unit(UnitName::Joul, 1));
+            unit_code as isize - 0x0B + 3
+        }
+        0x14..=0x1C => {
+            labels.push(ValueLabel::Power);
+            units.push(unit(UnitName::Watt, 1));
+            unit_code as isize - 0x14
+        }
+        0x1D..=0x25 => {
+            labels.push(ValueLabel::Power);
+            units.push(unit(UnitName::Joul, 1));
+            units.push(unit(UnitName::Hour, -1));
+            unit_code as isize - 0x1D + 3
+        }
+        0x26..=0x2E => {
+            labels.push(ValueLabel::Volume);
+            units.push(unit(UnitName::Meter, 3));
+            unit_code as isize - 0x26 - 6
+        }
+        0x2F..=0x37 => {
+            labels.push(ValueLabel::VolumeFlow);
+            units.push(unit(UnitName::Meter, 3));
+            units.push(unit(UnitName::Hour, -1));
+            unit_code as isize - 0x2F - 6
+        }
+        0x38 => {
+            units.push(unit(UnitName::Celsius, 1));
+            -3
+        }
+        0x39 => {
+            labels.push(ValueLabel::DimensionlessHCA);
+            0
+        }
+        0x3F => {
+            units.push(unit(UnitName::WithoutUnits, 1));
+            0
+        }
+        _ => return None,
+    };
+    Some(ValueInformation {
+        decimal_offset_exponent: None,
+        labels,
+        decimal_scale_exponent,
+        units,
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_medium_units_and_historic_counter() {
+        // manual_frame2 from the rSCADA corpus: water, 1 l and a historic
+        // second counter in the same unit.
+        let counter1 = Counter::from_bcd_hex_digits([0x01, 0x00, 0x00, 0x00]).unwrap();
+        let counter2 = Counter::from_bcd_hex_digits([0x35, 0x01, 0x00, 0x00]).unwrap();
+        assert_eq!(
+            FixedMedium::from_medium_and_unit(0xE97E),
+            FixedMedium::Water
+        );
+
+        let [first, second] =
+            fixed_data_records(StatusField::empty(), 0xE97E, &counter1, &counter2);
+        assert_eq!(first.value, 1);
+        assert!(!first.historic);
+        let information = first.value_information.unwrap();
+        assert_eq!(information.labels.as_slice(), &[ValueLabel::Volume]);
+        assert_eq!(information.decimal_scale_exponent, -3);
+
+        assert_eq!(second.value, 135);
+        assert!(second.historic);
+        assert_eq!(second.unit_code, 0x3E);
+        assert_eq!(second.raw_bytes, [0x35, 0x01, 0x00, 0x00]);
+        assert_eq!(
+            second.value_information.unwrap().labels.as_slice(),
+            &[ValueLabel::Volume]
+        );
+    }
+
+    #[test]
+    fn binary_counters_are_signed() {
+        let counter = Counter::from_binary_bytes([0xFE, 0xFF, 0xFF, 0xFF]);
+        let [first, _] = fixed_data_records(
+            StatusField::COUNTER_BINARY_SIGNED | StatusField::COUNTER_FIXED_DATE,
+            0x0569,
+            &counter,
+            &counter,
+        );
+        assert_eq!(first.value, -2);
+        assert_eq!(first.data_field_coding, DataFieldCoding::Integer32Bit);
+        assert!(first.historic);
+        assert_eq!(
+            first.value_information.unwrap().units.as_slice(),
+            &[
+                Unit {
+                    name: UnitName::Watt,
+                    exponent: 1
+                },
+                Unit {
+                    name: UnitName::Hour,
+                    exponent: 1
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Readings of heat cost allocators.
+//!
+//! Heat cost allocators (EN 834) report the consumption units of the current
+//! billing period with storage number 0, and the units and date of the last
+//! set day, the end of the previous billing period, with a higher storage
+//! number, usually 1. The displayed units are either already rated or have to
+//! be multiplied by the resulting rating factor `K = KQ · KC · KT` of the
+//! radiator, which some allocators transmit with the VIF extension `0xFB`.
+//! [`HeatCostAllocation`] groups these records into billing periods and
+//! applies the rating factors.
+
+use m_bus_core::DeviceType;
+
+use crate::data_information::{DataType, FunctionField};
+use crate::history::StorageTimestamps;
+use crate::scaled_value::ScaledNumber;
+use crate::value_information::ValueLabel;
+use crate::DataRecords;
+
+/// Rating factors of the radiator an allocator is mounted on.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RatingFactors {
+    /// Resulting rating factor `K`.
+    pub resulting: Option<ScaledNumber>,
+    /// Thermal output rating factor `KQ`.
+    pub thermal_output: Option<ScaledNumber>,
+    /// Overall thermal coupling rating factor `KC`.
+    pub thermal_coupling: Option<ScaledNumber>,
+    /// Low temperature rating factor `KT`.
+    pub low_temperature: Option<ScaledNumber>,
+}
+
+impl RatingFactors {
+    /// Returns the transmitted resulting rating factor, or the product of the
+    /// individual factors that are transmitted.
+    #[must_use]
+    pub fn resulting_factor(&self) -> Option<ScaledNumber> {
+        self.resulting.or_else(|| {
+            [
+                self.thermal_output,
+                self.thermal_coupling,
+                self.low_temperature,
+            ]
+            .into_iter()
+            .flatten()
+            .reduce(multiply)
+        })
+    }
+
+    fn record(&mut self, label: ValueLabel, value: ScaledNumber) {
+        let factor = match label {
+            ValueLabel::ResultingRatingFactor => &mut self.resulting,
+            ValueLabel::ThermalOutputRatingFactor => &mut self.thermal_output,
+            ValueLabel::ThermalCouplingRatingFactorOverall => &mut self.thermal_coupling,
+            ValueLabel::LowTemperatureRatingFactor => &mut self.low_temperature,
+            _ => return,
+        };
+        factor.get_or_insert(value);
+    }
+}
+
+/// Consumption of one billing period.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BillingPeriodReading<'a> {
+    pub storage_number: u64,
+    /// Consumption units as displayed by the allocator.
+    pub units: ScaledNumber,
+    /// `units` multiplied by the resulting rating factor, or `units` if no
+    /// rating factor is transmitted.
+    pub consumption: ScaledNumber,
+    /// Date of the reading: the set day for previous billing periods, the
+    /// current date for the current one.
+    pub date: Option<DataType<'a>>,
+}
+
+/// Current and previous billing period of a heat cost allocator.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HeatCostAllocation<'a> {
+    pub current: Option<BillingPeriodReading<'a>>,
+    /// Reading at the last set day, the lowest storage number above 0.
+    pub previous: Option<BillingPeriodReading<'a>>,
+    pub rating_factors: RatingFactors,
+}
+
+impl<'a> HeatCostAllocation<'a> {
+    /// Collects the billing periods from the records of a heat cost
+    /// allocator, skipping records that fail to parse. Returns `None` for
+    /// other devices and when no record holds heat cost allocation units.
+    pub fn collect(device_type: DeviceType, records: DataRecords<'a>) -> Option<Self> {
+        if device_type != DeviceType::HeatCostAllocator {
+            return None;
+        }
+        let mut allocation = Self::default();
+        let mut current = None;
+        let mut previous: Option<(u64, ScaledNumber)> = None;
+        let mut dates = StorageTimestamps::new();
+        for record in records.recovering().filter_map(Result::ok) {
+            if dates.insert(&record) {
+                continue;
+            }
+            let (Some(information), Some(scaled)) =
+                (record.data_information(), record.scaled_value())
+            else {
+                continue;
+            };
+            let Some(label) = scaled.label() else {
+                continue;
+            };
+            if label != ValueLabel::DimensionlessHCA {
+                allocation.rating_factors.record(label, scaled.value);
+                continue;
+            }
+            if information.function_field != FunctionField::InstantaneousValue
+                || information.tariff != 0
+                || information.device != 0
+            {
+                continue;
+            }
+            match information.storage_number {
+                0 => {
+                    current.get_or_insert(scaled.value);
+                }
+                storage_number => {
+                    if previous.is_none_or(|(known, _)| storage_number < known) {
+                        previous = Some((storage_number, scaled.value));
+                    }
+                }
+            }
+        }
+        if current.is_none() && previous.is_none() {
+            return None;
+        }
+        let factor = allocation.rating_factors.resulting_factor();
+        let reading = |storage_number, units| BillingPeriodReading {
+            storage_number,
+            units,
+            consumption: factor.map_or(units, |factor| multiply(units, factor)),
+            date: dates.timestamp(storage_number).cloned(),
+        };
+        allocation.current = current.map(|units| reading(0, units));
+        allocation.previous =
+            previous.map(|(storage_number, units)| reading(storage_number, units));
+        Some(allocation)
+    }
+}
+
+fn multiply(value: ScaledNumber, factor: ScaledNumber) -> ScaledNumber {
+    match factor {
+        ScaledNumber::Exact(factor) => value.times(factor),
+        ScaledNumber::Real(factor) => ScaledNumber::Real(value.to_f64() * factor),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn collect(device_type: DeviceType, data: &[u8]) -> Option<HeatCostAllocation<'_>> {
+        HeatCostAllocation::collect(device_type, DataRecords::new(data, None))
+    }
+
+    #[test]
+    fn groups_billing_periods_and_applies_rating_factor() {
+        let data = [
+            0x0B, 0x6E, 0x31, 0x01, 0x00, // 131 units
+            0x02, 0x6C, 0x88, 0x22, // 8 February 2020
+            0x42, 0x6C, 0x7F, 0x2C, // set day 31 December 2019
+            0x4B, 0x6E, 0x02, 0x10, 0x00, // 1002 units at the set day
+            0x02, 0xFB, 0xE8, 0x73, 0xDC, 0x05, // K = 1.5
+        ];
+        let allocation = collect(DeviceType::HeatCostAllocator, &data).unwrap();
+        let current = allocation.current.unwrap();
+        assert_eq!(current.storage_number, 0);
+        assert_eq!(current.units.to_string(), "131");
+        assert_eq!(current.consumption.to_string(), "196.5");
+        assert_eq!(
+            format!("{:?}", current.date.unwrap()),
+            "Date(Single(8), Single(February), Single(2020))"
+        );
+        let previous = allocation.previous.unwrap();
+        assert_eq!(previous.storage_number, 1);
+        assert_eq!(previous.units.to_string(), "1002");
+        assert_eq!(previous.consumption.to_string(), "1503");
+        assert_eq!(
+            format!("{:?}", previous.date.unwrap()),
+            "Date(Single(31), Single(December), Single(2019))"
+        );
+        assert_eq!(
+            allocation.rating_factors.resulting.map(|k| k.to_string()),
+            Some("1.5".to_string())
+        );
+    }
+
+    #[test]
+    fn individual_rating_factors_are_multiplied() {
+        let data = [
+            0x0B, 0x6E, 0x00, 0x01, 0x00, // 100 units
+            0x02, 0xFB, 0xE9, 0x73, 0xD0, 0x07, // KQ = 2
+            0x02, 0xFB, 0xEA, 0x73, 0xF4, 0x01, // KC = 0.5
+            0x02, 0xFB, 0xED, 0x73, 0xB0, 0x04, // KT = 1.2
+        ];
+        let allocation = collect(DeviceType::HeatCostAllocator, &data).unwrap();
+        assert!(allocation.previous.is_none());
+        let current = allocation.current.unwrap();
+        assert_eq!(current.consumption.to_string(), "120");
+    }
+
+    #[test]
+    fn other_devices_and_records_are_ignored() {
+        let units = [0x0B, 0x6E, 0x31, 0x01, 0x00];
+        assert!(collect(DeviceType::WaterMeter, &units).is_none());
+        let volume = [0x04, 0x13, 0x31, 0x01, 0x00, 0x00];
+        assert!(collect(DeviceType::HeatCostAllocator, &volume).is_none());
+    }
+
+    #[test]
+    fn faulty_records_are_skipped() {
+        let data = [
+            0x0A, 0x13, 0xAB, 0x0C, // 4-digit BCD with non-decimal digits
+            0x0B, 0x6E, 0x31, 0x01, 0x00, // 131 units
+        ];
+        let allocation = collect(DeviceType::HeatCostAllocator, &data).unwrap();
+        assert_eq!(allocation.current.unwrap().units.to_string(), "131");
+    }
+}
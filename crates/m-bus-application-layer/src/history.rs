@@ -0,0 +1,248 @@
+//! Historic values by storage number.
+//!
+//! Meters report earlier readings, e.g. at the end of each month, as records
+//! with increasing storage numbers, and the date of each reading as a date or
+//! date and time record with the same storage number. [`StorageTimestamps`]
+//! collects these dates, and [`TimeSeries`] groups the records of each
+//! quantity into a series with one point per storage number.
+
+use arrayvec::ArrayVec;
+
+use crate::data_information::{DataType, FunctionField};
+use crate::data_record::DataRecord;
+use crate::value_information::{Unit, ValueLabel};
+use crate::DataRecords;
+
+/// Maximum number of storage numbers tracked by [`StorageTimestamps`].
+pub const MAX_STORAGE_TIMESTAMPS: usize = 32;
+
+/// Returns the storage number and value of a record holding the date or date
+/// and time of its storage number: an instantaneous date or date and time
+/// record without tariff and subunit.
+#[must_use]
+pub fn timestamp_of<'a>(record: &DataRecord<'a>) -> Option<(u64, DataType<'a>)> {
+    let information = record.data_information()?;
+    let labels = &record.value_information()?.labels;
+    if information.function_field != FunctionField::InstantaneousValue
+        || information.tariff != 0
+        || information.device != 0
+        || !(labels.contains(&ValueLabel::Date) || labels.contains(&ValueLabel::DateTime))
+    {
+        return None;
+    }
+    let value = record.data.value.clone()?;
+    matches!(
+        value,
+        DataType::Date(..)
+            | DataType::DateTime(..)
+            | DataType::DateTimeWithSeconds(..)
+            | DataType::DateTimeWithOffset(..)
+    )
+    .then_some((information.storage_number, value))
+}
+
+/// Dates of the storage numbers of a frame.
+#[derive(Debug, Clone, Default)]
+pub struct StorageTimestamps<'a> {
+    entries: ArrayVec<(u64, DataType<'a>), MAX_STORAGE_TIMESTAMPS>,
+}
+
+impl<'a> StorageTimestamps<'a> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the dates of all records, skipping faulty records.
+    pub fn collect(records: DataRecords<'a>) -> Self {
+        let mut timestamps = Self::new();
+        for record in records.recovering().filter_map(Result::ok) {
+            timestamps.insert(&record);
+        }
+        timestamps
+    }
+
+    /// Stores the date of a date record unless its storage number already
+    /// has one. Returns `false` for other records and when no capacity is
+    /// left.
+    pub fn insert(&mut self, record: &DataRecord<'a>) -> bool {
+        let Some((storage_number, value)) = timestamp_of(record) else {
+            return false;
+        };
+        if self.timestamp(storage_number).is_some() {
+            return true;
+        }
+        self.entries.try_push((storage_number, value)).is_ok()
+    }
+
+    /// Returns the date of the readings with this storage number.
+    #[must_use]
+    pub fn timestamp(&self, storage_number: u64) -> Option<&DataType<'a>> {
+        self.entries
+            .iter()
+            .find(|(known, _)| *known == storage_number)
+            .map(|(_, value)| value)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// The quantity of a record, shared by its readings at all storage numbers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesKey {
+    pub labels: ArrayVec<ValueLabel, 10>,
+    pub units: ArrayVec<Unit, 10>,
+    pub function: FunctionField,
+    pub tariff: u64,
+    pub subunit: u64,
+}
+
+impl SeriesKey {
+    /// Returns the quantity of a record with data and value information.
+    #[must_use]
+    pub fn of(record: &DataRecord<'_>) -> Option<Self> {
+        let information = record.data_information()?;
+        let value_information = record.value_information()?;
+        Some(Self {
+            labels: value_information.labels.clone(),
+            units: value_information.units.clone(),
+            function: information.function_field,
+            tariff: information.tariff,
+            subunit: information.device,
+        })
+    }
+}
+
+/// Reading of a quantity at one storage number.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeriesPoint<'a> {
+    pub storage_number: u64,
+    /// Date of the storage number, if the frame holds one.
+    pub timestamp: Option<DataType<'a>>,
+    pub record: DataRecord<'a>,
+}
+
+/// Readings of one quantity, ordered by storage number.
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeries<'a> {
+    pub key: SeriesKey,
+    pub points: Vec<TimeSeriesPoint<'a>>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> TimeSeries<'a> {
+    /// Groups the records by quantity, in the order the quantities first
+    /// appear. Faulty records, date records and records without value
+    /// information are left out.
+    pub fn collect(records: DataRecords<'a>) -> Vec<Self> {
+        let records: Vec<_> = records.recovering().filter_map(Result::ok).collect();
+        let mut timestamps = StorageTimestamps::new();
+        for record in &records {
+            timestamps.insert(record);
+        }
+        let mut series: Vec<Self> = Vec::new();
+        for record in records {
+            if timestamp_of(&record).is_some() {
+                continue;
+            }
+            let (Some(key), Some(information)) =
+                (SeriesKey::of(&record), record.data_information())
+            else {
+                continue;
+            };
+            let storage_number = information.storage_number;
+            let point = TimeSeriesPoint {
+                storage_number,
+                timestamp: timestamps.timestamp(storage_number).cloned(),
+                record,
+            };
+            match series.iter_mut().find(|series| series.key == key) {
+                Some(series) => series.points.push(point),
+                None => series.push(Self {
+                    key,
+                    points: vec![point],
+                }),
+            }
+        }
+        for series in &mut series {
+            series.points.sort_by_key(|point| point.storage_number);
+        }
+        series
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const MONTHLY_VOLUMES: &[u8] = &[
+        0x04, 0x13, 0xE8, 0x03, 0x00, 0x00, // 1000 l
+        0x04, 0x6D, 0x00, 0x0C, 0x21, 0x3C, // 1 December 2025 12:00
+        0x44, 0x13, 0x84, 0x03, 0x00, 0x00, // 900 l at storage 1
+        0x84, 0x01, 0x13, 0x20, 0x03, 0x00, 0x00, // 800 l at storage 2
+        0x42, 0x6C, 0x3F, 0x3A, // 31 October 2025 at storage 1
+        0x02, 0xFD, // partial record, ignored
+    ];
+
+    #[test]
+    fn timestamps_by_storage_number() {
+        let timestamps = StorageTimestamps::collect(DataRecords::new(MONTHLY_VOLUMES, None));
+        assert_eq!(timestamps.len(), 2);
+        assert!(matches!(
+            timestamps.timestamp(0),
+            Some(DataType::DateTime(..))
+        ));
+        assert!(matches!(timestamps.timestamp(1), Some(DataType::Date(..))));
+        assert!(timestamps.timestamp(2).is_none());
+    }
+
+    #[test]
+    fn groups_storage_numbers_into_series() {
+        let series = TimeSeries::collect(DataRecords::new(MONTHLY_VOLUMES, None));
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].key.labels.as_slice(), [ValueLabel::Volume]);
+        let points: Vec<_> = series[0]
+            .points
+            .iter()
+            .map(|point| {
+                (
+                    point.storage_number,
+                    point.timestamp.is_some(),
+                    point.record.scaled_value().unwrap().value.to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            points,
+            [
+                (0, true, "1".to_string()),
+                (1, true, "0.9".to_string()),
+                (2, false, "0.8".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn faulty_records_are_skipped() {
+        let mut data = vec![0x0A, 0x13, 0xAB, 0x0C];
+        data.extend_from_slice(MONTHLY_VOLUMES);
+        let timestamps = StorageTimestamps::collect(DataRecords::new(&data, None));
+        assert_eq!(timestamps.len(), 2);
+        let series = TimeSeries::collect(DataRecords::new(&data, None));
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].points.len(), 3);
+    }
+}
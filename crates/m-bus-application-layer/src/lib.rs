@@ -18,7 +18,7 @@
 //!     let mut records = parse_data_records(&data);
 //!     let record = records.next().expect("one data record")?;
 //!
-//!     assert_eq!(record.value(), Some(&DataType::Number(12_565.0)));
+//!     assert_eq!(record.value(), Some(&DataType::Integer(12_565)));
 //!     assert_eq!(record.raw_bytes(), &data);
 //!     Ok(())
 //! }
@@ -40,9 +40,23 @@ pub use self::data_record::DataRecord;
 use m_bus_core::decryption::DecryptionError::{NotEncrypted, UnknownEncryptionState};
 pub use m_bus_core::ApplicationLayerError;
 
+pub mod compact_frame;
+pub mod compact_profile;
+pub mod cosem;
 pub mod data_information;
 pub mod data_record;
+pub mod encoder;
 pub mod extended_link_layer;
+pub mod fixed_data_structure;
+pub mod heat_cost_allocator;
+pub mod history;
+pub mod manufacturer_specific;
+pub mod network_layer;
+pub mod obis;
+pub mod register;
+pub mod scaled_value;
+pub mod status;
+pub mod unit_conversion;
 pub mod value_information;
 pub mod variable_user_data;
 
@@ -81,6 +95,7 @@ pub struct DataRecords<'a> {
     offset: usize,
     data: &'a [u8],
     long_tpl_header: Option<&'a LongTplHeader>,
+    plaintext_vif_order: value_information::PlaintextVifOrder,
 }
 
 #[cfg(feature = "std")]
@@ -95,7 +110,142 @@ impl<'a> Iterator for DataRecords<'a> {
     type Item = Result<DataRecord<'a>, DataRecordError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_record(false).map(|(_, record)| record)
+    }
+}
+
+/// A record that failed to parse and was skipped by [`RecoveringDataRecords`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SkippedRecord {
+    pub error: DataRecordError,
+    /// Offset of the first byte of the record in the record data.
+    pub start: usize,
+    /// Offset after the last skipped byte. Records whose length cannot be
+    /// determined from the DIF are skipped up to the end of the data.
+    pub end: usize,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for SkippedRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "skipped bytes {}..{}: {}",
+            self.start, self.end, self.error
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SkippedRecord {}
+
+/// Iterator over data records that skips a record that fails to parse, e.g.
+/// because of an unknown VIF, and continues with the next one. The length of
+/// the faulty record is taken from its DIF, see [`DataRecords::recovering`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecoveringDataRecords<'a> {
+    records: DataRecords<'a>,
+}
+
+impl<'a> Iterator for RecoveringDataRecords<'a> {
+    type Item = Result<DataRecord<'a>, SkippedRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, record) = self.records.next_record(true)?;
+        Some(record.map_err(|error| SkippedRecord {
+            error,
+            start,
+            end: self.records.offset,
+        }))
+    }
+}
+
+impl<'a> DataRecords<'a> {
+    #[must_use]
+    pub const fn new(data: &'a [u8], long_tpl_header: Option<&'a LongTplHeader>) -> Self {
+        DataRecords {
+            offset: 0,
+            data,
+            long_tpl_header,
+            plaintext_vif_order: value_information::PlaintextVifOrder::FEATURE_DEFAULT,
+        }
+    }
+
+    /// Parses plaintext units in the given position instead of the one the
+    /// `plaintext-before-extension` feature selects, see
+    /// [`PlaintextVifOrder`](value_information::PlaintextVifOrder).
+    #[must_use]
+    pub const fn with_plaintext_vif_order(
+        mut self,
+        plaintext_vif_order: value_information::PlaintextVifOrder,
+    ) -> Self {
+        self.plaintext_vif_order = plaintext_vif_order;
+        self
+    }
+
+    /// Continues after records that fail to parse instead of ending the
+    /// iteration with the first error. Each faulty record is skipped by the
+    /// data length its DIF declares and reported as [`SkippedRecord`].
+    #[must_use]
+    pub const fn recovering(self) -> RecoveringDataRecords<'a> {
+        RecoveringDataRecords { records: self }
+    }
+
+    /// Returns the manufacturer, version and device type of the long transport
+    /// header the records were parsed with.
+    #[must_use]
+    pub fn meter_identity(&self) -> Option<manufacturer_specific::MeterIdentity> {
+        self.long_tpl_header
+            .and_then(|header| manufacturer_specific::MeterIdentity::try_from(header).ok())
+    }
+
+    /// Decodes manufacturer specific records with the decoder `registry`
+    /// selects for the meter. The meter is taken from the long transport
+    /// header unless given, e.g. from the wireless link layer.
+    #[must_use]
+    pub fn with_decoders<'r, R: manufacturer_specific::DecoderRegistry + ?Sized>(
+        self,
+        registry: &'r R,
+        meter: Option<manufacturer_specific::MeterIdentity>,
+    ) -> manufacturer_specific::DecodedDataRecords<'a, 'r, R> {
+        manufacturer_specific::DecodedDataRecords::new(self, registry, meter)
+    }
+
+    /// Returns the dates of the storage numbers, see [`history`].
+    #[must_use]
+    pub fn storage_timestamps(&self) -> history::StorageTimestamps<'a> {
+        history::StorageTimestamps::collect(self.clone())
+    }
+
+    /// Groups the records by quantity into series of readings by storage
+    /// number, each with the date of its storage number.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn time_series(&self) -> Vec<history::TimeSeries<'a>> {
+        history::TimeSeries::collect(self.clone())
+    }
+
+    /// Expands the compact profiles into timestamped readings, see
+    /// [`compact_profile`].
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn compact_profiles(
+        &self,
+    ) -> Vec<Result<compact_profile::ExpandedProfile<'a>, compact_profile::CompactProfileError>>
+    {
+        compact_profile::ExpandedProfile::collect(self.clone())
+    }
+
+    /// Returns the next record with its offset in the record data.
+    fn next_record(
+        &mut self,
+        recover: bool,
+    ) -> Option<(usize, Result<DataRecord<'a>, DataRecordError>)> {
         while self.offset < self.data.len() {
+            let start = self.offset;
             let dif = data_information::DataInformationField::from(*self.data.get(self.offset)?);
 
             if dif.is_special_function() {
@@ -107,41 +257,46 @@ impl<'a> Iterator for DataRecords<'a> {
                     | data_information::SpecialFunctions::MoreRecordsFollow => {
                         let remaining = self.data.get(self.offset..)?;
                         self.offset = self.data.len();
-                        let record = if let Some(long_tpl_header) = self.long_tpl_header {
-                            DataRecord::try_from((remaining, long_tpl_header))
-                        } else {
-                            DataRecord::try_from(remaining)
-                        };
-                        return Some(record);
+                        let record = DataRecord::parse(
+                            remaining,
+                            self.long_tpl_header,
+                            self.plaintext_vif_order,
+                        );
+                        return Some((start, record));
                     }
                     data_information::SpecialFunctions::GlobalReadoutRequest => {
                         let remaining = self.data.get(self.offset..)?;
                         self.offset += 1;
-                        let record = if let Some(long_tpl_header) = self.long_tpl_header {
-                            DataRecord::try_from((remaining, long_tpl_header))
-                        } else {
-                            DataRecord::try_from(remaining)
-                        };
-                        return Some(record);
+                        let record = DataRecord::parse(
+                            remaining,
+                            self.long_tpl_header,
+                            self.plaintext_vif_order,
+                        );
+                        return Some((start, record));
                     }
                     data_information::SpecialFunctions::Reserved => {
                         self.offset += 1;
                     }
                 }
             } else {
-                let record = if let Some(long_tpl_header) = self.long_tpl_header {
-                    DataRecord::try_from((self.data.get(self.offset..)?, long_tpl_header))
-                } else {
-                    DataRecord::try_from(self.data.get(self.offset..)?)
-                };
+                let record = DataRecord::parse(
+                    self.data.get(self.offset..)?,
+                    self.long_tpl_header,
+                    self.plaintext_vif_order,
+                );
                 match record {
                     Ok(record) => {
                         self.offset += record.get_size();
-                        return Some(Ok(record));
+                        return Some((start, Ok(record)));
                     }
                     Err(error) => {
-                        self.offset = self.data.len();
-                        return Some(Err(error));
+                        self.offset = self
+                            .data
+                            .get(start..)
+                            .and_then(|data| DataRecord::raw_length(data, self.plaintext_vif_order))
+                            .filter(|_| recover)
+                            .map_or(self.data.len(), |length| start + length);
+                        return Some((start, Err(error)));
                     }
                 }
             }
@@ -150,17 +305,6 @@ impl<'a> Iterator for DataRecords<'a> {
     }
 }
 
-impl<'a> DataRecords<'a> {
-    #[must_use]
-    pub const fn new(data: &'a [u8], long_tpl_header: Option<&'a LongTplHeader>) -> Self {
-        DataRecords {
-            offset: 0,
-            data,
-            long_tpl_header,
-        }
-    }
-}
-
 bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -249,6 +393,7 @@ impl From<ControlInformation> for Direction {
             ControlInformation::ReadEEPROM => Self::MasterToSlave,
             ControlInformation::StartSoftwareTest => Self::MasterToSlave,
             ControlInformation::HashProcedure(_) => Self::MasterToSlave,
+            ControlInformation::ManufacturerSpecific(_) => Self::SlaveToMaster,
             ControlInformation::SendErrorStatus => Self::SlaveToMaster,
             ControlInformation::SendAlarmStatus => Self::SlaveToMaster,
             ControlInformation::ResponseWithVariableDataStructure { lsb_order: _ } => {
@@ -316,9 +461,14 @@ pub enum ControlInformation {
     ReadEEPROM,
     StartSoftwareTest,
     HashProcedure(u8),
+    /// Proprietary application layer (CI `0xA0` to `0xAF`), see
+    /// [`manufacturer_specific`].
+    ManufacturerSpecific(u8),
     SendErrorStatus,
     SendAlarmStatus,
-    ResponseWithVariableDataStructure { lsb_order: bool },
+    ResponseWithVariableDataStructure {
+        lsb_order: bool,
+    },
     ResponseWithFixedDataStructure,
     // Wireless M-Bus CI values
     DataSentWithShortTransportLayer,
@@ -407,13 +557,7 @@ impl ControlInformation {
             0x8D => Ok(Self::ExtendedLinkLayerII),
             0x8E => Ok(Self::ExtendedLinkLayerIII),
             0x90..=0x97 => Ok(Self::HashProcedure(byte - 0x90)),
-            // Encrypted CI codes (0xA0-0xAF) - these are encrypted variants of 0x7A (ApplicationLayerShortTransport)
-            // When used with NOKEY, they should be parsed as unencrypted ApplicationLayerShortTransport
-            // The lower 4 bits indicate the encryption mode:
-            //   0xA0 = Mode 5 (AES-CBC-IV zero) or NOKEY
-            //   0xA2 = Mode 7 (AES-CBC-IV non-zero) or NOKEY
-            //   0xA4, 0xA6, etc. = other modes
-            0xA0..=0xAF => Ok(Self::ApplicationLayerShortTransport),
+            0xA0..=0xAF => Ok(Self::ManufacturerSpecific(byte)),
             0xB1 => Ok(Self::OutputRAMContent),
             0xB2 => Ok(Self::WriteRAMContent),
             0xB3 => Ok(Self::StartCalibrationTestMode),
@@ -524,6 +668,20 @@ impl Counter {
         let count = bcd_hex_digits_to_u32(digits)?;
         Ok(Self { count })
     }
+
+    /// Creates a counter from little-endian binary bytes. Signed counters are
+    /// stored as their two's complement bits.
+    #[must_use]
+    pub const fn from_binary_bytes(bytes: [u8; 4]) -> Self {
+        Self {
+            count: u32::from_le_bytes(bytes),
+        }
+    }
+
+    #[must_use]
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -545,6 +703,9 @@ pub enum UserDataBlock<'a> {
     },
     VariableDataStructureWithLongTplHeader {
         extended_link_layer: Option<ExtendedLinkLayer>,
+        /// Transport layer header (CI `0x80`, `0x8A`, `0x8B`) in front of
+        /// this block.
+        transport_layer_header: Option<TransportLayerHeader>,
         long_tpl_header: LongTplHeader,
         #[cfg_attr(feature = "serde", serde(skip_serializing))]
         variable_data_block: &'a [u8],
@@ -552,6 +713,9 @@ pub enum UserDataBlock<'a> {
 
     VariableDataStructureWithShortTplHeader {
         extended_link_layer: Option<ExtendedLinkLayer>,
+        /// Transport layer header (CI `0x80`, `0x8A`, `0x8B`) in front of
+        /// this block.
+        transport_layer_header: Option<TransportLayerHeader>,
         short_tpl_header: ShortTplHeader,
         #[cfg_attr(feature = "serde", serde(skip_serializing))]
         variable_data_block: &'a [u8],
@@ -562,6 +726,116 @@ pub enum UserDataBlock<'a> {
         #[cfg_attr(feature = "serde", serde(skip_serializing))]
         variable_data_block: &'a [u8],
     },
+
+    /// Compact frame: format signature, full-frame CRC and record data
+    /// without DIF/VIF headers, see [`compact_frame`].
+    CompactDataStructureWithShortTplHeader {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        /// Transport layer header (CI `0x80`, `0x8A`, `0x8B`) in front of
+        /// this block.
+        transport_layer_header: Option<TransportLayerHeader>,
+        short_tpl_header: ShortTplHeader,
+        #[cfg_attr(feature = "serde", serde(skip_serializing))]
+        compact_data_block: &'a [u8],
+    },
+
+    CompactDataStructureWithoutTplHeader {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing))]
+        compact_data_block: &'a [u8],
+    },
+
+    /// Format frame: the DIF/VIF layout of the records without their data.
+    FormatFrameWithLongTplHeader {
+        /// Transport layer header (CI `0x80`, `0x8A`, `0x8B`) in front of
+        /// this block.
+        transport_layer_header: Option<TransportLayerHeader>,
+        long_tpl_header: LongTplHeader,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        record_layout: compact_frame::RecordLayout<'a>,
+    },
+
+    FormatFrameWithShortTplHeader {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        /// Transport layer header (CI `0x80`, `0x8A`, `0x8B`) in front of
+        /// this block.
+        transport_layer_header: Option<TransportLayerHeader>,
+        short_tpl_header: ShortTplHeader,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        record_layout: compact_frame::RecordLayout<'a>,
+    },
+
+    FormatFrameWithoutTplHeader {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        record_layout: compact_frame::RecordLayout<'a>,
+    },
+
+    /// DLMS/COSEM APDU (CI `0x60`, `0x7C`), see [`cosem`].
+    CosemDataStructureWithLongTplHeader {
+        long_tpl_header: LongTplHeader,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        cosem_payload: cosem::CosemPayload<'a>,
+    },
+
+    /// DLMS/COSEM APDU (CI `0x61`, `0x7D`), see [`cosem`].
+    CosemDataStructureWithShortTplHeader {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        short_tpl_header: ShortTplHeader,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        cosem_payload: cosem::CosemPayload<'a>,
+    },
+
+    /// Data records keyed by OBIS codes (CI `0x64`, `0x7E`), see [`obis`].
+    ObisDataStructureWithLongTplHeader {
+        long_tpl_header: LongTplHeader,
+        #[cfg_attr(feature = "serde", serde(skip_serializing))]
+        obis_data_block: &'a [u8],
+    },
+
+    /// Data records keyed by OBIS codes (CI `0x65`, `0x7F`), see [`obis`].
+    ObisDataStructureWithShortTplHeader {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        short_tpl_header: ShortTplHeader,
+        #[cfg_attr(feature = "serde", serde(skip_serializing))]
+        obis_data_block: &'a [u8],
+    },
+
+    /// Network layer header of a relayed frame (CI `0x81`) followed by the
+    /// next CI field, see [`network_layer`] and [`Self::split_network_layer`].
+    NetworkLayer {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        network_layer_header: network_layer::NetworkLayerHeader,
+        #[cfg_attr(feature = "serde", serde(skip_serializing))]
+        network_layer_data: &'a [u8],
+    },
+
+    /// Network management application (CI `0x83`) addressed to repeaters.
+    NetworkManagement {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing))]
+        network_management_data: &'a [u8],
+    },
+
+    /// Proprietary application layer (CI `0xA0` to `0xAF`) that only a
+    /// [`manufacturer_specific::ManufacturerDecoder`] can interpret.
+    ManufacturerSpecific {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        control_information: u8,
+        #[cfg_attr(feature = "serde", serde(skip_serializing))]
+        manufacturer_data: &'a [u8],
+    },
+
+    /// Pure transport layer header (CI `0x80`, `0x8A`, `0x8B`). A following
+    /// application layer with its own CI is folded into the matching
+    /// variant with TPL header instead; anything else is kept in
+    /// `application_data`.
+    TransportLayer {
+        extended_link_layer: Option<ExtendedLinkLayer>,
+        transport_layer_header: TransportLayerHeader,
+        #[cfg_attr(feature = "serde", serde(skip_serializing))]
+        application_data: &'a [u8],
+    },
 }
 
 impl<'a> UserDataBlock<'a> {
@@ -589,10 +863,111 @@ impl<'a> UserDataBlock<'a> {
                 variable_data_block,
                 ..
             } => Some(parse_data_records(variable_data_block)),
+            Self::ObisDataStructureWithLongTplHeader {
+                long_tpl_header,
+                obis_data_block,
+            } if !long_tpl_header.is_encrypted() => Some(parse_data_records_with_header(
+                obis_data_block,
+                long_tpl_header,
+            )),
+            Self::ObisDataStructureWithShortTplHeader {
+                short_tpl_header,
+                obis_data_block,
+                ..
+            } if !short_tpl_header.is_encrypted() => Some(parse_data_records(obis_data_block)),
+            _ => None,
+        }
+    }
+
+    /// Returns the manufacturer, version and device type of a long transport
+    /// header, used to select manufacturer specific decoders.
+    #[must_use]
+    pub fn meter_identity(&self) -> Option<manufacturer_specific::MeterIdentity> {
+        match self {
+            Self::VariableDataStructureWithLongTplHeader {
+                long_tpl_header, ..
+            }
+            | Self::ObisDataStructureWithLongTplHeader {
+                long_tpl_header, ..
+            } => manufacturer_specific::MeterIdentity::try_from(long_tpl_header).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the status byte of the transport header of a variable, compact,
+    /// OBIS or COSEM data structure, see [`status`]. Fixed data structures,
+    /// whose status bits 0 and 1 describe the counters, and headers built
+    /// from an encrypted extended link layer have none.
+    #[must_use]
+    pub fn status(&self) -> Option<StatusField> {
+        match self {
+            Self::VariableDataStructureWithLongTplHeader {
+                long_tpl_header, ..
+            }
+            | Self::ObisDataStructureWithLongTplHeader {
+                long_tpl_header, ..
+            }
+            | Self::CosemDataStructureWithLongTplHeader {
+                long_tpl_header, ..
+            } => Some(long_tpl_header.short_tpl_header.status),
+            Self::VariableDataStructureWithShortTplHeader {
+                extended_link_layer:
+                    Some(ExtendedLinkLayer {
+                        encryption: Some(_),
+                        ..
+                    }),
+                short_tpl_header,
+                ..
+            } if matches!(
+                short_tpl_header.configuration_field.security_mode(),
+                m_bus_core::SecurityMode::NoEncryption
+            ) =>
+            {
+                None
+            }
+            Self::VariableDataStructureWithShortTplHeader {
+                short_tpl_header, ..
+            }
+            | Self::CompactDataStructureWithShortTplHeader {
+                short_tpl_header, ..
+            }
+            | Self::ObisDataStructureWithShortTplHeader {
+                short_tpl_header, ..
+            }
+            | Self::CosemDataStructureWithShortTplHeader {
+                short_tpl_header, ..
+            } => Some(short_tpl_header.status),
+            _ => None,
+        }
+    }
+
+    /// Returns the proprietary application layer of a block with a
+    /// manufacturer specific CI.
+    #[must_use]
+    pub const fn manufacturer_payload(
+        &self,
+    ) -> Option<manufacturer_specific::ManufacturerPayload<'a>> {
+        match self {
+            Self::ManufacturerSpecific {
+                control_information,
+                manufacturer_data,
+                ..
+            } => Some(manufacturer_specific::ManufacturerPayload::new(
+                *control_information,
+                manufacturer_data,
+            )),
             _ => None,
         }
     }
 
+    /// Returns the OBIS codes declared by the data records of this block.
+    #[must_use]
+    pub fn obis_declarations(&self) -> obis::ObisDeclarations {
+        self.data_records()
+            .map(obis::ObisDeclarations::collect)
+            .unwrap_or_default()
+    }
+
     #[must_use]
     pub fn is_encrypted(&self) -> Option<bool> {
         match self {
@@ -623,6 +998,282 @@ impl<'a> UserDataBlock<'a> {
         }
     }
 
+    /// Returns an iterator over the OBIS-coded values of a COSEM payload, or
+    /// `None` for other blocks and encrypted payloads.
+    #[must_use]
+    pub fn cosem_records(&self) -> Option<cosem::CosemRecords<'a>> {
+        match self {
+            Self::CosemDataStructureWithLongTplHeader {
+                long_tpl_header,
+                cosem_payload,
+            } if !long_tpl_header.is_encrypted() => Some(cosem_payload.records()),
+            Self::CosemDataStructureWithShortTplHeader {
+                short_tpl_header,
+                cosem_payload,
+                ..
+            } if !short_tpl_header.is_encrypted() => Some(cosem_payload.records()),
+            _ => None,
+        }
+    }
+
+    /// Separates the network layer header of a relayed frame from the block
+    /// that follows it. Other blocks are returned unchanged.
+    pub fn split_network_layer(
+        self,
+    ) -> Result<(Option<network_layer::NetworkLayerHeader>, Self), ApplicationLayerError> {
+        match self {
+            Self::NetworkLayer {
+                extended_link_layer,
+                network_layer_header,
+                network_layer_data,
+            } => {
+                let mut inner = UserDataBlock::try_from(network_layer_data)?;
+                if let Some(ell) = extended_link_layer {
+                    inner.set_extended_link_layer(ell);
+                }
+                Ok((Some(network_layer_header), inner))
+            }
+            other => Ok((None, other)),
+        }
+    }
+
+    /// Attaches an extended link layer parsed in front of this block, unless
+    /// the block already has one.
+    fn set_extended_link_layer(&mut self, ell: ExtendedLinkLayer) {
+        match self {
+            Self::VariableDataStructureWithLongTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::VariableDataStructureWithShortTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::VariableDataStructureWithoutTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::CompactDataStructureWithShortTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::CompactDataStructureWithoutTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::FormatFrameWithShortTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::FormatFrameWithoutTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::CosemDataStructureWithShortTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::ObisDataStructureWithShortTplHeader {
+                extended_link_layer,
+                ..
+            }
+            | Self::NetworkLayer {
+                extended_link_layer,
+                ..
+            }
+            | Self::NetworkManagement {
+                extended_link_layer,
+                ..
+            }
+            | Self::ManufacturerSpecific {
+                extended_link_layer,
+                ..
+            }
+            | Self::TransportLayer {
+                extended_link_layer,
+                ..
+            } => {
+                extended_link_layer.get_or_insert(ell);
+            }
+            _ => {}
+        }
+    }
+
+    /// Decodes the two counters of a fixed data structure with their units.
+    #[must_use]
+    pub fn fixed_data_records(&self) -> Option<[fixed_data_structure::FixedDataRecord; 2]> {
+        match self {
+            Self::FixedDataStructure {
+                status,
+                device_type_and_unit,
+                counter1,
+                counter2,
+                ..
+            } => Some(fixed_data_structure::fixed_data_records(
+                *status,
+                *device_type_and_unit,
+                counter1,
+                counter2,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the bidirectional transport layer header (CI `0x80`, `0x8A`,
+    /// `0x8B`) that carried this block.
+    #[must_use]
+    pub const fn transport_layer_header(&self) -> Option<&TransportLayerHeader> {
+        match self {
+            Self::VariableDataStructureWithLongTplHeader {
+                transport_layer_header,
+                ..
+            }
+            | Self::VariableDataStructureWithShortTplHeader {
+                transport_layer_header,
+                ..
+            }
+            | Self::CompactDataStructureWithShortTplHeader {
+                transport_layer_header,
+                ..
+            }
+            | Self::FormatFrameWithLongTplHeader {
+                transport_layer_header,
+                ..
+            }
+            | Self::FormatFrameWithShortTplHeader {
+                transport_layer_header,
+                ..
+            } => transport_layer_header.as_ref(),
+            Self::TransportLayer {
+                transport_layer_header,
+                ..
+            } => Some(transport_layer_header),
+            _ => None,
+        }
+    }
+
+    /// Returns the CI of the bidirectional transport layer header (`0x80`,
+    /// `0x8A`, `0x8B`) that carried this block.
+    #[must_use]
+    pub const fn transport_control_information(&self) -> Option<u8> {
+        match self.transport_layer_header() {
+            Some(header) => Some(header.control_information()),
+            None => None,
+        }
+    }
+
+    /// Stores the DIF/VIF layout of this full frame or format frame so that
+    /// later compact frames can reference it. Returns the format signature.
+    pub fn learn_format<C: compact_frame::FormatSignatureCache + ?Sized>(
+        &self,
+        cache: &mut C,
+    ) -> Result<u16, compact_frame::CompactFrameError> {
+        match self {
+            Self::VariableDataStructureWithLongTplHeader { .. }
+            | Self::VariableDataStructureWithShortTplHeader { .. }
+            | Self::VariableDataStructureWithoutTplHeader { .. } => {
+                if self.is_payload_encrypted() {
+                    return Err(compact_frame::CompactFrameError::Encrypted);
+                }
+                let data = self.payload();
+                cache.learn(data)
+            }
+            Self::FormatFrameWithLongTplHeader { record_layout, .. }
+            | Self::FormatFrameWithShortTplHeader { record_layout, .. }
+            | Self::FormatFrameWithoutTplHeader { record_layout, .. } => record_layout.store(cache),
+            _ => Err(compact_frame::CompactFrameError::UnsupportedDataBlock),
+        }
+    }
+
+    /// Rebuilds the full data records of a compact frame into `output` using
+    /// a previously learned format. Returns the number of bytes written.
+    pub fn expand_compact_data<C: compact_frame::FormatSignatureCache + ?Sized>(
+        &self,
+        cache: &C,
+        output: &mut [u8],
+    ) -> Result<usize, compact_frame::CompactFrameError> {
+        match self {
+            Self::CompactDataStructureWithShortTplHeader { .. }
+            | Self::CompactDataStructureWithoutTplHeader { .. } => {
+                if self.is_payload_encrypted() {
+                    return Err(compact_frame::CompactFrameError::Encrypted);
+                }
+                compact_frame::CompactData::try_from(self.payload())?.expand_into(cache, output)
+            }
+            _ => Err(compact_frame::CompactFrameError::UnsupportedDataBlock),
+        }
+    }
+
+    /// Rebuilds the full data records of an encrypted compact frame from its
+    /// decrypted payload, see [`Self::expand_compact_data`]. The `0x2F 0x2F`
+    /// verification bytes in front of the payload and `0x2F` fill bytes
+    /// behind it are left out.
+    pub fn expand_decrypted_compact_data<C: compact_frame::FormatSignatureCache + ?Sized>(
+        &self,
+        decrypted: &[u8],
+        cache: &C,
+        output: &mut [u8],
+    ) -> Result<usize, compact_frame::CompactFrameError> {
+        if !matches!(
+            self,
+            Self::CompactDataStructureWithShortTplHeader { .. }
+                | Self::CompactDataStructureWithoutTplHeader { .. }
+        ) {
+            return Err(compact_frame::CompactFrameError::UnsupportedDataBlock);
+        }
+        let payload = decrypted.strip_prefix(&[0x2F, 0x2F]).unwrap_or(decrypted);
+        let expanded = compact_frame::CompactData::try_from(payload)?.expand_into(cache, output);
+        let unpadded = payload
+            .iter()
+            .rposition(|byte| *byte != 0x2F)
+            .map_or(&[][..], |last| &payload[..=last]);
+        match expanded {
+            Err(_) if unpadded.len() < payload.len() => {
+                compact_frame::CompactData::try_from(unpadded)?.expand_into(cache, output)
+            }
+            expanded => expanded,
+        }
+    }
+
+    fn is_payload_encrypted(&self) -> bool {
+        match self {
+            Self::VariableDataStructureWithLongTplHeader {
+                long_tpl_header, ..
+            } => long_tpl_header.is_encrypted(),
+            Self::VariableDataStructureWithShortTplHeader {
+                short_tpl_header, ..
+            }
+            | Self::CompactDataStructureWithShortTplHeader {
+                short_tpl_header, ..
+            } => short_tpl_header.is_encrypted(),
+            _ => false,
+        }
+    }
+
+    fn payload(&self) -> &'a [u8] {
+        match self {
+            Self::VariableDataStructureWithLongTplHeader {
+                variable_data_block,
+                ..
+            }
+            | Self::VariableDataStructureWithShortTplHeader {
+                variable_data_block,
+                ..
+            }
+            | Self::VariableDataStructureWithoutTplHeader {
+                variable_data_block,
+                ..
+            } => variable_data_block,
+            Self::CompactDataStructureWithShortTplHeader {
+                compact_data_block, ..
+            }
+            | Self::CompactDataStructureWithoutTplHeader {
+                compact_data_block, ..
+            } => compact_data_block,
+            _ => &[],
+        }
+    }
+
     #[cfg(feature = "decryption")]
     pub fn decrypt_variable_data<K: crate::decryption::KeyProvider>(
         &self,
@@ -664,6 +1315,9 @@ impl<'a> UserDataBlock<'a> {
             }
             Self::VariableDataStructureWithShortTplHeader {
                 short_tpl_header, ..
+            }
+            | Self::CompactDataStructureWithShortTplHeader {
+                short_tpl_header, ..
             } => {
                 if !short_tpl_header.is_encrypted() {
                     Err(NotEncrypted)
@@ -679,6 +1333,8 @@ impl<'a> UserDataBlock<'a> {
 
     /// Decrypt variable data when manufacturer info is not available in the TPL header.
     /// Use this for frames with Short TPL header where manufacturer info comes from the link layer.
+    /// Compact frames with Short TPL header are decrypted the same way; expand
+    /// the result with [`Self::expand_decrypted_compact_data`].
     #[cfg(feature = "decryption")]
     pub fn decrypt_variable_data_with_context<K: crate::decryption::KeyProvider>(
         &self,
@@ -696,6 +1352,11 @@ impl<'a> UserDataBlock<'a> {
                 short_tpl_header,
                 variable_data_block,
                 ..
+            }
+            | Self::CompactDataStructureWithShortTplHeader {
+                short_tpl_header,
+                compact_data_block: variable_data_block,
+                ..
             } => {
                 if !short_tpl_header.is_encrypted() {
                     return Err(NotEncrypted);
@@ -725,7 +1386,7 @@ impl<'a> UserDataBlock<'a> {
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct LongTplHeader {
     pub identification_number: IdentificationNumber,
@@ -740,21 +1401,261 @@ pub struct LongTplHeader {
     pub lsb_order: bool,
 }
 
-#[cfg(feature = "serde")]
-fn default_manufacturer_result() -> Result<ManufacturerCode, ApplicationLayerError> {
-    Err(ApplicationLayerError::InsufficientData)
-}
+#[cfg(feature = "serde")]
+fn default_manufacturer_result() -> Result<ManufacturerCode, ApplicationLayerError> {
+    Err(ApplicationLayerError::InsufficientData)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ShortTplHeader {
+    pub access_number: u8,
+    pub status: StatusField,
+    pub configuration_field: ConfigurationField,
+}
+
+/// Transport layer header sent without application layer, as used by
+/// bidirectional wireless M-Bus.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum TransportLayerHeader {
+    /// CI `0x80`: long header from the readout device to the meter.
+    LongReadoutToMeter(LongTplHeader),
+    /// CI `0x8A`: short header from the meter to the readout device.
+    ShortMeterToReadout(ShortTplHeader),
+    /// CI `0x8B`: long header from the meter to the readout device.
+    LongMeterToReadout(LongTplHeader),
+}
+
+impl TransportLayerHeader {
+    #[must_use]
+    pub const fn short_tpl_header(&self) -> &ShortTplHeader {
+        match self {
+            Self::LongReadoutToMeter(header) | Self::LongMeterToReadout(header) => {
+                &header.short_tpl_header
+            }
+            Self::ShortMeterToReadout(header) => header,
+        }
+    }
+
+    #[must_use]
+    pub const fn long_tpl_header(&self) -> Option<&LongTplHeader> {
+        match self {
+            Self::LongReadoutToMeter(header) | Self::LongMeterToReadout(header) => Some(header),
+            Self::ShortMeterToReadout(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn direction(&self) -> Direction {
+        match self {
+            Self::LongReadoutToMeter(_) => Direction::MasterToSlave,
+            Self::ShortMeterToReadout(_) | Self::LongMeterToReadout(_) => Direction::SlaveToMaster,
+        }
+    }
+
+    /// Returns the CI the header was sent with.
+    #[must_use]
+    pub const fn control_information(&self) -> u8 {
+        match self {
+            Self::LongReadoutToMeter(_) => 0x80,
+            Self::ShortMeterToReadout(_) => 0x8A,
+            Self::LongMeterToReadout(_) => 0x8B,
+        }
+    }
+
+    /// Combines the header with the application layer that follows it. The
+    /// header is kept in front of application layers with a header of their
+    /// own, since their header identifies the meter the records belong to.
+    /// Application layers with an unsupported CI are kept as raw
+    /// `application_data`.
+    fn with_application_data(
+        self,
+        data: &[u8],
+    ) -> Result<UserDataBlock<'_>, ApplicationLayerError> {
+        let block = match UserDataBlock::try_from(data) {
+            Ok(block) => block,
+            Err(
+                ApplicationLayerError::MissingControlInformation
+                | ApplicationLayerError::InvalidControlInformation { .. }
+                | ApplicationLayerError::Unimplemented { .. },
+            ) => {
+                return Ok(UserDataBlock::TransportLayer {
+                    extended_link_layer: None,
+                    transport_layer_header: self,
+                    application_data: data,
+                })
+            }
+            Err(error) => return Err(error),
+        };
+        let transport_layer_header = Some(self);
+        Ok(match (self, block) {
+            // CI 0x72
+            (
+                _,
+                UserDataBlock::VariableDataStructureWithLongTplHeader {
+                    long_tpl_header,
+                    variable_data_block,
+                    ..
+                },
+            ) => UserDataBlock::VariableDataStructureWithLongTplHeader {
+                extended_link_layer: None,
+                transport_layer_header,
+                long_tpl_header,
+                variable_data_block,
+            },
+            // CI 0x7A
+            (
+                _,
+                UserDataBlock::VariableDataStructureWithShortTplHeader {
+                    short_tpl_header,
+                    variable_data_block,
+                    ..
+                },
+            ) => UserDataBlock::VariableDataStructureWithShortTplHeader {
+                extended_link_layer: None,
+                transport_layer_header,
+                short_tpl_header,
+                variable_data_block,
+            },
+            // CI 0x7B
+            (
+                _,
+                UserDataBlock::CompactDataStructureWithShortTplHeader {
+                    short_tpl_header,
+                    compact_data_block,
+                    ..
+                },
+            ) => UserDataBlock::CompactDataStructureWithShortTplHeader {
+                extended_link_layer: None,
+                transport_layer_header,
+                short_tpl_header,
+                compact_data_block,
+            },
+            // CI 0x6B
+            (
+                _,
+                UserDataBlock::FormatFrameWithLongTplHeader {
+                    long_tpl_header,
+                    record_layout,
+                    ..
+                },
+            ) => UserDataBlock::FormatFrameWithLongTplHeader {
+                transport_layer_header,
+                long_tpl_header,
+                record_layout,
+            },
+            // CI 0x6A
+            (
+                _,
+                UserDataBlock::FormatFrameWithShortTplHeader {
+                    short_tpl_header,
+                    record_layout,
+                    ..
+                },
+            ) => UserDataBlock::FormatFrameWithShortTplHeader {
+                extended_link_layer: None,
+                transport_layer_header,
+                short_tpl_header,
+                record_layout,
+            },
+            (
+                Self::ShortMeterToReadout(short_tpl_header),
+                UserDataBlock::VariableDataStructureWithoutTplHeader {
+                    variable_data_block,
+                    ..
+                },
+            ) => UserDataBlock::VariableDataStructureWithShortTplHeader {
+                extended_link_layer: None,
+                transport_layer_header,
+                short_tpl_header,
+                variable_data_block,
+            },
+            (
+                Self::LongReadoutToMeter(long_tpl_header)
+                | Self::LongMeterToReadout(long_tpl_header),
+                UserDataBlock::VariableDataStructureWithoutTplHeader {
+                    variable_data_block,
+                    ..
+                },
+            ) => UserDataBlock::VariableDataStructureWithLongTplHeader {
+                extended_link_layer: None,
+                transport_layer_header,
+                long_tpl_header,
+                variable_data_block,
+            },
+            (
+                Self::ShortMeterToReadout(short_tpl_header),
+                UserDataBlock::CompactDataStructureWithoutTplHeader {
+                    compact_data_block, ..
+                },
+            ) => UserDataBlock::CompactDataStructureWithShortTplHeader {
+                extended_link_layer: None,
+                transport_layer_header,
+                short_tpl_header,
+                compact_data_block,
+            },
+            (
+                Self::ShortMeterToReadout(short_tpl_header),
+                UserDataBlock::FormatFrameWithoutTplHeader { record_layout, .. },
+            ) => UserDataBlock::FormatFrameWithShortTplHeader {
+                extended_link_layer: None,
+                transport_layer_header,
+                short_tpl_header,
+                record_layout,
+            },
+            (
+                Self::LongReadoutToMeter(long_tpl_header)
+                | Self::LongMeterToReadout(long_tpl_header),
+                UserDataBlock::FormatFrameWithoutTplHeader { record_layout, .. },
+            ) => UserDataBlock::FormatFrameWithLongTplHeader {
+                transport_layer_header,
+                long_tpl_header,
+                record_layout,
+            },
+            (transport_layer_header, _) => UserDataBlock::TransportLayer {
+                extended_link_layer: None,
+                transport_layer_header,
+                application_data: data,
+            },
+        })
+    }
+}
+
+impl LongTplHeader {
+    /// Parses the 12 header bytes following the CI field.
+    fn parse(data: &[u8], lsb_order: bool) -> Result<Self, ApplicationLayerError> {
+        let mut iter = data.iter();
+        let mut identification_number_bytes = [
+            *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+        ];
+        if lsb_order {
+            identification_number_bytes.reverse();
+        }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, PartialEq)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct ShortTplHeader {
-    pub access_number: u8,
-    pub status: StatusField,
-    pub configuration_field: ConfigurationField,
-}
+        Ok(LongTplHeader {
+            identification_number: IdentificationNumber::from_bcd_hex_digits(
+                identification_number_bytes,
+            )?,
+            manufacturer: ManufacturerCode::from_id(u16::from_le_bytes([
+                *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+                *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            ])),
+            version: *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            device_type: DeviceType::from(
+                *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            ),
+            short_tpl_header: ShortTplHeader::parse(iter.as_slice())?,
+            lsb_order,
+        })
+    }
 
-impl LongTplHeader {
     #[must_use]
     pub fn is_encrypted(&self) -> bool {
         use m_bus_core::SecurityMode;
@@ -766,6 +1667,21 @@ impl LongTplHeader {
 }
 
 impl ShortTplHeader {
+    /// Parses the 4 header bytes: access number, status and configuration field.
+    fn parse(data: &[u8]) -> Result<Self, ApplicationLayerError> {
+        let mut iter = data.iter();
+        Ok(ShortTplHeader {
+            access_number: *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            status: StatusField::from_bits_truncate(
+                *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            ),
+            configuration_field: ConfigurationField::from_bytes(
+                *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+                *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+            ),
+        })
+    }
+
     #[must_use]
     pub fn is_encrypted(&self) -> bool {
         use m_bus_core::SecurityMode;
@@ -776,6 +1692,26 @@ impl ShortTplHeader {
     }
 }
 
+/// Parses the body of a format frame: the length of the header bytes, the
+/// format signature and the DIF/VIF header bytes of each record.
+fn parse_record_layout(
+    data: &[u8],
+) -> Result<compact_frame::RecordLayout<'_>, ApplicationLayerError> {
+    let length = *data
+        .first()
+        .ok_or(ApplicationLayerError::InsufficientData)? as usize;
+    let format_signature = u16::from_le_bytes([
+        *data.get(1).ok_or(ApplicationLayerError::InsufficientData)?,
+        *data.get(2).ok_or(ApplicationLayerError::InsufficientData)?,
+    ]);
+    Ok(compact_frame::RecordLayout {
+        format_signature,
+        format: data
+            .get(3..3 + length)
+            .ok_or(ApplicationLayerError::InsufficientData)?,
+    })
+}
+
 impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
     type Error = ApplicationLayerError;
 
@@ -856,48 +1792,13 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
                 feature: "SendAlarmStatus control information",
             }),
             ControlInformation::ResponseWithVariableDataStructure { lsb_order } => {
-                let mut iter = data.iter().skip(1);
-                let mut identification_number_bytes = [
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                ];
-                if lsb_order {
-                    identification_number_bytes.reverse();
-                }
-
                 Ok(UserDataBlock::VariableDataStructureWithLongTplHeader {
-                    long_tpl_header: LongTplHeader {
-                        identification_number: IdentificationNumber::from_bcd_hex_digits(
-                            identification_number_bytes,
-                        )?,
-                        manufacturer: ManufacturerCode::from_id(u16::from_le_bytes([
-                            *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                            *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                        ])),
-                        version: *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                        device_type: DeviceType::from(
-                            *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                        ),
-                        short_tpl_header: ShortTplHeader {
-                            access_number: *iter
-                                .next()
-                                .ok_or(ApplicationLayerError::InsufficientData)?,
-                            status: {
-                                StatusField::from_bits_truncate(
-                                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                                )
-                            },
-                            configuration_field: {
-                                ConfigurationField::from_bytes(
-                                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                                )
-                            },
-                        },
+                    transport_layer_header: None,
+                    long_tpl_header: LongTplHeader::parse(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
                         lsb_order,
-                    },
+                    )?,
                     variable_data_block: data
                         .get(13..data.len())
                         .ok_or(ApplicationLayerError::InsufficientData)?,
@@ -922,18 +1823,21 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
                     *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
                     *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
                 ]);
-                let counter1 = Counter::from_bcd_hex_digits([
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                ])?;
-                let counter2 = Counter::from_bcd_hex_digits([
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                    *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
-                ])?;
+                let mut counter = || -> Result<Counter, ApplicationLayerError> {
+                    let bytes = [
+                        *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+                        *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+                        *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+                        *iter.next().ok_or(ApplicationLayerError::InsufficientData)?,
+                    ];
+                    if status.contains(StatusField::COUNTER_BINARY_SIGNED) {
+                        Ok(Counter::from_binary_bytes(bytes))
+                    } else {
+                        Counter::from_bcd_hex_digits(bytes)
+                    }
+                };
+                let counter1 = counter()?;
+                let counter2 = counter()?;
                 Ok(UserDataBlock::FixedDataStructure {
                     identification_number,
                     access_number,
@@ -953,39 +1857,97 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
                     feature: "DataSentWithLongTransportLayer control information",
                 })
             }
-            ControlInformation::CosemDataWithLongTransportLayer => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "CosemDataWithLongTransportLayer control information",
+            ControlInformation::CosemDataWithLongTransportLayer
+            | ControlInformation::CosemApplicationLayerLongTransport => {
+                Ok(UserDataBlock::CosemDataStructureWithLongTplHeader {
+                    long_tpl_header: LongTplHeader::parse(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                        false,
+                    )?,
+                    cosem_payload: cosem::CosemPayload {
+                        apdu: data
+                            .get(13..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    },
                 })
             }
-            ControlInformation::CosemDataWithShortTransportLayer => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "CosemDataWithShortTransportLayer control information",
+            ControlInformation::CosemDataWithShortTransportLayer
+            | ControlInformation::CosemApplicationLayerShortTransport => {
+                Ok(UserDataBlock::CosemDataStructureWithShortTplHeader {
+                    extended_link_layer: None,
+                    short_tpl_header: ShortTplHeader::parse(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    )?,
+                    cosem_payload: cosem::CosemPayload {
+                        apdu: data
+                            .get(5..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    },
                 })
             }
-            ControlInformation::ObisDataReservedLongTransportLayer => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ObisDataReservedLongTransportLayer control information",
+            ControlInformation::ObisDataReservedLongTransportLayer
+            | ControlInformation::ObisApplicationLayerReservedLongTransport => {
+                Ok(UserDataBlock::ObisDataStructureWithLongTplHeader {
+                    long_tpl_header: LongTplHeader::parse(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                        false,
+                    )?,
+                    obis_data_block: data
+                        .get(13..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
                 })
             }
-            ControlInformation::ObisDataReservedShortTransportLayer => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ObisDataReservedShortTransportLayer control information",
+            ControlInformation::ObisDataReservedShortTransportLayer
+            | ControlInformation::ObisApplicationLayerReservedShortTransport => {
+                Ok(UserDataBlock::ObisDataStructureWithShortTplHeader {
+                    extended_link_layer: None,
+                    short_tpl_header: ShortTplHeader::parse(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    )?,
+                    obis_data_block: data
+                        .get(5..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
                 })
             }
             ControlInformation::ApplicationLayerFormatFrameNoTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ApplicationLayerFormatFrameNoTransport control information",
+                Ok(UserDataBlock::FormatFrameWithoutTplHeader {
+                    extended_link_layer: None,
+                    record_layout: parse_record_layout(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    )?,
                 })
             }
             ControlInformation::ApplicationLayerFormatFrameShortTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ApplicationLayerFormatFrameShortTransport control information",
+                Ok(UserDataBlock::FormatFrameWithShortTplHeader {
+                    extended_link_layer: None,
+                    transport_layer_header: None,
+                    short_tpl_header: ShortTplHeader::parse(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    )?,
+                    record_layout: parse_record_layout(
+                        data.get(5..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    )?,
                 })
             }
             ControlInformation::ApplicationLayerFormatFrameLongTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ApplicationLayerFormatFrameLongTransport control information",
+                Ok(UserDataBlock::FormatFrameWithLongTplHeader {
+                    transport_layer_header: None,
+                    long_tpl_header: LongTplHeader::parse(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                        false,
+                    )?,
+                    record_layout: parse_record_layout(
+                        data.get(13..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    )?,
                 })
             }
             ControlInformation::ClockSyncAbsolute => Err(ApplicationLayerError::Unimplemented {
@@ -1019,20 +1981,18 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
                 })
             }
             ControlInformation::ApplicationLayerCompactFrameNoTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ApplicationLayerCompactFrameNoTransport control information",
+                Ok(UserDataBlock::CompactDataStructureWithoutTplHeader {
+                    extended_link_layer: None,
+                    compact_data_block: data
+                        .get(1..data.len())
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
                 })
             }
             ControlInformation::ApplicationLayerShortTransport => {
-                // CI=0xA0 (encryption mode 5) has an additional encryption configuration byte after CI
-                // Other encrypted CI codes (0xA2, 0xA4, etc.) do not have this byte
-                let has_encryption_config_byte = data[0] == 0xA0;
-                let skip_count = if has_encryption_config_byte { 2 } else { 1 };
-                let data_block_offset = if has_encryption_config_byte { 6 } else { 5 };
-
-                let mut iter = data.iter().skip(skip_count);
+                let mut iter = data.iter().skip(1);
 
                 Ok(UserDataBlock::VariableDataStructureWithShortTplHeader {
+                    transport_layer_header: None,
                     short_tpl_header: ShortTplHeader {
                         access_number: *iter
                             .next()
@@ -1050,50 +2010,66 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
                         },
                     },
                     variable_data_block: data
-                        .get(data_block_offset..data.len())
+                        .get(5..data.len())
                         .ok_or(ApplicationLayerError::InsufficientData)?,
                     extended_link_layer: None,
                 })
             }
             ControlInformation::ApplicationLayerCompactFrameShortTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ApplicationLayerCompactFrameShortTransport control information",
-                })
-            }
-            ControlInformation::CosemApplicationLayerLongTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "CosemApplicationLayerLongTransport control information",
-                })
-            }
-            ControlInformation::CosemApplicationLayerShortTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "CosemApplicationLayerShortTransport control information",
-                })
-            }
-            ControlInformation::ObisApplicationLayerReservedLongTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ObisApplicationLayerReservedLongTransport control information",
-                })
-            }
-            ControlInformation::ObisApplicationLayerReservedShortTransport => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "ObisApplicationLayerReservedShortTransport control information",
+                Ok(UserDataBlock::CompactDataStructureWithShortTplHeader {
+                    transport_layer_header: None,
+                    short_tpl_header: ShortTplHeader::parse(
+                        data.get(1..)
+                            .ok_or(ApplicationLayerError::InsufficientData)?,
+                    )?,
+                    compact_data_block: data
+                        .get(5..data.len())
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                    extended_link_layer: None,
                 })
             }
-            ControlInformation::TransportLayerLongReadoutToMeter => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "TransportLayerLongReadoutToMeter control information",
+            ControlInformation::TransportLayerLongReadoutToMeter => Ok(
+                TransportLayerHeader::LongReadoutToMeter(LongTplHeader::parse(
+                    data.get(1..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                    false,
+                )?)
+                .with_application_data(
+                    data.get(13..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                )?,
+            ),
+            ControlInformation::NetworkLayerData => {
+                let (network_layer_header, size) = network_layer::NetworkLayerHeader::parse(
+                    data.get(1..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                )?;
+                Ok(UserDataBlock::NetworkLayer {
+                    extended_link_layer: None,
+                    network_layer_header,
+                    network_layer_data: data
+                        .get(1 + size..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
                 })
             }
-            ControlInformation::NetworkLayerData => Err(ApplicationLayerError::Unimplemented {
-                feature: "NetworkLayerData control information",
-            }),
             ControlInformation::FutureUse => Err(ApplicationLayerError::Unimplemented {
                 feature: "FutureUse control information",
             }),
+            ControlInformation::ManufacturerSpecific(control_information) => {
+                Ok(UserDataBlock::ManufacturerSpecific {
+                    extended_link_layer: None,
+                    control_information,
+                    manufacturer_data: data
+                        .get(1..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                })
+            }
             ControlInformation::NetworkManagementApplication => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "NetworkManagementApplication control information",
+                Ok(UserDataBlock::NetworkManagement {
+                    extended_link_layer: None,
+                    network_management_data: data
+                        .get(1..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
                 })
             }
             ControlInformation::TransportLayerCompactFrame => {
@@ -1111,16 +2087,27 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
                     feature: "NetworkManagementDataReserved control information",
                 })
             }
-            ControlInformation::TransportLayerShortMeterToReadout => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "TransportLayerShortMeterToReadout control information",
-                })
-            }
-            ControlInformation::TransportLayerLongMeterToReadout => {
-                Err(ApplicationLayerError::Unimplemented {
-                    feature: "TransportLayerLongMeterToReadout control information",
-                })
-            }
+            ControlInformation::TransportLayerShortMeterToReadout => Ok(
+                TransportLayerHeader::ShortMeterToReadout(ShortTplHeader::parse(
+                    data.get(1..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                )?)
+                .with_application_data(
+                    data.get(5..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                )?,
+            ),
+            ControlInformation::TransportLayerLongMeterToReadout => Ok(
+                TransportLayerHeader::LongMeterToReadout(LongTplHeader::parse(
+                    data.get(1..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                    false,
+                )?)
+                .with_application_data(
+                    data.get(13..)
+                        .ok_or(ApplicationLayerError::InsufficientData)?,
+                )?,
+            ),
             ControlInformation::ExtendedLinkLayerI => {
                 let mut iter = data.iter();
                 iter.next();
@@ -1133,12 +2120,25 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
                     encryption: None,
                 });
                 match UserDataBlock::try_from(iter.as_slice()) {
+                    Ok(UserDataBlock::VariableDataStructureWithLongTplHeader {
+                        transport_layer_header,
+                        long_tpl_header,
+                        variable_data_block,
+                        ..
+                    }) => Ok(UserDataBlock::VariableDataStructureWithLongTplHeader {
+                        extended_link_layer,
+                        transport_layer_header,
+                        long_tpl_header,
+                        variable_data_block,
+                    }),
                     Ok(UserDataBlock::VariableDataStructureWithShortTplHeader {
+                        transport_layer_header,
                         short_tpl_header,
                         variable_data_block,
                         ..
                     }) => Ok(UserDataBlock::VariableDataStructureWithShortTplHeader {
                         extended_link_layer,
+                        transport_layer_header,
                         short_tpl_header,
                         variable_data_block,
                     }),
@@ -1149,6 +2149,93 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
                         extended_link_layer,
                         variable_data_block,
                     }),
+                    Ok(UserDataBlock::CompactDataStructureWithShortTplHeader {
+                        transport_layer_header,
+                        short_tpl_header,
+                        compact_data_block,
+                        ..
+                    }) => Ok(UserDataBlock::CompactDataStructureWithShortTplHeader {
+                        extended_link_layer,
+                        transport_layer_header,
+                        short_tpl_header,
+                        compact_data_block,
+                    }),
+                    Ok(UserDataBlock::CompactDataStructureWithoutTplHeader {
+                        compact_data_block,
+                        ..
+                    }) => Ok(UserDataBlock::CompactDataStructureWithoutTplHeader {
+                        extended_link_layer,
+                        compact_data_block,
+                    }),
+                    Ok(UserDataBlock::FormatFrameWithShortTplHeader {
+                        transport_layer_header,
+                        short_tpl_header,
+                        record_layout,
+                        ..
+                    }) => Ok(UserDataBlock::FormatFrameWithShortTplHeader {
+                        extended_link_layer,
+                        transport_layer_header,
+                        short_tpl_header,
+                        record_layout,
+                    }),
+                    Ok(UserDataBlock::FormatFrameWithoutTplHeader { record_layout, .. }) => {
+                        Ok(UserDataBlock::FormatFrameWithoutTplHeader {
+                            extended_link_layer,
+                            record_layout,
+                        })
+                    }
+                    Ok(UserDataBlock::CosemDataStructureWithShortTplHeader {
+                        short_tpl_header,
+                        cosem_payload,
+                        ..
+                    }) => Ok(UserDataBlock::CosemDataStructureWithShortTplHeader {
+                        extended_link_layer,
+                        short_tpl_header,
+                        cosem_payload,
+                    }),
+                    Ok(UserDataBlock::ObisDataStructureWithShortTplHeader {
+                        short_tpl_header,
+                        obis_data_block,
+                        ..
+                    }) => Ok(UserDataBlock::ObisDataStructureWithShortTplHeader {
+                        extended_link_layer,
+                        short_tpl_header,
+                        obis_data_block,
+                    }),
+                    Ok(UserDataBlock::NetworkLayer {
+                        network_layer_header,
+                        network_layer_data,
+                        ..
+                    }) => Ok(UserDataBlock::NetworkLayer {
+                        extended_link_layer,
+                        network_layer_header,
+                        network_layer_data,
+                    }),
+                    Ok(UserDataBlock::NetworkManagement {
+                        network_management_data,
+                        ..
+                    }) => Ok(UserDataBlock::NetworkManagement {
+                        extended_link_layer,
+                        network_management_data,
+                    }),
+                    Ok(UserDataBlock::ManufacturerSpecific {
+                        control_information,
+                        manufacturer_data,
+                        ..
+                    }) => Ok(UserDataBlock::ManufacturerSpecific {
+                        extended_link_layer,
+                        control_information,
+                        manufacturer_data,
+                    }),
+                    Ok(UserDataBlock::TransportLayer {
+                        transport_layer_header,
+                        application_data,
+                        ..
+                    }) => Ok(UserDataBlock::TransportLayer {
+                        extended_link_layer,
+                        transport_layer_header,
+                        application_data,
+                    }),
                     _ => Err(ApplicationLayerError::MissingControlInformation),
                 }
             }
@@ -1171,6 +2258,7 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
 
                 Ok(UserDataBlock::VariableDataStructureWithShortTplHeader {
                     extended_link_layer: Some(ell),
+                    transport_layer_header: None,
                     short_tpl_header,
                     variable_data_block: data
                         .get(app_data_offset..)
@@ -1196,6 +2284,7 @@ impl<'a> TryFrom<&'a [u8]> for UserDataBlock<'a> {
 
                 Ok(UserDataBlock::VariableDataStructureWithShortTplHeader {
                     extended_link_layer: Some(ell),
+                    transport_layer_header: None,
                     short_tpl_header,
                     variable_data_block: data
                         .get(app_data_offset..)
@@ -1462,13 +2551,41 @@ mod tests {
         let mut records = parse_data_records(&data);
         let record = records.next().unwrap().unwrap();
 
-        assert_eq!(record.value(), Some(&DataType::Number(12_565.0)));
+        assert_eq!(record.value(), Some(&DataType::Integer(12_565)));
         assert_eq!(record.raw_bytes(), &data);
         assert!(record.data_information().is_some());
         assert!(record.value_information().is_some());
         assert!(records.next().is_none());
     }
 
+    #[test]
+    fn recovering_records_skip_invalid_records() {
+        use crate::data_information::DataType;
+
+        let data = [
+            0x0A, 0x13, 0xAB, 0x0C, // 4-digit BCD with non-decimal digits
+            0x03, 0x13, 0x15, 0x31, 0x00,
+        ];
+        let records: Vec<_> = parse_data_records(&data).collect();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].is_err());
+
+        let mut records = parse_data_records(&data).recovering();
+        let skipped = records.next().unwrap().unwrap_err();
+        assert_eq!((skipped.start, skipped.end), (0, 4));
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.value(), Some(&DataType::Integer(12_565)));
+        assert!(records.next().is_none());
+
+        // the length of a variable length record is read from its LVAR byte
+        let data = [0x0D, 0x13, 0xC2, 0xAB, 0x0C, 0x01, 0x13, 0x07];
+        let skipped: Vec<_> = parse_data_records(&data)
+            .recovering()
+            .map(|record| record.err().map(|skipped| (skipped.start, skipped.end)))
+            .collect();
+        assert_eq!(skipped, [Some((0, 5)), None]);
+    }
+
     #[test]
     fn parse_application_layer_api_exposes_records() {
         let data = [0x78, 0x03, 0x13, 0x15, 0x31, 0x00];
@@ -1485,4 +2602,265 @@ mod tests {
         assert!(records.next().unwrap().is_err());
         assert!(records.next().is_none());
     }
+
+    #[test]
+    fn compact_frame_is_expanded_with_format_of_full_frame() {
+        use compact_frame::{format_signature, FormatSignatureCache, StaticFormatCache};
+
+        let records = [0x0C, 0x13, 0x78, 0x56, 0x34, 0x12, 0x02, 0x5A, 0x2C, 0x01];
+        let mut full = vec![0x7A, 0x01, 0x00, 0x00, 0x00];
+        full.extend_from_slice(&records);
+        let mut cache = StaticFormatCache::<4>::new();
+        let signature = UserDataBlock::try_from(full.as_slice())
+            .unwrap()
+            .learn_format(&mut cache)
+            .unwrap();
+        assert_eq!(signature, format_signature(&[0x0C, 0x13, 0x02, 0x5A]));
+        assert!(cache.lookup(signature).is_some());
+
+        let mut compact = vec![0x7B, 0x02, 0x00, 0x00, 0x00];
+        compact.extend_from_slice(&signature.to_le_bytes());
+        compact.extend_from_slice(&format_signature(&records).to_le_bytes());
+        compact.extend_from_slice(&[0x78, 0x56, 0x34, 0x12, 0x2C, 0x01]);
+        let block = UserDataBlock::try_from(compact.as_slice()).unwrap();
+        assert!(matches!(
+            block,
+            UserDataBlock::CompactDataStructureWithShortTplHeader {
+                short_tpl_header: ShortTplHeader {
+                    access_number: 0x02,
+                    ..
+                },
+                ..
+            }
+        ));
+        assert!(block.data_records().is_none());
+
+        let mut output = [0u8; 32];
+        let len = block.expand_compact_data(&cache, &mut output).unwrap();
+        assert_eq!(&output[..len], &records);
+    }
+
+    #[test]
+    fn format_frame_layout_expands_later_compact_frame() {
+        use compact_frame::{format_signature, CompactFrameError, StaticFormatCache};
+
+        let format = [0x0C, 0x13, 0x02, 0x5A];
+        let signature = format_signature(&format);
+        let mut frame = vec![0x69, 0x04];
+        frame.extend_from_slice(&signature.to_le_bytes());
+        frame.extend_from_slice(&format);
+
+        let block = UserDataBlock::try_from(frame.as_slice()).unwrap();
+        let UserDataBlock::FormatFrameWithoutTplHeader { record_layout, .. } = &block else {
+            panic!("expected format frame, got {block:?}");
+        };
+        assert_eq!(record_layout.format, &format);
+        assert!(record_layout.is_signature_valid());
+        assert_eq!(record_layout.headers().count(), 2);
+
+        let mut cache = StaticFormatCache::<4>::new();
+        assert_eq!(block.learn_format(&mut cache), Ok(signature));
+
+        let records = [0x0C, 0x13, 0x78, 0x56, 0x34, 0x12, 0x02, 0x5A, 0x2C, 0x01];
+        let mut compact = vec![0x79];
+        compact.extend_from_slice(&signature.to_le_bytes());
+        compact.extend_from_slice(&format_signature(&records).to_le_bytes());
+        compact.extend_from_slice(&[0x78, 0x56, 0x34, 0x12, 0x2C, 0x01]);
+        let mut output = [0u8; 32];
+        let len = UserDataBlock::try_from(compact.as_slice())
+            .unwrap()
+            .expand_compact_data(&cache, &mut output)
+            .unwrap();
+        assert_eq!(&output[..len], &records);
+
+        frame[2] ^= 0xFF;
+        let block = UserDataBlock::try_from(frame.as_slice()).unwrap();
+        assert!(matches!(
+            block.learn_format(&mut cache),
+            Err(CompactFrameError::FormatSignatureMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn format_frame_with_long_tpl_header() {
+        let frame = [
+            0x6B, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, 0x02,
+            0xAA, 0xBB, 0x04, 0x13,
+        ];
+        match UserDataBlock::try_from(frame.as_slice()) {
+            Ok(UserDataBlock::FormatFrameWithLongTplHeader {
+                long_tpl_header,
+                record_layout,
+                transport_layer_header: None,
+            }) => {
+                assert_eq!(long_tpl_header.identification_number.number, 12_345_678);
+                assert_eq!(long_tpl_header.short_tpl_header.access_number, 0x55);
+                assert_eq!(record_layout.format_signature, 0xBBAA);
+                assert_eq!(record_layout.format, &[0x04, 0x13]);
+            }
+            other => panic!("expected format frame, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transport_layer_header_without_application_layer() {
+        let frame = [
+            0x80, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x2A, 0x00, 0x00, 0x00,
+        ];
+        match UserDataBlock::try_from(frame.as_slice()) {
+            Ok(UserDataBlock::TransportLayer {
+                transport_layer_header,
+                application_data,
+                ..
+            }) => {
+                assert_eq!(transport_layer_header.direction(), Direction::MasterToSlave);
+                let long_tpl_header = transport_layer_header.long_tpl_header().unwrap();
+                assert_eq!(long_tpl_header.identification_number.number, 12_345_678);
+                assert_eq!(
+                    transport_layer_header.short_tpl_header().access_number,
+                    0x2A
+                );
+                assert!(application_data.is_empty());
+            }
+            other => panic!("expected transport layer, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transport_layer_header_preceding_application_layer() {
+        let frame = [
+            0x8A, 0x2A, 0x00, 0x00, 0x00, 0x78, 0x03, 0x13, 0x15, 0x31, 0x00,
+        ];
+        let user_data = UserDataBlock::try_from(frame.as_slice()).unwrap();
+        match &user_data {
+            UserDataBlock::VariableDataStructureWithShortTplHeader {
+                short_tpl_header, ..
+            } => assert_eq!(short_tpl_header.access_number, 0x2A),
+            other => panic!("expected short TPL header, got {other:?}"),
+        }
+        assert_eq!(user_data.transport_control_information(), Some(0x8A));
+        assert_eq!(user_data.data_records().unwrap().count(), 1);
+
+        let frame = [
+            0x8C, 0x20, 0x2A, 0x8B, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x2B, 0x00,
+            0x00, 0x00, 0x78, 0x03, 0x13, 0x15, 0x31, 0x00,
+        ];
+        match UserDataBlock::try_from(frame.as_slice()) {
+            Ok(UserDataBlock::VariableDataStructureWithLongTplHeader {
+                extended_link_layer: Some(extended_link_layer),
+                long_tpl_header,
+                ..
+            }) => {
+                assert_eq!(extended_link_layer.access_number, 0x2A);
+                assert_eq!(long_tpl_header.short_tpl_header.access_number, 0x2B);
+            }
+            other => panic!("expected long TPL header, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transport_layer_header_preceding_long_tpl_application_layer() {
+        let frame = [
+            0x8A, 0x2A, 0x00, 0x00, 0x00, // transport layer header
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x2B, 0x00, 0x00, 0x00, //
+            0x03, 0x13, 0x15, 0x31, 0x00,
+        ];
+        let user_data = UserDataBlock::try_from(frame.as_slice()).unwrap();
+        match &user_data {
+            UserDataBlock::VariableDataStructureWithLongTplHeader {
+                long_tpl_header, ..
+            } => {
+                assert_eq!(long_tpl_header.identification_number.number, 12_345_678);
+                assert_eq!(long_tpl_header.short_tpl_header.access_number, 0x2B);
+            }
+            other => panic!("expected long TPL header, got {other:?}"),
+        }
+        assert_eq!(user_data.transport_control_information(), Some(0x8A));
+        assert_eq!(
+            user_data
+                .transport_layer_header()
+                .unwrap()
+                .short_tpl_header()
+                .access_number,
+            0x2A
+        );
+        assert_eq!(user_data.data_records().unwrap().count(), 1);
+
+        // The application layer is cut short after its CI.
+        let frame = [0x8A, 0x2A, 0x00, 0x00, 0x00, 0x7A, 0x2B];
+        assert_eq!(
+            UserDataBlock::try_from(frame.as_slice()),
+            Err(ApplicationLayerError::InsufficientData)
+        );
+    }
+
+    #[test]
+    fn transport_layer_header_preceding_short_tpl_application_layer() {
+        let frame = [
+            0x8A, 0x2A, 0x00, 0x00, 0x00, // transport layer header
+            0x7A, 0x2B, 0x00, 0x00, 0x00, 0x03, 0x13, 0x15, 0x31, 0x00,
+        ];
+        let user_data = UserDataBlock::try_from(frame.as_slice()).unwrap();
+        match &user_data {
+            UserDataBlock::VariableDataStructureWithShortTplHeader {
+                transport_layer_header: Some(transport_layer_header),
+                short_tpl_header,
+                ..
+            } => {
+                assert_eq!(
+                    transport_layer_header.short_tpl_header().access_number,
+                    0x2A
+                );
+                assert_eq!(short_tpl_header.access_number, 0x2B);
+            }
+            other => panic!("expected short TPL header, got {other:?}"),
+        }
+        assert_eq!(user_data.data_records().unwrap().count(), 1);
+    }
+
+    #[test]
+    fn transport_layer_header_keeps_unsupported_application_layer() {
+        let frame = [
+            0x8A, 0x2A, 0x00, 0x00, 0x00, // transport layer header
+            0xFF, 0x01, 0x02, 0x03,
+        ];
+        match UserDataBlock::try_from(frame.as_slice()) {
+            Ok(UserDataBlock::TransportLayer {
+                transport_layer_header,
+                application_data,
+                ..
+            }) => {
+                assert_eq!(transport_layer_header.control_information(), 0x8A);
+                assert_eq!(application_data, &[0xFF, 0x01, 0x02, 0x03]);
+            }
+            other => panic!("expected transport layer, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn network_layer_is_split_from_inner_block() {
+        let frame = [
+            0x8C, 0x20, 0x2A, // ELL I
+            0x81, 0x81, 0x2D, 0x2C, 0x01, 0x00, 0x00, 0x10, 0x01, 0x32, // one hop
+            0x7A, 0x2B, 0x00, 0x00, 0x00, 0x03, 0x13, 0x15, 0x31, 0x00,
+        ];
+        let user_data = UserDataBlock::try_from(frame.as_slice()).unwrap();
+        assert!(user_data.data_records().is_none());
+        let (network_layer_header, inner) = user_data.split_network_layer().unwrap();
+        let network_layer_header = network_layer_header.unwrap();
+        assert_eq!(network_layer_header.hop_count(), 1);
+        assert!(network_layer_header.is_repeated());
+        match &inner {
+            UserDataBlock::VariableDataStructureWithShortTplHeader {
+                extended_link_layer: Some(extended_link_layer),
+                short_tpl_header,
+                ..
+            } => {
+                assert_eq!(extended_link_layer.access_number, 0x2A);
+                assert_eq!(short_tpl_header.access_number, 0x2B);
+            }
+            other => panic!("expected short TPL header, got {other:?}"),
+        }
+        assert_eq!(inner.data_records().unwrap().count(), 1);
+    }
 }
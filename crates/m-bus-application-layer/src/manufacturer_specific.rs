@@ -0,0 +1,616 @@
+//! Decoders for manufacturer specific records.
+//!
+//! Records with the manufacturer specific VIF `0x7F`/`0xFF` and the data after
+//! the DIF `0x0F`/`0x1F` carry vendor defined content that EN 13757-3 does not
+//! describe. A [`ManufacturerDecoder`] selected by manufacturer, version and
+//! device type turns such a record into named, typed [`ManufacturerValue`]s.
+//!
+//! Frames with a manufacturer specific CI (`0xA0` to `0xAF`) carry a
+//! proprietary application layer instead of data records, which a decoder
+//! reads from a [`ManufacturerPayload`].
+//!
+//! Decoders are looked up in a [`DecoderRegistry`]. On `no_std` targets a
+//! static table of decoders is a registry, with `std` decoders can also be
+//! registered at runtime in a [`ManufacturerRegistry`].
+//!
+//! ```
+//! use m_bus_application_layer::data_information::DataType;
+//! use m_bus_application_layer::manufacturer_specific::{
+//!     DecoderRegistry, ManufacturerDecoder, ManufacturerValue, MeterIdentity,
+//! };
+//! use m_bus_application_layer::DataRecord;
+//! use m_bus_core::{DeviceType, ManufacturerCode};
+//!
+//! struct Counter;
+//!
+//! impl ManufacturerDecoder for Counter {
+//!     fn supports(&self, meter: &MeterIdentity) -> bool {
+//!         meter.manufacturer.code == ['A', 'B', 'C']
+//!     }
+//!
+//!     fn decode<'a>(
+//!         &self,
+//!         _meter: &MeterIdentity,
+//!         record: &DataRecord<'a>,
+//!         sink: &mut dyn FnMut(ManufacturerValue<'a>),
+//!     ) -> bool {
+//!         let Some(&[low, high]) = record.manufacturer_data() else {
+//!             return false;
+//!         };
+//!         let count = u16::from_le_bytes([low, high]);
+//!         sink(ManufacturerValue::new("Counter", DataType::Integer(count.into())));
+//!         true
+//!     }
+//! }
+//!
+//! static DECODERS: &[&dyn ManufacturerDecoder] = &[&Counter];
+//!
+//! let meter = MeterIdentity::new(ManufacturerCode { code: ['A', 'B', 'C'] }, 1, DeviceType::WaterMeter);
+//! let data = [0x0F, 0x2A, 0x00];
+//! let record = DataRecord::try_from(data.as_slice()).unwrap();
+//! let values = DECODERS.decode(&meter, &record).unwrap();
+//! assert_eq!(values[0].name, "Counter");
+//! assert_eq!(values[0].value, DataType::Integer(42));
+//! ```
+
+pub mod diehl;
+pub mod kamstrup;
+pub mod techem;
+
+use arrayvec::ArrayVec;
+use m_bus_core::{DeviceType, ManufacturerCode};
+
+use crate::data_information::DataType;
+use crate::data_record::DataRecord;
+use crate::scaled_value::{ScaledNumber, ScaledValue};
+use crate::status::{ConditionBits, ConditionSource};
+use crate::value_information::Unit;
+use crate::variable_user_data::DataRecordError;
+use crate::{DataRecords, LongTplHeader};
+
+/// The decoders this crate ships, as a static table for `no_std` targets.
+pub static BUILTIN_DECODERS: &[&dyn ManufacturerDecoder] =
+    &[&kamstrup::Kamstrup, &techem::Techem, &diehl::Izar::DEFAULT];
+
+/// Maximum number of values a decoder can extract from a single record.
+pub const MAX_MANUFACTURER_VALUES: usize = 16;
+
+/// Values a decoder extracted from a single record.
+pub type ManufacturerValues<'a> = ArrayVec<ManufacturerValue<'a>, MAX_MANUFACTURER_VALUES>;
+
+/// Manufacturer, version and device type of the meter that sent a record.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeterIdentity {
+    pub manufacturer: ManufacturerCode,
+    pub version: u8,
+    pub device_type: DeviceType,
+}
+
+impl MeterIdentity {
+    #[must_use]
+    pub const fn new(manufacturer: ManufacturerCode, version: u8, device_type: DeviceType) -> Self {
+        Self {
+            manufacturer,
+            version,
+            device_type,
+        }
+    }
+}
+
+impl TryFrom<&LongTplHeader> for MeterIdentity {
+    type Error = m_bus_core::ApplicationLayerError;
+
+    fn try_from(header: &LongTplHeader) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            header.manufacturer?,
+            header.version,
+            header.device_type,
+        ))
+    }
+}
+
+/// Named bits of a bitfield value, e.g. an info code.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Flags {
+    pub bits: u32,
+    /// Mask and name of each known flag.
+    pub names: &'static [(u32, &'static str)],
+    /// Conditions the bits signal, see [`status`](crate::status).
+    pub conditions: &'static [ConditionBits],
+}
+
+impl Flags {
+    /// Returns the names of the set flags.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.names
+            .iter()
+            .filter(|(mask, _)| self.bits & mask != 0)
+            .map(|(_, name)| *name)
+    }
+}
+
+/// A named value decoded from a manufacturer specific record.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManufacturerValue<'a> {
+    /// Name of the value in sentence case, e.g. `Info code`.
+    pub name: &'static str,
+    pub value: DataType<'a>,
+    pub decimal_scale_exponent: isize,
+    pub units: ArrayVec<Unit, 10>,
+    /// Storage number as in a data record, e.g. 1 for the value at the end
+    /// of the previous billing period.
+    pub storage_number: u64,
+    /// Meaning of the bits of a bitfield value.
+    pub flags: Option<Flags>,
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ManufacturerValue<'_> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "ManufacturerValue{{ name: {}, value: {}, decimal_scale_exponent: {}, units: {:?}, storage_number: {}, flags: {} }}",
+            self.name,
+            self.value,
+            self.decimal_scale_exponent,
+            self.units.as_slice(),
+            self.storage_number,
+            self.flags
+        );
+    }
+}
+
+impl<'a> ManufacturerValue<'a> {
+    #[must_use]
+    pub fn new(name: &'static str, value: DataType<'a>) -> Self {
+        Self {
+            name,
+            value,
+            decimal_scale_exponent: 0,
+            units: ArrayVec::new(),
+            storage_number: 0,
+            flags: None,
+        }
+    }
+
+    /// Sets the unit of the value. Units beyond the tenth are ignored.
+    #[must_use]
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        let _ = self.units.try_push(unit);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_scale(mut self, decimal_scale_exponent: isize) -> Self {
+        self.decimal_scale_exponent = decimal_scale_exponent;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_storage_number(mut self, storage_number: u64) -> Self {
+        self.storage_number = storage_number;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_flags(mut self, names: &'static [(u32, &'static str)]) -> Self {
+        let bits = match self.value {
+            DataType::Integer(value) => value as u32,
+            _ => 0,
+        };
+        self.flags = Some(Flags {
+            bits,
+            names,
+            conditions: &[],
+        });
+        self
+    }
+
+    /// Assigns conditions to the bits of flags set by [`Self::with_flags`].
+    #[must_use]
+    pub const fn with_conditions(mut self, conditions: &'static [ConditionBits]) -> Self {
+        if let Some(flags) = &mut self.flags {
+            flags.conditions = conditions;
+        }
+        self
+    }
+
+    /// Returns the physical quantity `value * 10^scale` of a numeric value
+    /// with its units.
+    #[must_use]
+    pub fn scaled_value(&self) -> Option<ScaledValue> {
+        let value = match &self.value {
+            DataType::Number(value) | DataType::LossyNumber(value) => ScaledNumber::Real(*value),
+            value => ScaledNumber::Exact(value.decimal()?),
+        };
+        Some(ScaledValue {
+            value: value.scaled(i16::try_from(self.decimal_scale_exponent).ok()?),
+            units: self.units.clone(),
+            labels: ArrayVec::new(),
+        })
+    }
+}
+
+/// The proprietary application layer of a frame with a manufacturer
+/// specific CI, see [`crate::UserDataBlock::ManufacturerSpecific`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ManufacturerPayload<'a> {
+    pub control_information: u8,
+    /// Manufacturer, identification number, version and device type bytes
+    /// of the wireless link layer header, which some meters use to scramble
+    /// the payload.
+    pub link_address: Option<[u8; 8]>,
+    /// The bytes after the CI field.
+    pub data: &'a [u8],
+}
+
+impl<'a> ManufacturerPayload<'a> {
+    #[must_use]
+    pub const fn new(control_information: u8, data: &'a [u8]) -> Self {
+        Self {
+            control_information,
+            link_address: None,
+            data,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_link_address(mut self, link_address: [u8; 8]) -> Self {
+        self.link_address = Some(link_address);
+        self
+    }
+}
+
+/// Decodes the manufacturer specific records and payloads of some meters.
+pub trait ManufacturerDecoder: Send + Sync {
+    /// Returns whether the decoder handles records of this meter.
+    fn supports(&self, meter: &MeterIdentity) -> bool;
+
+    /// Decodes a manufacturer specific record and passes its values to
+    /// `sink`. Returns `false` if the record is not handled, in which case it
+    /// is kept as it is.
+    fn decode<'a>(
+        &self,
+        _meter: &MeterIdentity,
+        _record: &DataRecord<'a>,
+        _sink: &mut dyn FnMut(ManufacturerValue<'a>),
+    ) -> bool {
+        false
+    }
+
+    /// Decodes a proprietary application layer and passes its values to
+    /// `sink`. Returns `false` if the layout is not recognised.
+    fn decode_payload<'a>(
+        &self,
+        _meter: &MeterIdentity,
+        _payload: &ManufacturerPayload<'a>,
+        _sink: &mut dyn FnMut(ManufacturerValue<'a>),
+    ) -> bool {
+        false
+    }
+
+    /// Returns the conditions of the status bits or error flags of this
+    /// meter. Status entries replace the OMS entries for the bits they cover.
+    fn conditions(
+        &self,
+        _meter: &MeterIdentity,
+        _source: ConditionSource,
+    ) -> &'static [ConditionBits] {
+        &[]
+    }
+}
+
+impl<D: ManufacturerDecoder + ?Sized> ManufacturerDecoder for &D {
+    fn supports(&self, meter: &MeterIdentity) -> bool {
+        (**self).supports(meter)
+    }
+
+    fn decode<'a>(
+        &self,
+        meter: &MeterIdentity,
+        record: &DataRecord<'a>,
+        sink: &mut dyn FnMut(ManufacturerValue<'a>),
+    ) -> bool {
+        (**self).decode(meter, record, sink)
+    }
+
+    fn decode_payload<'a>(
+        &self,
+        meter: &MeterIdentity,
+        payload: &ManufacturerPayload<'a>,
+        sink: &mut dyn FnMut(ManufacturerValue<'a>),
+    ) -> bool {
+        (**self).decode_payload(meter, payload, sink)
+    }
+
+    fn conditions(
+        &self,
+        meter: &MeterIdentity,
+        source: ConditionSource,
+    ) -> &'static [ConditionBits] {
+        (**self).conditions(meter, source)
+    }
+}
+
+/// Looks up the decoder for the records of a meter.
+pub trait DecoderRegistry {
+    /// Returns the decoder that handles records of this meter.
+    fn decoder(&self, meter: &MeterIdentity) -> Option<&dyn ManufacturerDecoder>;
+
+    /// Decodes a manufacturer specific record with the decoder of the meter.
+    /// Returns `None` for standard records and records no decoder handles.
+    /// Values beyond [`MAX_MANUFACTURER_VALUES`] are dropped.
+    fn decode<'a>(
+        &self,
+        meter: &MeterIdentity,
+        record: &DataRecord<'a>,
+    ) -> Option<ManufacturerValues<'a>> {
+        if !record.is_manufacturer_specific() {
+            return None;
+        }
+        let mut values = ArrayVec::new();
+        let handled = self.decoder(meter)?.decode(meter, record, &mut |value| {
+            let _ = values.try_push(value);
+        });
+        handled.then_some(values)
+    }
+
+    /// Decodes a proprietary application layer with the decoder of the
+    /// meter. Returns `None` if no decoder recognises the layout.
+    fn decode_payload<'a>(
+        &self,
+        meter: &MeterIdentity,
+        payload: &ManufacturerPayload<'a>,
+    ) -> Option<ManufacturerValues<'a>> {
+        let mut values = ArrayVec::new();
+        let handled = self
+            .decoder(meter)?
+            .decode_payload(meter, payload, &mut |value| {
+                let _ = values.try_push(value);
+            });
+        handled.then_some(values)
+    }
+}
+
+/// A static table of decoders for `no_std` targets. The first decoder that
+/// supports a meter is used.
+impl DecoderRegistry for [&dyn ManufacturerDecoder] {
+    fn decoder(&self, meter: &MeterIdentity) -> Option<&dyn ManufacturerDecoder> {
+        self.iter().copied().find(|decoder| decoder.supports(meter))
+    }
+}
+
+/// Decoders registered at runtime. Later registrations take precedence, so
+/// a decoder can replace an earlier one for the same meters.
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct ManufacturerRegistry {
+    decoders: Vec<Box<dyn ManufacturerDecoder>>,
+}
+
+#[cfg(feature = "std")]
+impl ManufacturerRegistry {
+    /// Returns a registry without decoders.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a registry with the [`BUILTIN_DECODERS`].
+    #[must_use]
+    pub fn with_builtin_decoders() -> Self {
+        let mut registry = Self::new();
+        for decoder in BUILTIN_DECODERS {
+            registry.register(*decoder);
+        }
+        registry
+    }
+
+    pub fn register<D: ManufacturerDecoder + 'static>(&mut self, decoder: D) {
+        self.decoders.push(Box::new(decoder));
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Debug for ManufacturerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManufacturerRegistry")
+            .field("decoders", &self.decoders.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl DecoderRegistry for ManufacturerRegistry {
+    fn decoder(&self, meter: &MeterIdentity) -> Option<&dyn ManufacturerDecoder> {
+        self.decoders
+            .iter()
+            .rev()
+            .find(|decoder| decoder.supports(meter))
+            .map(|decoder| decoder.as_ref())
+    }
+}
+
+/// A data record with the values a manufacturer decoder extracted from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedRecord<'a> {
+    pub record: DataRecord<'a>,
+    /// Values of a manufacturer specific record a decoder handled.
+    pub manufacturer_values: Option<ManufacturerValues<'a>>,
+}
+
+/// Data records with manufacturer specific records decoded by a
+/// [`DecoderRegistry`], see [`DataRecords::with_decoders`].
+#[derive(Debug, Clone)]
+pub struct DecodedDataRecords<'a, 'r, R: ?Sized> {
+    records: DataRecords<'a>,
+    registry: &'r R,
+    meter: Option<MeterIdentity>,
+}
+
+impl<'a, 'r, R: DecoderRegistry + ?Sized> DecodedDataRecords<'a, 'r, R> {
+    pub(crate) fn new(
+        records: DataRecords<'a>,
+        registry: &'r R,
+        meter: Option<MeterIdentity>,
+    ) -> Self {
+        let meter = meter.or_else(|| records.meter_identity());
+        Self {
+            records,
+            registry,
+            meter,
+        }
+    }
+}
+
+impl<'a, R: DecoderRegistry + ?Sized> Iterator for DecodedDataRecords<'a, '_, R> {
+    type Item = Result<DecodedRecord<'a>, DataRecordError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match self.records.next()? {
+            Ok(record) => record,
+            Err(error) => return Some(Err(error)),
+        };
+        let manufacturer_values = self
+            .meter
+            .as_ref()
+            .and_then(|meter| self.registry.decode(meter, &record));
+        Some(Ok(DecodedRecord {
+            record,
+            manufacturer_values,
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::value_information::UnitName;
+    use crate::{parse_data_records, parse_data_records_with_header, UserDataBlock};
+
+    const METER: MeterIdentity = MeterIdentity::new(
+        ManufacturerCode {
+            code: ['A', 'B', 'C'],
+        },
+        1,
+        DeviceType::WaterMeter,
+    );
+
+    /// Reads the manufacturer data as 16-bit counters in litres.
+    struct Counters(&'static str);
+
+    impl ManufacturerDecoder for Counters {
+        fn supports(&self, meter: &MeterIdentity) -> bool {
+            meter.manufacturer == METER.manufacturer
+        }
+
+        fn decode<'a>(
+            &self,
+            _meter: &MeterIdentity,
+            record: &DataRecord<'a>,
+            sink: &mut dyn FnMut(ManufacturerValue<'a>),
+        ) -> bool {
+            let Some(data) = record.manufacturer_data() else {
+                return false;
+            };
+            for counter in data.chunks_exact(2) {
+                let value = u16::from_le_bytes([counter[0], counter[1]]);
+                sink(
+                    ManufacturerValue::new(self.0, DataType::Integer(value.into()))
+                        .with_unit(Unit {
+                            name: UnitName::Liter,
+                            exponent: 1,
+                        })
+                        .with_scale(-1),
+                );
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn static_table_decodes_manufacturer_data() {
+        static DECODERS: &[&dyn ManufacturerDecoder] = &[&Counters("Counter")];
+
+        let data = [0x01, 0x13, 0x07, 0x0F, 0x0A, 0x00, 0x14, 0x00];
+        let records: Vec<_> = parse_data_records(&data)
+            .with_decoders(DECODERS, Some(METER))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].manufacturer_values.is_none());
+
+        let values = records[1].manufacturer_values.as_ref().unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[1].name, "Counter");
+        let scaled = values[1].scaled_value().unwrap();
+        assert_eq!(
+            scaled.value,
+            ScaledNumber::Exact(crate::data_information::Decimal::new(20, -1))
+        );
+
+        // records of other manufacturers are left as they are
+        let other = MeterIdentity {
+            version: 2,
+            manufacturer: ManufacturerCode {
+                code: ['X', 'Y', 'Z'],
+            },
+            ..METER
+        };
+        assert!(parse_data_records(&data)
+            .with_decoders(DECODERS, Some(other))
+            .all(|record| record.unwrap().manufacturer_values.is_none()));
+    }
+
+    #[test]
+    fn registry_prefers_later_decoders_and_reads_the_header() {
+        let mut registry = ManufacturerRegistry::new();
+        registry.register(Counters("First"));
+        registry.register(Counters("Second"));
+
+        // long transport header of an ABC water meter, version 1
+        let data = [
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x43, 0x04, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x0F, 0x0A, 0x00,
+        ];
+        let Ok(UserDataBlock::VariableDataStructureWithLongTplHeader {
+            long_tpl_header,
+            variable_data_block,
+            ..
+        }) = UserDataBlock::try_from(data.as_slice())
+        else {
+            panic!("expected a long transport header");
+        };
+        let record = parse_data_records_with_header(variable_data_block, &long_tpl_header)
+            .with_decoders(&registry, None)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.manufacturer_values.unwrap()[0].name, "Second");
+
+        let builtin = ManufacturerRegistry::with_builtin_decoders();
+        let kamstrup = MeterIdentity {
+            manufacturer: ManufacturerCode {
+                code: ['K', 'A', 'M'],
+            },
+            ..METER
+        };
+        assert!(builtin.decoder(&kamstrup).is_some());
+        assert!(builtin.decoder(&METER).is_none());
+    }
+
+    #[test]
+    fn flags_name_the_set_bits() {
+        const NAMES: &[(u32, &str)] = &[(0x01, "Dry"), (0x02, "Leak"), (0x04, "Burst")];
+        let value = ManufacturerValue::new("Info code", DataType::Integer(0b101)).with_flags(NAMES);
+        let flags: Vec<_> = value.flags.unwrap().iter().collect();
+        assert_eq!(flags, ["Dry", "Burst"]);
+    }
+}
@@ -0,0 +1,216 @@
+//! Kamstrup (`KAM`) MULTICAL and flowIQ meters.
+//!
+//! The meters report their info code, the alarm and error state of the
+//! meter, in a record with the manufacturer specific VIF `0xFF` followed by
+//! the VIFE `0x20` (16-bit, water meters) or `0x22` (32-bit, heat and cooling
+//! meters).
+//!
+//! The target date and the readings at the target date are standard records
+//! with storage number 1, minimum and maximum flow and power use the minimum
+//! and maximum function, and the hour counters are on time records (VIF
+//! `0x22`), the error hour counter with the function value during error state.
+//! They are decoded with their standard labels and pass this decoder
+//! unchanged.
+//!
+//! Only the info code is decoded. The layout of the manufacturer specific
+//! data after the DIF `0x0F` is not published, so this decoder does not
+//! interpret it and the data is reported as it is.
+
+use m_bus_core::ManufacturerCode;
+
+use super::{ManufacturerDecoder, ManufacturerValue, MeterIdentity};
+use crate::data_record::DataRecord;
+use crate::status::{Condition, ConditionBits};
+
+const KAMSTRUP: ManufacturerCode = ManufacturerCode {
+    code: ['K', 'A', 'M'],
+};
+
+/// VIFE of the info code of MULTICAL 21 and flowIQ water meters.
+const WATER_INFO_CODE: u8 = 0x20;
+/// VIFE of the info code of MULTICAL heat and cooling meters.
+const HEAT_INFO_CODE: u8 = 0x22;
+
+/// Info code bits of MULTICAL 21 and flowIQ water meters.
+pub const WATER_INFO_CODES: &[(u32, &str)] = &[
+    (0x0001, "Dry"),
+    (0x0002, "Reverse flow"),
+    (0x0004, "Leak"),
+    (0x0008, "Burst"),
+];
+
+/// Info code bits of MULTICAL heat and cooling meters.
+pub const HEAT_INFO_CODES: &[(u32, &str)] = &[
+    (0x0000_0001, "Supply voltage interrupted"),
+    (0x0000_0002, "Low battery level"),
+    (0x0000_0008, "T1 above measuring range"),
+    (0x0000_0010, "T2 above measuring range"),
+    (0x0000_0020, "T1 below measuring range"),
+    (0x0000_0040, "T2 below measuring range"),
+    (0x0000_0080, "Temperature difference wrong polarity"),
+    (0x0000_0100, "Weak flow sensor signal or air"),
+    (0x0000_0200, "Wrong flow direction"),
+    (0x0000_0800, "Increased flow"),
+    (0x0000_1000, "Leakage on input A1"),
+    (0x0000_2000, "Leakage on input B1"),
+    (0x0000_4000, "External alarm on input A"),
+    (0x0000_8000, "External alarm on input B"),
+    (0x0001_0000, "V1 communication error"),
+    (0x0002_0000, "V1 wrong pulse figure"),
+    (0x0004_0000, "V2 communication error"),
+    (0x0008_0000, "V2 wrong pulse figure"),
+];
+
+/// Conditions of the info code bits of water meters.
+pub const WATER_CONDITIONS: &[ConditionBits] = &[
+    ConditionBits::flag(0x0001, Condition::Dry),
+    ConditionBits::flag(0x0002, Condition::ReverseFlow),
+    ConditionBits::flag(0x0004, Condition::Leak),
+    ConditionBits::flag(0x0008, Condition::Burst),
+];
+
+/// Conditions of the info code bits of heat and cooling meters.
+pub const HEAT_CONDITIONS: &[ConditionBits] = &[
+    ConditionBits::flag(0x0000_0001, Condition::PowerFailure),
+    ConditionBits::flag(0x0000_0002, Condition::LowBattery),
+    ConditionBits::flag(0x0000_0008, Condition::TemperatureSensorFault),
+    ConditionBits::flag(0x0000_0010, Condition::TemperatureSensorFault),
+    ConditionBits::flag(0x0000_0020, Condition::TemperatureSensorFault),
+    ConditionBits::flag(0x0000_0040, Condition::TemperatureSensorFault),
+    ConditionBits::flag(0x0000_0100, Condition::AirInPipe),
+    ConditionBits::flag(0x0000_0200, Condition::ReverseFlow),
+    ConditionBits::flag(0x0000_1000, Condition::Leak),
+    ConditionBits::flag(0x0000_2000, Condition::Leak),
+];
+
+/// Decodes the info codes of Kamstrup meters.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Kamstrup;
+
+impl ManufacturerDecoder for Kamstrup {
+    fn supports(&self, meter: &MeterIdentity) -> bool {
+        meter.manufacturer == KAMSTRUP
+    }
+
+    fn decode<'a>(
+        &self,
+        _meter: &MeterIdentity,
+        record: &DataRecord<'a>,
+        sink: &mut dyn FnMut(ManufacturerValue<'a>),
+    ) -> bool {
+        let (names, conditions) = match record.manufacturer_vife() {
+            Some(WATER_INFO_CODE) => (WATER_INFO_CODES, WATER_CONDITIONS),
+            Some(HEAT_INFO_CODE) => (HEAT_INFO_CODES, HEAT_CONDITIONS),
+            _ => return false,
+        };
+        let Some(value) = record.data.value.clone() else {
+            return false;
+        };
+        sink(
+            ManufacturerValue::new("Info code", value)
+                .with_flags(names)
+                .with_conditions(conditions),
+        );
+        true
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::data_information::DataType;
+    use crate::manufacturer_specific::DecoderRegistry;
+    use m_bus_core::DeviceType;
+
+    static DECODERS: &[&dyn ManufacturerDecoder] = &[&Kamstrup];
+
+    #[test]
+    fn heat_meter_info_code() {
+        let meter = MeterIdentity::new(KAMSTRUP, 0x1B, DeviceType::HeatMeterReturn);
+        let data = [0x04, 0xFF, 0x22, 0x01, 0x02, 0x00, 0x00];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        let values = DECODERS.decode(&meter, &record).unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].name, "Info code");
+        assert_eq!(values[0].value, DataType::Integer(0x0201));
+        let flags: Vec<_> = values[0].flags.unwrap().iter().collect();
+        assert_eq!(
+            flags,
+            ["Supply voltage interrupted", "Wrong flow direction"]
+        );
+    }
+
+    #[test]
+    fn water_meter_info_code() {
+        let meter = MeterIdentity::new(KAMSTRUP, 0x1B, DeviceType::ColdWaterMeter);
+        let data = [0x02, 0xFF, 0x20, 0x04, 0x00];
+        let record = DataRecord::try_from(data.as_slice()).unwrap();
+        let values = DECODERS.decode(&meter, &record).unwrap();
+        let flags: Vec<_> = values[0].flags.unwrap().iter().collect();
+        assert_eq!(flags, ["Leak"]);
+    }
+
+    #[test]
+    fn multical_601_records_are_standard() {
+        use crate::data_information::FunctionField;
+        use crate::parse_data_records;
+        use crate::value_information::ValueLabel;
+
+        let meter = MeterIdentity::new(KAMSTRUP, 0x08, DeviceType::HeatMeterReturn);
+        let data = [
+            0x04, 0x22, 0xD9, 0x03, 0x00, 0x00, // hour counter, 985 h
+            0x14, 0x3B, 0x74, 0x02, 0x00, 0x00, // maximum flow, 628 l/h
+            0x54, 0x3B, 0x03, 0x04, 0x00, 0x00, // maximum flow at the target date
+            0x42, 0x6C, 0x5F, 0x1C, // target date 31.12.2008
+            0x0F, 0x00, 0x00, 0x00, 0x00, 0xE7, 0xE4, 0x00, 0x00,
+        ];
+        let records: Vec<_> = parse_data_records(&data)
+            .with_decoders(DECODERS, Some(meter))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(records
+            .iter()
+            .all(|record| record.manufacturer_values.is_none()));
+
+        let label = |index: usize| records[index].record.value_information().unwrap().labels[0];
+        let information = |index: usize| records[index].record.data_information().unwrap();
+        assert_eq!(label(0), ValueLabel::OnTime);
+        assert_eq!(records[0].record.value(), Some(&DataType::Integer(985)));
+        assert_eq!(label(1), ValueLabel::VolumeFlow);
+        assert_eq!(information(1).function_field, FunctionField::MaximumValue);
+        assert_eq!(information(2).function_field, FunctionField::MaximumValue);
+        assert_eq!(information(2).storage_number, 1);
+        assert_eq!(label(3), ValueLabel::Date);
+        assert_eq!(information(3).storage_number, 1);
+        assert_eq!(
+            records[4].record.value(),
+            Some(&DataType::ManufacturerSpecific(&[
+                0x00, 0x00, 0x00, 0x00, 0xE7, 0xE4, 0x00, 0x00
+            ]))
+        );
+    }
+
+    #[test]
+    fn other_records_are_kept() {
+        let meter = MeterIdentity::new(KAMSTRUP, 0x1B, DeviceType::HeatMeterReturn);
+        let other_manufacturer = MeterIdentity::new(
+            ManufacturerCode {
+                code: ['A', 'B', 'C'],
+            },
+            0x1B,
+            DeviceType::HeatMeterReturn,
+        );
+        let info_code = [0x04, 0xFF, 0x22, 0x01, 0x00, 0x00, 0x00];
+        let record = DataRecord::try_from(info_code.as_slice()).unwrap();
+        assert!(DECODERS.decode(&other_manufacturer, &record).is_none());
+
+        for data in [
+            [0x04, 0xFF, 0x23, 0x01, 0x00, 0x00, 0x00].as_slice(),
+            [0x04, 0x06, 0x01, 0x00, 0x00, 0x00].as_slice(),
+        ] {
+            let record = DataRecord::try_from(data).unwrap();
+            assert!(DECODERS.decode(&meter, &record).is_none());
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Techem (`TCH`) heat cost allocators, water meters and heat meters.
+//!
+//! The meters send a proprietary application layer with the CI `0xA0`
+//! (heat cost allocators) or `0xA2` (water and heat meters). The payload
+//! starts with a status byte, followed by the date of the last billing date
+//! and the reading at that date, and the date and reading of the current
+//! billing period. Water and heat meters count the current period from zero,
+//! so their total is the sum of both readings.
+
+use m_bus_core::ManufacturerCode;
+
+use super::{ManufacturerDecoder, ManufacturerPayload, ManufacturerValue, MeterIdentity};
+use crate::data_information::{month, DataType, SingleEveryOrInvalid};
+use crate::value_information::{Unit, UnitName};
+
+const TECHEM: ManufacturerCode = ManufacturerCode {
+    code: ['T', 'C', 'H'],
+};
+
+const CUBIC_METRE: Unit = Unit {
+    name: UnitName::Meter,
+    exponent: 3,
+};
+const CELSIUS: Unit = Unit {
+    name: UnitName::Celsius,
+    exponent: 1,
+};
+const JOULE: Unit = Unit {
+    name: UnitName::Joul,
+    exponent: 1,
+};
+
+/// Layouts of the proprietary payload, selected by CI and version.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Layout {
+    /// FHKV data III heat cost allocator. `temperatures` is the offset of the
+    /// room and radiator temperatures.
+    HeatCostAllocator { temperatures: usize },
+    /// radio 3 and radio 4 water meters, volume in 0.1 m³.
+    WaterMeter,
+    /// compact V heat meters, energy in MJ.
+    HeatMeter,
+}
+
+impl Layout {
+    const fn from(control_information: u8, version: u8) -> Option<Self> {
+        match (control_information, version) {
+            (0xA0, 0x69) => Some(Self::HeatCostAllocator { temperatures: 9 }),
+            (0xA0, 0x94) => Some(Self::HeatCostAllocator { temperatures: 10 }),
+            (0xA2, 0x74 | 0x95) => Some(Self::WaterMeter),
+            (0xA2, 0x27) => Some(Self::HeatMeter),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes the proprietary payloads of Techem meters.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Techem;
+
+impl ManufacturerDecoder for Techem {
+    fn supports(&self, meter: &MeterIdentity) -> bool {
+        meter.manufacturer == TECHEM
+    }
+
+    fn decode_payload<'a>(
+        &self,
+        meter: &MeterIdentity,
+        payload: &ManufacturerPayload<'a>,
+        sink: &mut dyn FnMut(ManufacturerValue<'a>),
+    ) -> bool {
+        let Some(layout) = Layout::from(payload.control_information, meter.version) else {
+            return false;
+        };
+        let data = payload.data;
+        let (Some(previous_date), Some(current_date)) = (word(data, 1), word(data, 5)) else {
+            return false;
+        };
+        let previous_date = BillingDate::previous(previous_date);
+        match layout {
+            Layout::HeatCostAllocator { temperatures } => {
+                let (Some(previous), Some(current), Some(room), Some(radiator)) = (
+                    word(data, 3),
+                    word(data, 7),
+                    word(data, temperatures),
+                    word(data, temperatures + 2),
+                ) else {
+                    return false;
+                };
+                let current_date = previous_date.current(current_date);
+                sink(ManufacturerValue::new(
+                    "Heat cost allocation",
+                    integer(current),
+                ));
+                sink(ManufacturerValue::new(
+                    "Time point (date)",
+                    current_date.value(),
+                ));
+                sink(
+                    ManufacturerValue::new("Heat cost allocation", integer(previous))
+                        .with_storage_number(1),
+                );
+                sink(
+                    ManufacturerValue::new("Time point (date)", previous_date.value())
+                        .with_storage_number(1),
+                );
+                sink(
+                    ManufacturerValue::new("Room temperature", integer(room))
+                        .with_unit(CELSIUS)
+                        .with_scale(-2),
+                );
+                sink(
+                    ManufacturerValue::new("Radiator temperature", integer(radiator))
+                        .with_unit(CELSIUS)
+                        .with_scale(-2),
+                );
+            }
+            Layout::WaterMeter => {
+                let (Some(previous), Some(current)) = (word(data, 3), word(data, 7)) else {
+                    return false;
+                };
+                let current_date = previous_date.current(current_date);
+                let volume = |value: u32| {
+                    ManufacturerValue::new("Volume", integer(value))
+                        .with_unit(CUBIC_METRE)
+                        .with_scale(-1)
+                };
+                sink(volume(previous + current));
+                sink(ManufacturerValue::new(
+                    "Time point (date)",
+                    current_date.value(),
+                ));
+                sink(volume(previous).with_storage_number(1));
+                sink(
+                    ManufacturerValue::new("Time point (date)", previous_date.value())
+                        .with_storage_number(1),
+                );
+            }
+            Layout::HeatMeter => {
+                let (Some(previous), Some(current)) = (triple(data, 3), triple(data, 7)) else {
+                    return false;
+                };
+                let energy = |value: u32| {
+                    ManufacturerValue::new("Energy", integer(value))
+                        .with_unit(JOULE)
+                        .with_scale(6)
+                };
+                sink(energy(previous + current));
+                sink(energy(previous).with_storage_number(1));
+                sink(
+                    ManufacturerValue::new("Time point (date)", previous_date.value())
+                        .with_storage_number(1),
+                );
+            }
+        }
+        true
+    }
+}
+
+/// A date without or with the year of the last billing date.
+#[derive(Debug, Clone, Copy)]
+struct BillingDate {
+    day: u8,
+    month: u8,
+    year: u16,
+}
+
+impl BillingDate {
+    /// Day in bits 0 to 4, month in bits 5 to 8 and year since 2000 in bits
+    /// 9 to 14.
+    const fn previous(date: u32) -> Self {
+        Self {
+            day: (date & 0x1F) as u8,
+            month: ((date >> 5) & 0x0F) as u8,
+            year: 2000 + ((date >> 9) & 0x3F) as u16,
+        }
+    }
+
+    /// Day in bits 4 to 8 and month in bits 9 to 12. The year is not
+    /// transmitted; the current date lies within a year after the last
+    /// billing date.
+    const fn current(self, date: u32) -> Self {
+        let day = ((date >> 4) & 0x1F) as u8;
+        let month = ((date >> 9) & 0x0F) as u8;
+        let year = if month > self.month || (month == self.month && day > self.day) {
+            self.year
+        } else {
+            self.year + 1
+        };
+        Self { day, month, year }
+    }
+
+    const fn value(self) -> DataType<'static> {
+        DataType::Date(
+            SingleEveryOrInvalid::Single(self.day),
+            month(self.month),
+            SingleEveryOrInvalid::Single(self.year),
+        )
+    }
+}
+
+const fn integer(value: u32) -> DataType<'static> {
+    DataType::Integer(value as i64)
+}
+
+fn word(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u32::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+}
+
+fn triple(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 3)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::manufacturer_specific::{DecoderRegistry, ManufacturerValues};
+    use m_bus_core::DeviceType;
+
+    static DECODERS: &[&dyn ManufacturerDecoder] = &[&Techem];
+
+    fn decode(
+        version: u8,
+        device_type: DeviceType,
+        control_information: u8,
+        data: &[u8],
+    ) -> Option<ManufacturerValues<'_>> {
+        let meter = MeterIdentity::new(TECHEM, version, device_type);
+        DECODERS.decode_payload(&meter, &ManufacturerPayload::new(control_information, data))
+    }
+
+    fn summary<'a>(values: &'a ManufacturerValues<'_>) -> Vec<(&'a str, u64, String)> {
+        values
+            .iter()
+            .map(|value| {
+                (
+                    value.name,
+                    value.storage_number,
+                    value.scaled_value().map_or_else(
+                        || format!("{:?}", value.value),
+                        |scaled| format!("{}", scaled.value),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn heat_cost_allocator() {
+        let data = [
+            0x11, 0x9F, 0x27, 0x02, 0x04, 0x80, 0x04, 0x83, 0x00, 0xC4, 0x08, 0xF7, 0x09, 0x14,
+            0x3C, 0x00, 0x3D,
+        ];
+        let values = decode(0x69, DeviceType::HeatCostAllocator, 0xA0, &data).unwrap();
+        assert_eq!(
+            summary(&values),
+            [
+                ("Heat cost allocation", 0, "131".to_string()),
+                (
+                    "Time point (date)",
+                    0,
+                    "Date(Single(8), Single(February), Single(2020))".to_string()
+                ),
+                ("Heat cost allocation", 1, "1026".to_string()),
+                (
+                    "Time point (date)",
+                    1,
+                    "Date(Single(31), Single(December), Single(2019))".to_string()
+                ),
+                ("Room temperature", 0, "22.44".to_string()),
+                ("Radiator temperature", 0, "25.51".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn water_meter() {
+        let data = [
+            0x06, 0x9F, 0x25, 0x59, 0x00, 0xB0, 0x29, 0x31, 0x00, 0x00, 0x00, 0x03, 0x06,
+        ];
+        let values = decode(0x74, DeviceType::WarmWaterMeter, 0xA2, &data).unwrap();
+        assert_eq!(
+            summary(&values),
+            [
+                ("Volume", 0, "13.8".to_string()),
+                (
+                    "Time point (date)",
+                    0,
+                    "Date(Single(27), Single(April), Single(2019))".to_string()
+                ),
+                ("Volume", 1, "8.9".to_string()),
+                (
+                    "Time point (date)",
+                    1,
+                    "Date(Single(31), Single(December), Single(2018))".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn heat_meter() {
+        let data = [
+            0x12, 0x9F, 0x25, 0x38, 0x33, 0x00, 0xA8, 0x62, 0x26, 0x00, 0x00, 0x82, 0x00,
+        ];
+        let values = decode(0x27, DeviceType::HeatMeterFlow, 0xA2, &data).unwrap();
+        assert_eq!(
+            summary(&values),
+            [
+                ("Energy", 0, "22938000000".to_string()),
+                ("Energy", 1, "13112000000".to_string()),
+                (
+                    "Time point (date)",
+                    1,
+                    "Date(Single(31), Single(December), Single(2018))".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_versions_and_short_payloads_are_not_decoded() {
+        let data = [0x06, 0x9F, 0x25, 0x59, 0x00, 0xB0, 0x29, 0x31, 0x00];
+        assert!(decode(0x75, DeviceType::WarmWaterMeter, 0xA2, &data).is_none());
+        assert!(decode(0x74, DeviceType::WarmWaterMeter, 0xA2, &data[..8]).is_none());
+        assert!(decode(0x74, DeviceType::WarmWaterMeter, 0xA0, &data).is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! Network layer of relayed wireless M-Bus frames (CI `0x81`).
+//!
+//! Repeaters insert a network layer header in front of the next CI field. It
+//! starts with a network control field: bits 0 to 3 hold the hop count and
+//! bit 7 is set once the frame has been repeated. The control field is
+//! followed by the address of each repeater in relaying order, in the same
+//! layout as the receiver address of the extended link layer.
+
+use arrayvec::ArrayVec;
+
+use super::extended_link_layer::ReceiverAddress;
+use m_bus_core::ApplicationLayerError;
+
+/// Maximum number of repeater addresses in a network layer header.
+pub const MAX_HOPS: usize = 15;
+
+const HOP_COUNT_MASK: u8 = 0x0F;
+const REPEATED: u8 = 0x80;
+const ADDRESS_SIZE: usize = 8;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetworkLayerHeader {
+    pub network_control: u8,
+    /// Repeaters the frame passed, first repeater first.
+    pub repeater_path: ArrayVec<ReceiverAddress, MAX_HOPS>,
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for NetworkLayerHeader {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "NetworkLayerHeader{{ network_control: {}, repeater_path: {:?} }}",
+            self.network_control,
+            self.repeater_path.as_slice()
+        );
+    }
+}
+
+impl NetworkLayerHeader {
+    /// Parses the header following the CI field and returns it with its size.
+    pub fn parse(data: &[u8]) -> Result<(Self, usize), ApplicationLayerError> {
+        let network_control = *data
+            .first()
+            .ok_or(ApplicationLayerError::InsufficientData)?;
+        let hop_count = (network_control & HOP_COUNT_MASK) as usize;
+        let mut repeater_path = ArrayVec::new();
+        for hop in 0..hop_count {
+            let start = 1 + hop * ADDRESS_SIZE;
+            let address = data
+                .get(start..start + ADDRESS_SIZE)
+                .ok_or(ApplicationLayerError::InsufficientData)?;
+            repeater_path.push(ReceiverAddress {
+                manufacturer: u16::from_le_bytes([address[0], address[1]]),
+                address: [
+                    address[2], address[3], address[4], address[5], address[6], address[7],
+                ],
+            });
+        }
+        Ok((
+            Self {
+                network_control,
+                repeater_path,
+            },
+            1 + hop_count * ADDRESS_SIZE,
+        ))
+    }
+
+    #[must_use]
+    pub const fn hop_count(&self) -> u8 {
+        self.network_control & HOP_COUNT_MASK
+    }
+
+    #[must_use]
+    pub const fn is_repeated(&self) -> bool {
+        self.network_control & REPEATED != 0
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_repeater_path() {
+        let data = [
+            0x82, // repeated, two hops
+            0x2D, 0x2C, 0x01, 0x00, 0x00, 0x10, 0x01, 0x32, //
+            0x2D, 0x2C, 0x02, 0x00, 0x00, 0x10, 0x01, 0x32, //
+            0x78,
+        ];
+        let (header, size) = NetworkLayerHeader::parse(&data).unwrap();
+        assert_eq!(size, 17);
+        assert_eq!(header.hop_count(), 2);
+        assert!(header.is_repeated());
+        assert_eq!(header.repeater_path.len(), 2);
+        assert_eq!(header.repeater_path[1].manufacturer, 0x2C2D);
+        assert_eq!(header.repeater_path[1].address[0], 0x02);
+
+        assert_eq!(
+            NetworkLayerHeader::parse(&data[..9]),
+            Err(ApplicationLayerError::InsufficientData)
+        );
+    }
+}
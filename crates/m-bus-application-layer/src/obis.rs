@@ -0,0 +1,226 @@
+//! OBIS codes (IEC 62056-61) identifying values of electricity and other
+//! DLMS/COSEM meters.
+//!
+//! M-Bus records are linked to OBIS codes by declaration records, which carry
+//! the [`ValueLabel::ObisDeclaration`] VIFE and hold the six value groups as
+//! 48-bit integer or 6-byte string, least significant byte (group F) first.
+//! A declaration applies to the records with the same storage number, tariff,
+//! subunit, function and value information, see [`ObisDeclarations`]. Frames
+//! with the OBIS CI fields (`0x64`, `0x65`, `0x7E`, `0x7F`) carry such records.
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+use arrayvec::ArrayVec;
+
+use crate::data_information::FunctionField;
+use crate::data_record::DataRecord;
+use crate::value_information::{ValueInformation, ValueLabel};
+use crate::DataRecords;
+
+/// Maximum number of OBIS declarations tracked by [`ObisDeclarations`].
+pub const MAX_OBIS_DECLARATIONS: usize = 32;
+
+/// Six value groups `A-B:C.D.E*F` of an OBIS code.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ObisCode {
+    pub a: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub f: u8,
+}
+
+impl ObisCode {
+    #[must_use]
+    pub const fn new(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    #[must_use]
+    pub const fn from_bytes(bytes: [u8; 6]) -> Self {
+        let [a, b, c, d, e, f] = bytes;
+        Self { a, b, c, d, e, f }
+    }
+
+    #[must_use]
+    pub const fn to_bytes(&self) -> [u8; 6] {
+        [self.a, self.b, self.c, self.d, self.e, self.f]
+    }
+}
+
+impl TryFrom<&[u8]> for ObisCode {
+    type Error = core::array::TryFromSliceError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bytes(<[u8; 6]>::try_from(data)?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ObisCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}:{}.{}.{}*{}",
+            self.a, self.b, self.c, self.d, self.e, self.f
+        )
+    }
+}
+
+/// Returns whether the record declares an OBIS code.
+#[must_use]
+pub fn is_obis_declaration(record: &DataRecord<'_>) -> bool {
+    record
+        .value_information()
+        .is_some_and(|value| value.labels.contains(&ValueLabel::ObisDeclaration))
+}
+
+/// Returns the OBIS code held by a declaration record.
+#[must_use]
+pub fn declared_obis_code(record: &DataRecord<'_>) -> Option<ObisCode> {
+    if !is_obis_declaration(record) {
+        return None;
+    }
+    let data = record
+        .raw_bytes
+        .get(record.data_record_header.get_size()..)?;
+    let data = match data {
+        [0x06, string @ ..] if string.len() == 6 => string,
+        _ => data,
+    };
+    let mut bytes = <[u8; 6]>::try_from(data).ok()?;
+    bytes.reverse();
+    Some(ObisCode::from_bytes(bytes))
+}
+
+/// The part of a record a declaration is matched on.
+#[derive(Debug, Clone, PartialEq)]
+struct DeclarationKey {
+    storage_number: u64,
+    tariff: u64,
+    device: u64,
+    function_field: FunctionField,
+    value_information: Option<ValueInformation>,
+}
+
+impl DeclarationKey {
+    fn of(record: &DataRecord<'_>) -> Option<Self> {
+        let information = record.data_information()?;
+        let value_information = record.value_information().cloned().map(|mut value| {
+            value
+                .labels
+                .retain(|label| *label != ValueLabel::ObisDeclaration);
+            value
+        });
+        Some(Self {
+            storage_number: information.storage_number,
+            tariff: information.tariff,
+            device: information.device,
+            function_field: information.function_field,
+            value_information,
+        })
+    }
+}
+
+/// OBIS codes declared by the records of a frame.
+#[derive(Debug, Clone, Default)]
+pub struct ObisDeclarations {
+    entries: ArrayVec<(DeclarationKey, ObisCode), MAX_OBIS_DECLARATIONS>,
+}
+
+impl ObisDeclarations {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the declarations of all records, skipping records that fail
+    /// to parse.
+    pub fn collect(records: DataRecords<'_>) -> Self {
+        let mut declarations = Self::new();
+        for record in records.recovering().filter_map(Result::ok) {
+            declarations.declare(&record);
+        }
+        declarations
+    }
+
+    /// Stores the OBIS code of a declaration record. Returns `false` for
+    /// other records and when no capacity is left.
+    pub fn declare(&mut self, record: &DataRecord<'_>) -> bool {
+        let (Some(obis), Some(key)) = (declared_obis_code(record), DeclarationKey::of(record))
+        else {
+            return false;
+        };
+        if let Some(entry) = self.entries.iter_mut().find(|(known, _)| *known == key) {
+            entry.1 = obis;
+            return true;
+        }
+        self.entries.try_push((key, obis)).is_ok()
+    }
+
+    /// Returns the OBIS code declared for this record.
+    #[must_use]
+    pub fn obis_code(&self, record: &DataRecord<'_>) -> Option<ObisCode> {
+        let key = DeclarationKey::of(record)?;
+        self.entries
+            .iter()
+            .find(|(known, _)| *known == key)
+            .map(|(_, obis)| *obis)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::parse_data_records;
+
+    #[test]
+    fn declaration_links_records_with_same_header() {
+        let data = [
+            // 48-bit OBIS declaration for energy in Wh, tariff 0: 1-0:1.8.0*255
+            0x06, 0x83, 0x3F, 0xFF, 0x00, 0x08, 0x01, 0x00, 0x01, //
+            // 32-bit energy value in Wh
+            0x04, 0x03, 0x39, 0x30, 0x00, 0x00, //
+            // 32-bit energy value in Wh, storage number 1
+            0x44, 0x03, 0x10, 0x27, 0x00, 0x00,
+        ];
+        let records: Vec<_> = parse_data_records(&data).map(Result::unwrap).collect();
+        assert!(is_obis_declaration(&records[0]));
+        assert_eq!(
+            declared_obis_code(&records[0]),
+            Some(ObisCode::new(1, 0, 1, 8, 0, 255))
+        );
+
+        let declarations = ObisDeclarations::collect(parse_data_records(&data));
+        assert_eq!(declarations.len(), 1);
+        assert_eq!(
+            declarations.obis_code(&records[1]).unwrap().to_string(),
+            "1-0:1.8.0*255"
+        );
+        assert_eq!(declarations.obis_code(&records[2]), None);
+
+        // a faulty record before the declaration is skipped
+        let mut faulty = vec![0x0A, 0x13, 0xAB, 0x0C];
+        faulty.extend_from_slice(&data);
+        let declarations = ObisDeclarations::collect(parse_data_records(&faulty));
+        assert_eq!(
+            declarations.obis_code(&records[1]).unwrap().to_string(),
+            "1-0:1.8.0*255"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Logical registers of a meter.
+//!
+//! The DIF and DIFEs of a record place its value in a register of the meter:
+//! the function (instantaneous, maximum, minimum or value during error state),
+//! the tariff, the subunit and the storage number. Together with the quantity
+//! of the VIF and the modifiers of combinable VIFEs, a [`RegisterKey`]
+//! identifies the same register across frames and meters, whatever unit and
+//! scale the meter transmits it in.
+
+use core::fmt;
+
+use arrayvec::ArrayVec;
+
+use crate::data_information::FunctionField;
+use crate::data_record::DataRecord;
+use crate::value_information::ValueLabel;
+
+/// Quantity and position of a register, see the [module documentation](self).
+///
+/// It displays as a stable identifier of dot-separated [`ValueLabel::id`]s of
+/// the quantities and modifiers, then the function unless instantaneous, the
+/// tariff and subunit unless 0, and the storage number, e.g.
+/// `energy.tariff1.storage0` or `power.at_phase_l1.maximum.storage2`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegisterKey {
+    pub quantities: ArrayVec<ValueLabel, 10>,
+    /// Labels of combinable VIFEs, without correction factors, which are
+    /// already applied to the value.
+    pub modifiers: ArrayVec<ValueLabel, 10>,
+    pub function: FunctionField,
+    pub tariff: u64,
+    pub subunit: u64,
+    pub storage_number: u64,
+}
+
+impl RegisterKey {
+    /// Returns the register of a record with data information and at least
+    /// one quantity label.
+    #[must_use]
+    pub fn of(record: &DataRecord<'_>) -> Option<Self> {
+        let information = record.data_information()?;
+        let mut quantities = ArrayVec::new();
+        let mut modifiers = ArrayVec::new();
+        for label in &record.value_information()?.labels {
+            match label {
+                ValueLabel::MultiplicativeCorrectionFactor(_)
+                | ValueLabel::AdditiveCorrectionConstant(_) => {}
+                label if label.is_modifier() => modifiers.push(*label),
+                label => quantities.push(*label),
+            }
+        }
+        if quantities.is_empty() {
+            return None;
+        }
+        Some(Self {
+            quantities,
+            modifiers,
+            function: information.function_field,
+            tariff: information.tariff,
+            subunit: information.device,
+            storage_number: information.storage_number,
+        })
+    }
+}
+
+impl fmt::Display for RegisterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, label) in self.quantities.iter().chain(&self.modifiers).enumerate() {
+            if index > 0 {
+                f.write_str(".")?;
+            }
+            f.write_str(label.id())?;
+        }
+        match self.function {
+            FunctionField::InstantaneousValue => {}
+            FunctionField::MaximumValue => f.write_str(".maximum")?,
+            FunctionField::MinimumValue => f.write_str(".minimum")?,
+            FunctionField::ValueDuringErrorState => f.write_str(".error_state")?,
+        }
+        if self.tariff != 0 {
+            write!(f, ".tariff{}", self.tariff)?;
+        }
+        if self.subunit != 0 {
+            write!(f, ".subunit{}", self.subunit)?;
+        }
+        write!(f, ".storage{}", self.storage_number)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::DataRecords;
+
+    fn keys(data: &[u8]) -> Vec<String> {
+        DataRecords::new(data, None)
+            .map(|record| RegisterKey::of(&record.unwrap()).unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn identifiers_name_quantity_and_position() {
+        let data = [
+            0x04, 0x06, 0x10, 0x27, 0x00, 0x00, // energy in kWh
+            0x84, 0x10, 0x06, 0x10, 0x27, 0x00, 0x00, // energy of tariff 1
+            0xC4, 0x40, 0x13, 0x20, 0x03, 0x00, 0x00, // volume at storage 1, subunit 1
+            0x94, 0x01, 0xAB, 0xFC, 0x01, 0x20, 0x03, 0x00, 0x00, // maximum power at L1
+        ];
+        assert_eq!(
+            keys(&data),
+            [
+                "energy.storage0",
+                "energy.tariff1.storage0",
+                "volume.subunit1.storage1",
+                "power.at_phase_l1.maximum.storage2",
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_and_scale_do_not_change_the_register() {
+        let kilowatt_hours = [0x04, 0x06, 0x01, 0x00, 0x00, 0x00];
+        let megajoules = [0x04, 0x0E, 0x01, 0x00, 0x00, 0x00];
+        let corrected = [0x04, 0x86, 0x7C, 0x01, 0x00, 0x00, 0x00];
+        let key = |data| RegisterKey::of(&DataRecord::try_from(data).unwrap());
+        assert_eq!(key(&kilowatt_hours[..]), key(&megajoules[..]));
+        assert_eq!(key(&kilowatt_hours[..]), key(&corrected[..]));
+        let storage = [0x44, 0x06, 0x01, 0x00, 0x00, 0x00];
+        assert_ne!(key(&kilowatt_hours[..]), key(&storage[..]));
+    }
+}
@@ -0,0 +1,157 @@
+//! Physical quantities of data records.
+//!
+//! [`DataRecord::scaled_value`](crate::DataRecord::scaled_value) combines the
+//! parsed value with the decimal scale and additive offset of the value
+//! information, `value * 10^scale + 10^offset`. Integer and BCD values stay
+//! exact, real values are scaled as `f64`. [`ScaledValue::to_si`] converts the
+//! quantity to coherent SI units, e.g. Wh to J, °C to K and m³/h to m³/s, and
+//! [`ScaledValue::to_unit_system`] to metric or US customary units.
+
+use arrayvec::ArrayVec;
+
+use crate::data_information::Decimal;
+use crate::unit_conversion::{self, ConversionError, UnitSystem};
+use crate::value_information::{Unit, ValueLabel};
+
+/// Numeric part of a [`ScaledValue`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ScaledNumber {
+    Exact(Decimal),
+    Real(f64),
+}
+
+impl ScaledNumber {
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        match self {
+            Self::Exact(value) => value.to_f64(),
+            Self::Real(value) => value,
+        }
+    }
+
+    /// Multiplies the value by `10^exponent`.
+    #[must_use]
+    pub fn scaled(self, exponent: i16) -> Self {
+        match self {
+            Self::Exact(value) => Self::Exact(value.scaled(exponent)),
+            Self::Real(value) => Self::Real(value * Decimal::new(1, exponent).to_f64()),
+        }
+    }
+
+    #[must_use]
+    pub fn plus(self, other: Decimal) -> Self {
+        match self {
+            Self::Exact(value) => value
+                .checked_add(other)
+                .map_or(Self::Real(value.to_f64() + other.to_f64()), Self::Exact),
+            Self::Real(value) => Self::Real(value + other.to_f64()),
+        }
+    }
+
+    #[must_use]
+    pub fn times(self, factor: Decimal) -> Self {
+        match self {
+            Self::Exact(value) => value
+                .checked_mul(factor)
+                .map_or(Self::Real(value.to_f64() * factor.to_f64()), Self::Exact),
+            Self::Real(value) => Self::Real(value * factor.to_f64()),
+        }
+    }
+
+    /// Divides by `divisor`, falling back to `f64` if the quotient is not a
+    /// finite decimal.
+    #[must_use]
+    pub fn divided_by(self, divisor: Decimal) -> Self {
+        match self {
+            Self::Exact(value) => value
+                .checked_div(divisor)
+                .map_or(Self::Real(value.to_f64() / divisor.to_f64()), Self::Exact),
+            Self::Real(value) => Self::Real(value / divisor.to_f64()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for ScaledNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(value) => write!(f, "{}", value),
+            Self::Real(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Physical quantity of a record with its units and labels.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaledValue {
+    pub value: ScaledNumber,
+    pub units: ArrayVec<Unit, 10>,
+    pub labels: ArrayVec<ValueLabel, 10>,
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ScaledValue {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "ScaledValue{{ value: {}, units: {:?}, labels: {:?} }}",
+            self.value,
+            self.units.as_slice(),
+            self.labels.as_slice()
+        );
+    }
+}
+
+impl ScaledValue {
+    /// Returns the primary label of the quantity, e.g. `Energy`.
+    #[must_use]
+    pub fn label(&self) -> Option<ValueLabel> {
+        self.labels.first().copied()
+    }
+
+    /// Converts the quantity to coherent SI units.
+    ///
+    /// Time units become seconds, litres and gallons cubic metres, bar
+    /// pascal, tonnes kilograms, calories joules and temperatures kelvin.
+    /// W·s and J/s are reported as J and W. Units without an SI counterpart,
+    /// like months, years or HCA units, are kept.
+    #[must_use]
+    pub fn to_si(&self) -> Self {
+        self.to_unit_system(UnitSystem::Si)
+    }
+
+    /// Converts the quantity to the units of a unit system.
+    #[must_use]
+    pub fn to_unit_system(&self, system: UnitSystem) -> Self {
+        let units = system.units(&self.units);
+        match self.convert(&units) {
+            Ok(value) => Self {
+                value,
+                units,
+                labels: self.labels.clone(),
+            },
+            Err(_) => self.clone(),
+        }
+    }
+
+    /// Returns the value in `10^decimal_exponent` times the given units, e.g.
+    /// in MWh for watt and hour with exponent 6.
+    pub fn convert_to(
+        &self,
+        units: &[Unit],
+        decimal_exponent: i16,
+    ) -> Result<ScaledNumber, ConversionError> {
+        Ok(self
+            .convert(units)?
+            .scaled(decimal_exponent.saturating_neg()))
+    }
+
+    fn convert(&self, units: &[Unit]) -> Result<ScaledNumber, ConversionError> {
+        let absolute = !self.labels.contains(&ValueLabel::TemperatureDifference);
+        unit_conversion::convert_units(self.value, &self.units, units, absolute)
+    }
+}
@@ -0,0 +1,559 @@
+//! Named conditions of a meter from its status byte and error flags.
+//!
+//! The status byte of the transport header reports the application state in
+//! bits 0 and 1 (busy, error, abnormal condition), low power in bit 2 and
+//! permanent and temporary errors in bits 3 and 4; the meaning of bits 5 to 7
+//! is left to the manufacturer. Error flag records (VIF `0xFD 0x17`) and info
+//! codes in manufacturer specific records are bitfields whose layout depends
+//! on manufacturer and device type.
+//!
+//! [`Conditions::collect`] maps all three to [`Condition`]s: the status byte
+//! with [`OMS_STATUS`], bits a [`ManufacturerDecoder`] assigns through
+//! [`ManufacturerDecoder::conditions`], and decoded flags carrying
+//! [`Flags::conditions`]. Manufacturer tables take precedence over the OMS
+//! status table for the bits they cover. Neither EN 13757-3 nor OMS define
+//! the bits of error flag records, so they are only read with the table of
+//! a decoder, e.g. [`DeviceTypeErrorFlags`] for meters following a common
+//! layout per device type.
+//!
+//! [`ManufacturerDecoder::conditions`]: crate::manufacturer_specific::ManufacturerDecoder::conditions
+//! [`Flags::conditions`]: crate::manufacturer_specific::Flags::conditions
+
+use arrayvec::ArrayVec;
+use m_bus_core::DeviceType;
+
+use crate::data_information::{DataType, FunctionField};
+use crate::data_record::DataRecord;
+use crate::manufacturer_specific::{DecoderRegistry, ManufacturerDecoder, MeterIdentity};
+use crate::value_information::ValueLabel;
+use crate::{DataRecords, StatusField};
+
+/// Maximum number of distinct conditions collected by [`Conditions`].
+pub const MAX_CONDITIONS: usize = 16;
+
+/// A state of the meter named by status bits or error flags.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Condition {
+    ApplicationBusy,
+    ApplicationError,
+    AbnormalCondition,
+    LowBattery,
+    PermanentError,
+    TemporaryError,
+    PowerFailure,
+    Leak,
+    Burst,
+    Dry,
+    ReverseFlow,
+    Tamper,
+    AirInPipe,
+    TemperatureSensorFault,
+}
+
+impl Condition {
+    /// Returns the name of the condition in sentence case.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ApplicationBusy => "Application busy",
+            Self::ApplicationError => "Application error",
+            Self::AbnormalCondition => "Abnormal condition",
+            Self::LowBattery => "Low battery",
+            Self::PermanentError => "Permanent error",
+            Self::TemporaryError => "Temporary error",
+            Self::PowerFailure => "Power failure",
+            Self::Leak => "Leak",
+            Self::Burst => "Burst",
+            Self::Dry => "Dry",
+            Self::ReverseFlow => "Reverse flow",
+            Self::Tamper => "Tamper",
+            Self::AirInPipe => "Air in pipe",
+            Self::TemperatureSensorFault => "Temperature sensor fault",
+        }
+    }
+
+    /// Returns the stable snake case identifier of the condition.
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::ApplicationBusy => "application_busy",
+            Self::ApplicationError => "application_error",
+            Self::AbnormalCondition => "abnormal_condition",
+            Self::LowBattery => "low_battery",
+            Self::PermanentError => "permanent_error",
+            Self::TemporaryError => "temporary_error",
+            Self::PowerFailure => "power_failure",
+            Self::Leak => "leak",
+            Self::Burst => "burst",
+            Self::Dry => "dry",
+            Self::ReverseFlow => "reverse_flow",
+            Self::Tamper => "tamper",
+            Self::AirInPipe => "air_in_pipe",
+            Self::TemperatureSensorFault => "temperature_sensor_fault",
+        }
+    }
+}
+
+/// Where a condition was read from.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConditionSource {
+    /// Status byte of the transport header.
+    Status,
+    /// Error flag record of the current readings.
+    ErrorFlags,
+    /// Flags a manufacturer decoder extracted, e.g. an info code.
+    ManufacturerFlags,
+}
+
+/// A condition that is present when the bits under `mask` equal `value`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConditionBits {
+    pub mask: u32,
+    pub value: u32,
+    pub condition: Condition,
+}
+
+impl ConditionBits {
+    /// A condition signalled by all bits of `mask` being set.
+    #[must_use]
+    pub const fn flag(mask: u32, condition: Condition) -> Self {
+        Self {
+            mask,
+            value: mask,
+            condition,
+        }
+    }
+
+    #[must_use]
+    pub const fn matches(&self, bits: u32) -> bool {
+        bits & self.mask == self.value
+    }
+}
+
+/// Conditions of the status byte standardised by EN 13757-3 and OMS.
+pub const OMS_STATUS: &[ConditionBits] = &[
+    ConditionBits {
+        mask: 0b11,
+        value: 0b01,
+        condition: Condition::ApplicationBusy,
+    },
+    ConditionBits {
+        mask: 0b11,
+        value: 0b10,
+        condition: Condition::ApplicationError,
+    },
+    ConditionBits {
+        mask: 0b11,
+        value: 0b11,
+        condition: Condition::AbnormalCondition,
+    },
+    ConditionBits::flag(StatusField::POWER_LOW.bits() as u32, Condition::LowBattery),
+    ConditionBits::flag(
+        StatusField::PERMANENT_ERROR.bits() as u32,
+        Condition::PermanentError,
+    ),
+    ConditionBits::flag(
+        StatusField::TEMPORARY_ERROR.bits() as u32,
+        Condition::TemporaryError,
+    ),
+];
+
+/// Common error flag layout of water meters, not defined by a standard.
+pub const WATER_ERROR_FLAGS: &[ConditionBits] = &[
+    ConditionBits::flag(1 << 0, Condition::Dry),
+    ConditionBits::flag(1 << 1, Condition::ReverseFlow),
+    ConditionBits::flag(1 << 2, Condition::Leak),
+    ConditionBits::flag(1 << 3, Condition::Burst),
+    ConditionBits::flag(1 << 4, Condition::Tamper),
+    ConditionBits::flag(1 << 5, Condition::AirInPipe),
+];
+
+/// Common error flag layout of heat and cooling meters, not defined by a
+/// standard.
+pub const HEAT_ERROR_FLAGS: &[ConditionBits] = &[
+    ConditionBits::flag(1 << 0, Condition::TemperatureSensorFault),
+    ConditionBits::flag(1 << 1, Condition::AirInPipe),
+    ConditionBits::flag(1 << 2, Condition::Leak),
+    ConditionBits::flag(1 << 4, Condition::Tamper),
+];
+
+/// Common error flag layout of gas meters, not defined by a standard.
+pub const GAS_ERROR_FLAGS: &[ConditionBits] = &[
+    ConditionBits::flag(1 << 1, Condition::ReverseFlow),
+    ConditionBits::flag(1 << 4, Condition::Tamper),
+];
+
+/// Returns the common error flag layout of a device type. Device types
+/// without such a layout get an empty table.
+#[must_use]
+pub const fn device_type_error_flags(device_type: DeviceType) -> &'static [ConditionBits] {
+    match device_type {
+        DeviceType::WaterMeter
+        | DeviceType::WarmWaterMeter
+        | DeviceType::HotWaterMeter
+        | DeviceType::ColdWaterMeter
+        | DeviceType::DualRegisterWaterMeter
+        | DeviceType::IrrigationWaterMeter
+        | DeviceType::WasteWaterMeter => WATER_ERROR_FLAGS,
+        DeviceType::HeatMeterReturn
+        | DeviceType::HeatMeterFlow
+        | DeviceType::CoolingMeterReturn
+        | DeviceType::CoolingMeterFlow
+        | DeviceType::CombinedHeatCoolingMeter => HEAT_ERROR_FLAGS,
+        DeviceType::GasMeter => GAS_ERROR_FLAGS,
+        _ => &[],
+    }
+}
+
+/// Reads the error flags of all meters with [`device_type_error_flags`].
+///
+/// The layouts are not standardised, so the decoder is not built in. Put it
+/// after the manufacturer decoders of a static table, or register it before
+/// them in a [`ManufacturerRegistry`], so that it only applies to meters no
+/// other decoder supports.
+///
+/// [`ManufacturerRegistry`]: crate::manufacturer_specific::ManufacturerRegistry
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeviceTypeErrorFlags;
+
+impl ManufacturerDecoder for DeviceTypeErrorFlags {
+    fn supports(&self, _meter: &MeterIdentity) -> bool {
+        true
+    }
+
+    fn conditions(
+        &self,
+        meter: &MeterIdentity,
+        source: ConditionSource,
+    ) -> &'static [ConditionBits] {
+        match source {
+            ConditionSource::ErrorFlags => device_type_error_flags(meter.device_type),
+            _ => &[],
+        }
+    }
+}
+
+/// A condition and where it was read from.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DetectedCondition {
+    pub condition: Condition,
+    pub source: ConditionSource,
+}
+
+/// Conditions present in a frame, each reported once per source.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conditions {
+    entries: ArrayVec<DetectedCondition, MAX_CONDITIONS>,
+}
+
+impl Conditions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the conditions of the status byte of a variable data
+    /// structure, the error flag records with storage number 0 and the flags
+    /// decoded from manufacturer specific records, skipping faulty records.
+    /// Without `meter` only the OMS status table applies.
+    pub fn collect<R: DecoderRegistry + ?Sized>(
+        registry: &R,
+        meter: Option<&MeterIdentity>,
+        status: Option<StatusField>,
+        records: Option<DataRecords<'_>>,
+    ) -> Self {
+        let table = |source| {
+            meter
+                .and_then(|meter| Some(registry.decoder(meter)?.conditions(meter, source)))
+                .unwrap_or(&[])
+        };
+        let mut conditions = Self::new();
+        if let Some(status) = status {
+            let overrides = table(ConditionSource::Status);
+            let standard = OMS_STATUS.iter().filter(|standard| {
+                overrides
+                    .iter()
+                    .all(|entry| entry.mask & standard.mask == 0)
+            });
+            conditions.insert_matching(
+                overrides.iter().chain(standard),
+                u32::from(status.bits()),
+                ConditionSource::Status,
+            );
+        }
+        let error_flags = table(ConditionSource::ErrorFlags);
+        let records = records.into_iter().flat_map(DataRecords::recovering);
+        for record in records.filter_map(Result::ok) {
+            if let Some(bits) = error_flags_of(&record) {
+                conditions.insert_matching(error_flags, bits, ConditionSource::ErrorFlags);
+                continue;
+            }
+            let values = meter.and_then(|meter| registry.decode(meter, &record));
+            for flags in values.iter().flatten().filter_map(|value| value.flags) {
+                conditions.insert_matching(
+                    flags.conditions,
+                    flags.bits,
+                    ConditionSource::ManufacturerFlags,
+                );
+            }
+        }
+        conditions
+    }
+
+    /// Adds a condition unless it is already present from the same source.
+    /// Returns `false` when no capacity is left.
+    pub fn insert(&mut self, condition: Condition, source: ConditionSource) -> bool {
+        let detected = DetectedCondition { condition, source };
+        self.entries.contains(&detected) || self.entries.try_push(detected).is_ok()
+    }
+
+    fn insert_matching<'t>(
+        &mut self,
+        table: impl IntoIterator<Item = &'t ConditionBits>,
+        bits: u32,
+        source: ConditionSource,
+    ) {
+        for entry in table.into_iter().filter(|entry| entry.matches(bits)) {
+            self.insert(entry.condition, source);
+        }
+    }
+
+    /// Returns whether the condition is present from any source.
+    #[must_use]
+    pub fn contains(&self, condition: Condition) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.condition == condition)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DetectedCondition> {
+        self.entries.iter()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Returns the bits of a current error flag record.
+fn error_flags_of(record: &DataRecord<'_>) -> Option<u32> {
+    let information = record.data_information()?;
+    if information.storage_number != 0
+        || information.function_field != FunctionField::InstantaneousValue
+        || !record
+            .value_information()?
+            .labels
+            .contains(&ValueLabel::ErrorFlags)
+    {
+        return None;
+    }
+    match record.value()? {
+        // type B integers are signed, undo the sign extension of the field
+        DataType::Integer(bits) => match record.data.size {
+            size @ 1..=7 => u32::try_from(bits & ((1 << (size * 8)) - 1)).ok(),
+            _ => u32::try_from(*bits).ok(),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::manufacturer_specific::{kamstrup::Kamstrup, ManufacturerDecoder};
+    use m_bus_core::ManufacturerCode;
+
+    struct Acme;
+
+    const ACME: ManufacturerCode = ManufacturerCode {
+        code: ['A', 'C', 'M'],
+    };
+
+    const ACME_STATUS: &[ConditionBits] = &[
+        ConditionBits::flag(0x04, Condition::PowerFailure),
+        ConditionBits::flag(0x20, Condition::Tamper),
+    ];
+
+    const ACME_WATER_ERRORS: &[ConditionBits] = &[
+        ConditionBits::flag(0x01, Condition::Leak),
+        ConditionBits::flag(0x02, Condition::AirInPipe),
+    ];
+
+    impl ManufacturerDecoder for Acme {
+        fn supports(&self, meter: &MeterIdentity) -> bool {
+            meter.manufacturer == ACME
+        }
+
+        fn conditions(
+            &self,
+            meter: &MeterIdentity,
+            source: ConditionSource,
+        ) -> &'static [ConditionBits] {
+            match (source, meter.device_type) {
+                (ConditionSource::Status, _) => ACME_STATUS,
+                (ConditionSource::ErrorFlags, DeviceType::WaterMeter) => ACME_WATER_ERRORS,
+                _ => &[],
+            }
+        }
+    }
+
+    static DECODERS: &[&dyn ManufacturerDecoder] = &[&Acme, &Kamstrup];
+
+    static WITH_DEVICE_TYPE_ERROR_FLAGS: &[&dyn ManufacturerDecoder] =
+        &[&Acme, &Kamstrup, &DeviceTypeErrorFlags];
+
+    fn collect(
+        meter: Option<MeterIdentity>,
+        status: u8,
+        data: &[u8],
+    ) -> Vec<(Condition, ConditionSource)> {
+        collect_with(DECODERS, meter, status, data)
+    }
+
+    fn collect_with(
+        decoders: &[&dyn ManufacturerDecoder],
+        meter: Option<MeterIdentity>,
+        status: u8,
+        data: &[u8],
+    ) -> Vec<(Condition, ConditionSource)> {
+        Conditions::collect(
+            decoders,
+            meter.as_ref(),
+            Some(StatusField::from_bits_truncate(status)),
+            Some(DataRecords::new(data, None)),
+        )
+        .iter()
+        .map(|detected| (detected.condition, detected.source))
+        .collect()
+    }
+
+    #[test]
+    fn oms_status_bits() {
+        assert_eq!(
+            collect(None, 0b0001_0110, &[]),
+            [
+                (Condition::ApplicationError, ConditionSource::Status),
+                (Condition::LowBattery, ConditionSource::Status),
+                (Condition::TemporaryError, ConditionSource::Status),
+            ]
+        );
+        assert!(collect(None, 0xE0, &[]).is_empty());
+    }
+
+    #[test]
+    fn manufacturer_tables_override_status_and_name_error_flags() {
+        let meter = MeterIdentity::new(ACME, 1, DeviceType::WaterMeter);
+        let error_flags = [
+            0x02, 0xFD, 0x17, 0x03, 0x00, // leak and air in pipe
+            0x42, 0xFD, 0x17, 0x01, 0x00, // earlier flags, ignored
+        ];
+        assert_eq!(
+            collect(Some(meter), 0x27, &error_flags),
+            [
+                (Condition::PowerFailure, ConditionSource::Status),
+                (Condition::Tamper, ConditionSource::Status),
+                (Condition::AbnormalCondition, ConditionSource::Status),
+                (Condition::Leak, ConditionSource::ErrorFlags),
+                (Condition::AirInPipe, ConditionSource::ErrorFlags),
+            ]
+        );
+        let electricity_meter = MeterIdentity::new(ACME, 1, DeviceType::ElectricityMeter);
+        assert!(collect(Some(electricity_meter), 0x00, &error_flags).is_empty());
+    }
+
+    #[test]
+    fn device_type_error_flags_are_opt_in() {
+        let other = ManufacturerCode {
+            code: ['X', 'Y', 'Z'],
+        };
+        let error_flags = [
+            0x0A, 0x13, 0xAB, 0x0C, // faulty record, skipped
+            0x02, 0xFD, 0x17, 0x1C, 0x00, // leak, burst and tamper
+        ];
+        let water_meter = MeterIdentity::new(other, 1, DeviceType::WaterMeter);
+        assert!(collect(Some(water_meter), 0x00, &error_flags).is_empty());
+
+        let collect =
+            |meter, data: &[u8]| collect_with(WITH_DEVICE_TYPE_ERROR_FLAGS, meter, 0x00, data);
+        assert_eq!(
+            collect(Some(water_meter), &error_flags),
+            [
+                (Condition::Leak, ConditionSource::ErrorFlags),
+                (Condition::Burst, ConditionSource::ErrorFlags),
+                (Condition::Tamper, ConditionSource::ErrorFlags),
+            ]
+        );
+        let heat_meter = MeterIdentity::new(other, 1, DeviceType::HeatMeterReturn);
+        assert_eq!(
+            collect(Some(heat_meter), &[0x02, 0xFD, 0x17, 0x03, 0x00]),
+            [
+                (
+                    Condition::TemperatureSensorFault,
+                    ConditionSource::ErrorFlags
+                ),
+                (Condition::AirInPipe, ConditionSource::ErrorFlags),
+            ]
+        );
+        assert!(collect(None, &error_flags).is_empty());
+
+        // manufacturer tables still take precedence
+        let acme_water_meter = MeterIdentity::new(ACME, 1, DeviceType::WaterMeter);
+        assert_eq!(
+            collect(Some(acme_water_meter), &[0x02, 0xFD, 0x17, 0x01, 0x00]),
+            [(Condition::Leak, ConditionSource::ErrorFlags)]
+        );
+    }
+
+    #[test]
+    fn error_flags_are_read_without_sign_extension() {
+        let other = ManufacturerCode {
+            code: ['X', 'Y', 'Z'],
+        };
+        let water_meter = MeterIdentity::new(other, 1, DeviceType::WaterMeter);
+        let collect =
+            |data: &[u8]| collect_with(WITH_DEVICE_TYPE_ERROR_FLAGS, Some(water_meter), 0x00, data);
+        // 16-bit flags with the top bit set
+        assert_eq!(
+            collect(&[0x02, 0xFD, 0x17, 0x01, 0x80]),
+            [(Condition::Dry, ConditionSource::ErrorFlags)]
+        );
+        // flags beyond 32 bits are not truncated into the table
+        assert!(collect(&[0x06, 0xFD, 0x17, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00]).is_empty());
+    }
+
+    #[test]
+    fn decoded_info_codes_carry_conditions() {
+        let kamstrup = ManufacturerCode {
+            code: ['K', 'A', 'M'],
+        };
+        let meter = MeterIdentity::new(kamstrup, 0x1B, DeviceType::HeatMeterReturn);
+        let info_code = [0x04, 0xFF, 0x22, 0x18, 0x01, 0x00, 0x00];
+        assert_eq!(
+            collect(Some(meter), 0x00, &info_code),
+            [
+                (
+                    Condition::TemperatureSensorFault,
+                    ConditionSource::ManufacturerFlags
+                ),
+                (Condition::AirInPipe, ConditionSource::ManufacturerFlags),
+            ]
+        );
+    }
+}
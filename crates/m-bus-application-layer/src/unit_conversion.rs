@@ -0,0 +1,385 @@
+//! Conversion between compatible unit lists.
+//!
+//! Every unit with a fixed size is defined by its SI dimension (mass, length,
+//! time, temperature and current exponents) and its size in coherent SI
+//! units. Two unit lists can be converted if their dimensions are equal, so
+//! kWh converts to GJ but not to m³. Units without a fixed size, e.g. months,
+//! HCA units or currency, only convert to themselves. Prefixes are decimal
+//! exponents, see [`ScaledValue::convert_to`](crate::scaled_value::ScaledValue::convert_to).
+//!
+//! A list holding a single Celsius, Fahrenheit or Kelvin unit is converted as
+//! absolute temperature, anything else as temperature difference.
+
+use arrayvec::ArrayVec;
+
+use crate::data_information::Decimal;
+use crate::scaled_value::ScaledNumber;
+use crate::value_information::{Unit, UnitName};
+
+const ONE: Decimal = Decimal::new(1, 0);
+/// Exponents of mass, length, time, temperature and current.
+const DIMENSIONS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// The unit lists describe different quantities.
+    IncompatibleUnits,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionError::IncompatibleUnits => write!(f, "Incompatible units"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConversionError {}
+
+/// Unit conventions values can be reported in.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum UnitSystem {
+    /// Coherent SI units: J, W, K, m³, m³/s, Pa.
+    Si,
+    /// Metric units as used by meters: Wh, °C, m³/h, bar. Non-metric units
+    /// are converted to their metric counterpart.
+    Metric,
+    /// US customary units: °F, US gallons and feet.
+    Us,
+}
+
+/// Size of a unit in coherent SI units.
+struct Definition {
+    dimension: [i32; DIMENSIONS],
+    numerator: Decimal,
+    denominator: Decimal,
+}
+
+const fn definition(name: UnitName) -> Option<Definition> {
+    const fn unit(dimension: [i32; DIMENSIONS], numerator: Decimal) -> Option<Definition> {
+        Some(Definition {
+            dimension,
+            numerator,
+            denominator: ONE,
+        })
+    }
+    const ENERGY: [i32; DIMENSIONS] = [1, 2, -2, 0, 0];
+    const POWER: [i32; DIMENSIONS] = [1, 2, -3, 0, 0];
+    const PRESSURE: [i32; DIMENSIONS] = [1, -1, -2, 0, 0];
+    const VOLUME: [i32; DIMENSIONS] = [0, 3, 0, 0, 0];
+    const TIME_UNIT: [i32; DIMENSIONS] = [0, 0, 1, 0, 0];
+    match name {
+        UnitName::Watt => unit(POWER, ONE),
+        UnitName::Joul => unit(ENERGY, ONE),
+        UnitName::Calorie => unit(ENERGY, Decimal::new(4_184, -3)),
+        UnitName::Kilogram => unit([1, 0, 0, 0, 0], ONE),
+        UnitName::Tonne => unit([1, 0, 0, 0, 0], Decimal::new(1, 3)),
+        UnitName::Meter => unit([0, 1, 0, 0, 0], ONE),
+        UnitName::Feet => unit([0, 1, 0, 0, 0], Decimal::new(3_048, -4)),
+        UnitName::Liter => unit(VOLUME, Decimal::new(1, -3)),
+        UnitName::AmericanGallon => unit(VOLUME, Decimal::new(3_785_411_784, -12)),
+        UnitName::Kelvin | UnitName::Celsius => unit([0, 0, 0, 1, 0], ONE),
+        UnitName::Fahrenheit => Some(Definition {
+            dimension: [0, 0, 0, 1, 0],
+            numerator: Decimal::new(5, 0),
+            denominator: Decimal::new(9, 0),
+        }),
+        UnitName::Bar => unit(PRESSURE, Decimal::new(1, 5)),
+        UnitName::Pascal => unit(PRESSURE, ONE),
+        UnitName::Second => unit(TIME_UNIT, ONE),
+        UnitName::Minute => unit(TIME_UNIT, Decimal::new(60, 0)),
+        UnitName::Hour => unit(TIME_UNIT, Decimal::new(3_600, 0)),
+        UnitName::Day => unit(TIME_UNIT, Decimal::new(86_400, 0)),
+        UnitName::Week => unit(TIME_UNIT, Decimal::new(604_800, 0)),
+        UnitName::Hertz => unit([0, 0, -1, 0, 0], ONE),
+        UnitName::Volt => unit([1, 2, -3, 0, -1], ONE),
+        UnitName::Ampere => unit([0, 0, 0, 0, 1], ONE),
+        _ => None,
+    }
+}
+
+/// Zero point of a temperature scale in kelvin.
+const fn temperature_zero(name: UnitName) -> Option<Decimal> {
+    match name {
+        UnitName::Kelvin => Some(Decimal::new(0, 0)),
+        UnitName::Celsius => Some(Decimal::new(27_315, -2)),
+        UnitName::Fahrenheit => Some(Decimal::new(45_967, -2)),
+        _ => None,
+    }
+}
+
+/// SI dimension of a unit list with the units lacking a fixed size.
+#[derive(PartialEq)]
+struct Dimension {
+    base: [i32; DIMENSIONS],
+    other: ArrayVec<Unit, 10>,
+}
+
+impl Dimension {
+    fn of(units: &[Unit]) -> Self {
+        let mut base = [0; DIMENSIONS];
+        let mut other = ArrayVec::<Unit, 10>::new();
+        for unit in units {
+            match definition(unit.name) {
+                Some(definition) => {
+                    for (total, exponent) in base.iter_mut().zip(definition.dimension) {
+                        *total += exponent * unit.exponent;
+                    }
+                }
+                None => push_unit(&mut other, *unit),
+            }
+        }
+        other.retain(|unit| unit.exponent != 0);
+        other.sort_unstable_by_key(|unit| (unit.name as u8, unit.exponent));
+        Self { base, other }
+    }
+}
+
+/// Adds a unit to a list, merging it with a unit of the same name.
+pub(crate) fn push_unit(units: &mut ArrayVec<Unit, 10>, unit: Unit) {
+    match units.iter_mut().find(|known| known.name == unit.name) {
+        Some(known) => known.exponent += unit.exponent,
+        None => {
+            let _ = units.try_push(unit);
+        }
+    }
+}
+
+/// Converts a value between unit lists of the same dimension.
+pub fn convert(
+    value: ScaledNumber,
+    from: &[Unit],
+    to: &[Unit],
+) -> Result<ScaledNumber, ConversionError> {
+    convert_units(value, from, to, true)
+}
+
+pub(crate) fn convert_units(
+    value: ScaledNumber,
+    from: &[Unit],
+    to: &[Unit],
+    absolute_temperature: bool,
+) -> Result<ScaledNumber, ConversionError> {
+    if Dimension::of(from) != Dimension::of(to) {
+        return Err(ConversionError::IncompatibleUnits);
+    }
+    if let ([source], [target]) = (from, to) {
+        if let (Some(source_zero), Some(target_zero), true, 1, 1) = (
+            temperature_zero(source.name),
+            temperature_zero(target.name),
+            absolute_temperature,
+            source.exponent,
+            target.exponent,
+        ) {
+            let kelvin = to_si(value.plus(source_zero), source, 1);
+            return Ok(to_si(kelvin, target, -1).plus(negated(target_zero)));
+        }
+    }
+    let value = from.iter().fold(value, |value, unit| to_si(value, unit, 1));
+    Ok(to.iter().fold(value, |value, unit| to_si(value, unit, -1)))
+}
+
+/// Multiplies the value by the SI size of the unit, or divides it for a
+/// negative direction.
+fn to_si(value: ScaledNumber, unit: &Unit, direction: i32) -> ScaledNumber {
+    let Some(definition) = definition(unit.name) else {
+        return value;
+    };
+    let exponent = unit.exponent * direction;
+    (0..exponent.unsigned_abs()).fold(value, |value, _| {
+        if exponent < 0 {
+            value
+                .times(definition.denominator)
+                .divided_by(definition.numerator)
+        } else {
+            value
+                .times(definition.numerator)
+                .divided_by(definition.denominator)
+        }
+    })
+}
+
+const fn negated(value: Decimal) -> Decimal {
+    Decimal::new(-value.significand, value.exponent)
+}
+
+impl UnitSystem {
+    /// Returns the units a unit list is reported in by this system.
+    #[must_use]
+    pub fn units(self, units: &[Unit]) -> ArrayVec<Unit, 10> {
+        let mut target = ArrayVec::<Unit, 10>::new();
+        for unit in units {
+            push_unit(&mut target, self.unit(*unit));
+        }
+        target.retain(|unit| unit.exponent != 0);
+        if self == Self::Si {
+            derive_si_units(&mut target);
+        }
+        target
+    }
+
+    fn unit(self, unit: Unit) -> Unit {
+        let with = |name, exponent| Unit { name, exponent };
+        match (self, unit.name) {
+            (Self::Si, UnitName::Minute | UnitName::Hour | UnitName::Day | UnitName::Week) => {
+                with(UnitName::Second, unit.exponent)
+            }
+            (Self::Si | Self::Metric, UnitName::AmericanGallon) => {
+                with(UnitName::Meter, unit.exponent * 3)
+            }
+            (Self::Si, UnitName::Liter) => with(UnitName::Meter, unit.exponent * 3),
+            (Self::Si | Self::Metric, UnitName::Feet) => with(UnitName::Meter, unit.exponent),
+            (Self::Si, UnitName::Tonne) => with(UnitName::Kilogram, unit.exponent),
+            (Self::Si, UnitName::Bar) => with(UnitName::Pascal, unit.exponent),
+            (Self::Si, UnitName::Calorie) => with(UnitName::Joul, unit.exponent),
+            (Self::Si, UnitName::Celsius | UnitName::Fahrenheit) => {
+                with(UnitName::Kelvin, unit.exponent)
+            }
+            (Self::Metric, UnitName::Fahrenheit) => with(UnitName::Celsius, unit.exponent),
+            (Self::Us, UnitName::Celsius | UnitName::Kelvin) => {
+                with(UnitName::Fahrenheit, unit.exponent)
+            }
+            (Self::Us, UnitName::Liter) => with(UnitName::AmericanGallon, unit.exponent),
+            (Self::Us, UnitName::Meter) if unit.exponent % 3 == 0 => {
+                with(UnitName::AmericanGallon, unit.exponent / 3)
+            }
+            (Self::Us, UnitName::Meter) => with(UnitName::Feet, unit.exponent),
+            _ => unit,
+        }
+    }
+}
+
+/// Reports W·s as J and J/s as W.
+fn derive_si_units(units: &mut ArrayVec<Unit, 10>) {
+    let exponent = |units: &ArrayVec<Unit, 10>, name| {
+        units
+            .iter()
+            .find(|unit| unit.name == name)
+            .map(|unit| unit.exponent)
+    };
+    let derived = match (
+        exponent(units, UnitName::Watt),
+        exponent(units, UnitName::Joul),
+        exponent(units, UnitName::Second),
+    ) {
+        (Some(1), None, Some(1)) => UnitName::Joul,
+        (None, Some(1), Some(-1)) => UnitName::Watt,
+        _ => return,
+    };
+    units.retain(|unit| {
+        !matches!(
+            unit.name,
+            UnitName::Watt | UnitName::Joul | UnitName::Second
+        )
+    });
+    let _ = units.try_insert(
+        0,
+        Unit {
+            name: derived,
+            exponent: 1,
+        },
+    );
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    fn units(list: &[(UnitName, i32)]) -> ArrayVec<Unit, 10> {
+        list.iter()
+            .map(|&(name, exponent)| Unit { name, exponent })
+            .collect()
+    }
+
+    fn exact(significand: i128, exponent: i16) -> ScaledNumber {
+        ScaledNumber::Exact(Decimal::new(significand, exponent))
+    }
+
+    #[test]
+    fn converts_compatible_units() {
+        let watt_hour = units(&[(UnitName::Watt, 1), (UnitName::Hour, 1)]);
+        let joule = units(&[(UnitName::Joul, 1)]);
+        assert_eq!(
+            convert(exact(12_345, 3), &watt_hour, &joule),
+            Ok(exact(44_442_000, 3))
+        );
+
+        let gallon = units(&[(UnitName::AmericanGallon, 1)]);
+        let cubic_meter = units(&[(UnitName::Meter, 3)]);
+        assert_eq!(
+            convert(exact(100, 0), &gallon, &cubic_meter),
+            Ok(exact(378_541_178_400, -12))
+        );
+
+        let fahrenheit = units(&[(UnitName::Fahrenheit, 1)]);
+        let celsius = units(&[(UnitName::Celsius, 1)]);
+        assert_eq!(
+            convert(exact(212, 0), &fahrenheit, &celsius)
+                .unwrap()
+                .to_f64(),
+            100.0
+        );
+        assert_eq!(
+            convert(exact(-40, 0), &celsius, &fahrenheit)
+                .unwrap()
+                .to_string(),
+            "-40"
+        );
+        assert_eq!(
+            convert_units(exact(10, 0), &celsius, &fahrenheit, false)
+                .unwrap()
+                .to_string(),
+            "18"
+        );
+
+        assert_eq!(
+            convert(exact(1, 0), &watt_hour, &cubic_meter),
+            Err(ConversionError::IncompatibleUnits)
+        );
+        let hca = units(&[(UnitName::HCAUnit, 1)]);
+        assert_eq!(convert(exact(7, 0), &hca, &hca), Ok(exact(7, 0)));
+    }
+
+    #[test]
+    fn converts_to_prefixed_units() {
+        use crate::scaled_value::ScaledValue;
+        use crate::value_information::ValueLabel;
+
+        let energy = ScaledValue {
+            value: exact(12_345, 3),
+            units: units(&[(UnitName::Watt, 1), (UnitName::Hour, 1)]),
+            labels: [ValueLabel::Energy].into_iter().collect(),
+        };
+        let megawatt_hour = energy.convert_to(&energy.units, 6).unwrap();
+        assert_eq!(megawatt_hour.to_string(), "12.345");
+        let gigajoule = energy
+            .convert_to(&units(&[(UnitName::Joul, 1)]), 9)
+            .unwrap();
+        assert_eq!(gigajoule.to_string(), "44.442");
+    }
+
+    #[test]
+    fn selects_units_of_a_system() {
+        let flow = units(&[(UnitName::Meter, 3), (UnitName::Hour, -1)]);
+        assert_eq!(
+            UnitSystem::Si.units(&flow),
+            units(&[(UnitName::Meter, 3), (UnitName::Second, -1)])
+        );
+        assert_eq!(UnitSystem::Metric.units(&flow), flow);
+        assert_eq!(
+            UnitSystem::Us.units(&flow),
+            units(&[(UnitName::AmericanGallon, 1), (UnitName::Hour, -1)])
+        );
+
+        let power = units(&[(UnitName::Joul, 1), (UnitName::Hour, -1)]);
+        assert_eq!(UnitSystem::Si.units(&power), units(&[(UnitName::Watt, 1)]));
+    }
+}
@@ -27,22 +27,83 @@ macro_rules! unit {
         }
     };
 }
+pub(crate) use unit;
+
+/// Position of the length and ASCII unit of a plaintext VIF (`0x7C`, `0xFC`)
+/// relative to its VIFEs.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PlaintextVifOrder {
+    /// The unit follows the last VIFE, as EN 13757-3 specifies.
+    AfterExtensions,
+    /// The unit directly follows the VIF, as some meters send it.
+    BeforeExtensions,
+    /// Uses the standard order unless it yields a unit of more than 9 or
+    /// non-printable characters and the other order does not.
+    Detect,
+}
+
+impl PlaintextVifOrder {
+    /// The order selected by the `plaintext-before-extension` feature.
+    pub const FEATURE_DEFAULT: Self = if cfg!(feature = "plaintext-before-extension") {
+        Self::BeforeExtensions
+    } else {
+        Self::AfterExtensions
+    };
+}
+
+impl Default for PlaintextVifOrder {
+    fn default() -> Self {
+        Self::FEATURE_DEFAULT
+    }
+}
 
 impl TryFrom<&[u8]> for ValueInformationBlock {
     type Error = DataInformationError;
 
     fn try_from(data: &[u8]) -> Result<Self, DataInformationError> {
+        Self::parse(data, PlaintextVifOrder::default())
+    }
+}
+
+impl ValueInformationBlock {
+    /// Parses a value information block whose plaintext unit, if any, is in
+    /// the given position.
+    pub fn parse(data: &[u8], order: PlaintextVifOrder) -> Result<Self, DataInformationError> {
+        match order {
+            PlaintextVifOrder::AfterExtensions => Self::parse_ordered(data, false),
+            PlaintextVifOrder::BeforeExtensions => Self::parse_ordered(data, true),
+            PlaintextVifOrder::Detect => {
+                let standard = Self::parse_ordered(data, false);
+                if standard.as_ref().is_ok_and(Self::has_printable_plaintext) {
+                    return standard;
+                }
+                Self::parse_ordered(data, true)
+                    .ok()
+                    .filter(Self::has_printable_plaintext)
+                    .map_or(standard, Ok)
+            }
+        }
+    }
+
+    fn has_printable_plaintext(&self) -> bool {
+        self.plaintext_vife.as_ref().is_none_or(|text| {
+            text.iter()
+                .all(|character| character.is_ascii_graphic() || *character == ' ')
+        })
+    }
+
+    fn parse_ordered(
+        data: &[u8],
+        plaintext_before_extension: bool,
+    ) -> Result<Self, DataInformationError> {
         let mut vife = ArrayVec::<ValueInformationFieldExtension, MAX_VIFE_RECORDS>::new();
         let vif =
             ValueInformationField::from(*data.first().ok_or(DataInformationError::DataTooShort)?);
         let mut plaintext_vife: Option<ArrayVec<char, 9>> = None;
 
-        #[cfg(not(feature = "plaintext-before-extension"))]
-        let standard_plaintex_vib = true;
-        #[cfg(feature = "plaintext-before-extension")]
-        let standard_plaintex_vib = false;
-
-        if !standard_plaintex_vib && vif.value_information_contains_ascii() {
+        if plaintext_before_extension && vif.value_information_contains_ascii() {
             plaintext_vife = Some(extract_plaintext_vife(
                 data.get(1..).ok_or(DataInformationError::DataTooShort)?,
             )?);
@@ -52,23 +113,21 @@ impl TryFrom<&[u8]> for ValueInformationBlock {
             // When the plaintext VIF precedes the extensions, the VIFE chain
             // starts after the ASCII length byte and string, not at offset 1.
             let mut offset = match &plaintext_vife {
-                Some(chars) if !standard_plaintex_vib => 1 + 1 + chars.len(),
-                _ => 1,
+                Some(chars) => 1 + 1 + chars.len(),
+                None => 1,
             };
             while offset < data.len() {
                 let vife_data = *data.get(offset).ok_or(DataInformationError::DataTooShort)?;
                 let current_vife = ValueInformationFieldExtension { data: vife_data };
                 let has_extension = current_vife.has_extension();
-                vife.push(current_vife);
+                vife.try_push(current_vife)
+                    .map_err(|_| DataInformationError::InvalidValueInformation)?;
                 offset += 1;
                 if !has_extension {
                     break;
                 }
-                if vife.len() > MAX_VIFE_RECORDS {
-                    return Err(DataInformationError::InvalidValueInformation);
-                }
             }
-            if standard_plaintex_vib && vif.value_information_contains_ascii() {
+            if !plaintext_before_extension && vif.value_information_contains_ascii() {
                 plaintext_vife = Some(extract_plaintext_vife(
                     data.get(offset..)
                         .ok_or(DataInformationError::DataTooShort)?,
@@ -91,7 +150,9 @@ fn extract_plaintext_vife(data: &[u8]) -> Result<ArrayVec<char, 9>, DataInformat
         .get(1..=ascii_length)
         .ok_or(DataInformationError::DataTooShort)?
     {
-        ascii.push(*item as char);
+        ascii
+            .try_push(*item as char)
+            .map_err(|_| DataInformationError::InvalidValueInformation)?;
     }
     Ok(ascii)
 }
@@ -155,15 +216,12 @@ pub struct ValueInformationFieldExtension {
 impl From<&ValueInformationField> for ValueInformationCoding {
     fn from(value_information: &ValueInformationField) -> Self {
         match value_information.data {
-            0x00..=0x7B | 0x80..=0xFA => Self::Primary,
+            0x00..=0x7B | 0x7D | 0x80..=0xFA => Self::Primary,
+            0xFB => Self::AlternateVIFExtension,
             0x7C | 0xFC => Self::PlainText,
             0xFD => Self::MainVIFExtension,
-            0xFB => Self::AlternateVIFExtension,
-            0x7E => Self::ManufacturerSpecific,
-            0xFE => Self::ManufacturerSpecific,
-            0x7F => Self::ManufacturerSpecific,
-            0xFF => Self::ManufacturerSpecific,
-            _ => unreachable!("Invalid value information: {:X}", value_information.data),
+            0x7E | 0xFE => Self::AnyVIF,
+            0x7F | 0xFF => Self::ManufacturerSpecific,
         }
     }
 }
@@ -188,6 +246,8 @@ pub enum ValueInformationCoding {
     PlainText,
     MainVIFExtension,
     AlternateVIFExtension,
+    /// Any VIF (`0x7E`), selects records of every quantity for readout.
+    AnyVIF,
     ManufacturerSpecific,
 }
 
@@ -215,7 +275,7 @@ impl TryFrom<&ValueInformationBlock> for ValueInformation {
         let mut units = ArrayVec::<Unit, 10>::new();
         let mut labels = ArrayVec::<ValueLabel, 10>::new();
         let mut decimal_scale_exponent: isize = 0;
-        let mut decimal_offset_exponent = 0;
+        let mut decimal_offset_exponent = None;
         let vife_slice = match &value_information_block.value_information_extension {
             Some(v) => v.as_slice(),
             None => &[],
@@ -250,23 +310,15 @@ impl TryFrom<&ValueInformationBlock> for ValueInformation {
                     }
                     0x20..=0x23 => {
                         labels.push(ValueLabel::OnTime);
-                        match value_information_block.value_information.data & 0x03 {
-                            0x00 => units.push(unit!(Second)),
-                            0x01 => units.push(unit!(Minute)),
-                            0x02 => units.push(unit!(Hour)),
-                            0x03 => units.push(unit!(Day)),
-                            _ => unreachable!(),
-                        }
+                        units.push(duration_unit(
+                            value_information_block.value_information.data,
+                        ));
                     }
                     0x24..=0x27 => {
                         labels.push(ValueLabel::OperatingTime);
-                        match value_information_block.value_information.data & 0x03 {
-                            0x00 => units.push(unit!(Second)),
-                            0x01 => units.push(unit!(Minute)),
-                            0x02 => units.push(unit!(Hour)),
-                            0x03 => units.push(unit!(Day)),
-                            _ => unreachable!(),
-                        }
+                        units.push(duration_unit(
+                            value_information_block.value_information.data,
+                        ));
                     }
                     0x28..=0x2F => {
                         units.push(unit!(Watt));
@@ -342,18 +394,27 @@ impl TryFrom<&ValueInformationBlock> for ValueInformation {
                     0x6C => labels.push(ValueLabel::Date),
                     0x6D => labels.push(ValueLabel::DateTime),
                     0x6E => labels.push(ValueLabel::DimensionlessHCA),
-                    0x70..=0x73 => labels.push(ValueLabel::AveragingDuration),
-                    0x74..=0x77 => labels.push(ValueLabel::ActualityDuration),
+                    // reserved for a third extension table
+                    0x6F => labels.push(ValueLabel::Reserved),
+                    0x70..=0x73 => {
+                        labels.push(ValueLabel::AveragingDuration);
+                        units.push(duration_unit(
+                            value_information_block.value_information.data,
+                        ));
+                    }
+                    0x74..=0x77 => {
+                        labels.push(ValueLabel::ActualityDuration);
+                        units.push(duration_unit(
+                            value_information_block.value_information.data,
+                        ));
+                    }
                     0x78 => labels.push(ValueLabel::FabricationNumber),
                     0x79 => labels.push(ValueLabel::EnhancedIdentification),
                     0x7A => labels.push(ValueLabel::Address),
-                    0x7B => {}
-
-                    _ => {
-                        return Err(DataInformationError::Unimplemented {
-                            feature: "Primary value information unit codes (partial)",
-                        })
-                    }
+                    // extension markers without a following VIFE carry no quantity
+                    0x7B | 0x7D => {}
+                    // 0x7C, 0x7E and 0x7F are not primary codes, see ValueInformationCoding
+                    _ => return Err(DataInformationError::InvalidValueInformation),
                 };
                 consume_orthhogonal_vife(
                     vife_slice,
@@ -361,7 +422,7 @@ impl TryFrom<&ValueInformationBlock> for ValueInformation {
                     &mut units,
                     &mut decimal_scale_exponent,
                     &mut decimal_offset_exponent,
-                );
+                )?;
             }
             ValueInformationCoding::MainVIFExtension => {
                 let first_vife_data = vife_slice
@@ -552,7 +613,7 @@ impl TryFrom<&ValueInformationBlock> for ValueInformation {
                     &mut units,
                     &mut decimal_scale_exponent,
                     &mut decimal_offset_exponent,
-                );
+                )?;
             }
             ValueInformationCoding::AlternateVIFExtension => {
                 use UnitName::*;
@@ -684,7 +745,7 @@ impl TryFrom<&ValueInformationBlock> for ValueInformation {
                     &mut units,
                     &mut decimal_scale_exponent,
                     &mut decimal_offset_exponent,
-                );
+                )?;
             }
             // we need to check if the next byte is equivalent to the length of the rest of the
             // the data. In this case it is very likely that, this is how the payload is built up.
@@ -696,7 +757,17 @@ impl TryFrom<&ValueInformationBlock> for ValueInformation {
                     &mut units,
                     &mut decimal_scale_exponent,
                     &mut decimal_offset_exponent,
-                );
+                )?;
+            }
+            ValueInformationCoding::AnyVIF => {
+                labels.push(ValueLabel::AnyVIF);
+                consume_orthhogonal_vife(
+                    vife_slice,
+                    &mut labels,
+                    &mut units,
+                    &mut decimal_scale_exponent,
+                    &mut decimal_offset_exponent,
+                )?;
             }
             ValueInformationCoding::ManufacturerSpecific => {
                 labels.push(ValueLabel::ManufacturerSpecific)
@@ -712,59 +783,74 @@ impl TryFrom<&ValueInformationBlock> for ValueInformation {
     }
 }
 
+/// Time unit selected by the `nn` bits of on time and duration VIFs.
+const fn duration_unit(data: u8) -> Unit {
+    match data & 0b11 {
+        0b00 => unit!(Second),
+        0b01 => unit!(Minute),
+        0b10 => unit!(Hour),
+        _ => unit!(Day),
+    }
+}
+
 fn consume_orthhogonal_vife(
     vife: &[ValueInformationFieldExtension],
     labels: &mut ArrayVec<ValueLabel, 10>,
     units: &mut ArrayVec<Unit, 10>,
     decimal_scale_exponent: &mut isize,
-    decimal_offset_exponent: &mut isize,
-) {
+    decimal_offset_exponent: &mut Option<isize>,
+) -> Result<(), DataInformationError> {
     let mut is_extension_of_combinable_orthogonal_vife = false;
     for v in vife {
-        if v.data == 0xFC {
+        if v.data & 0x7F == 0x7C {
             is_extension_of_combinable_orthogonal_vife = true;
             continue;
         }
         if is_extension_of_combinable_orthogonal_vife {
             is_extension_of_combinable_orthogonal_vife = false;
             match v.data & 0x7F {
-                0x00 => labels.push(ValueLabel::Reserved),
-                0x01 => labels.push(ValueLabel::AtPhaseL1),
-                0x02 => labels.push(ValueLabel::AtPhaseL2),
-                0x03 => labels.push(ValueLabel::AtPhaseL3),
-                0x04 => labels.push(ValueLabel::AtNeutral),
-                0x05 => labels.push(ValueLabel::BetweenPhasesL1L2),
-                0x06 => labels.push(ValueLabel::BetweenPhasesL2L3),
-                0x07 => labels.push(ValueLabel::BetweenPhasesL3L1),
-                0x08 => labels.push(ValueLabel::AtQuadrant1),
-                0x09 => labels.push(ValueLabel::AtQuadrant2),
-                0x0A => labels.push(ValueLabel::AtQuadrant3),
-                0x0B => labels.push(ValueLabel::AtQuadrant4),
-                0x0C => labels.push(ValueLabel::DeltaBetweenImportAndExport),
-                0x0D => labels.push(ValueLabel::AlternativeNonMetricUnits),
-                0x0E => labels.push(ValueLabel::SecondarySensorMeasurement),
-                0x0F => labels.push(ValueLabel::HigherResolutionRegister),
-                0x10 => labels.push(
+                0x00 => push_label(labels, ValueLabel::Reserved)?,
+                0x01 => push_label(labels, ValueLabel::AtPhaseL1)?,
+                0x02 => push_label(labels, ValueLabel::AtPhaseL2)?,
+                0x03 => push_label(labels, ValueLabel::AtPhaseL3)?,
+                0x04 => push_label(labels, ValueLabel::AtNeutral)?,
+                0x05 => push_label(labels, ValueLabel::BetweenPhasesL1L2)?,
+                0x06 => push_label(labels, ValueLabel::BetweenPhasesL2L3)?,
+                0x07 => push_label(labels, ValueLabel::BetweenPhasesL3L1)?,
+                0x08 => push_label(labels, ValueLabel::AtQuadrant1)?,
+                0x09 => push_label(labels, ValueLabel::AtQuadrant2)?,
+                0x0A => push_label(labels, ValueLabel::AtQuadrant3)?,
+                0x0B => push_label(labels, ValueLabel::AtQuadrant4)?,
+                0x0C => push_label(labels, ValueLabel::DeltaBetweenImportAndExport)?,
+                0x0D => push_label(labels, ValueLabel::AlternativeNonMetricUnits)?,
+                0x0E => push_label(labels, ValueLabel::SecondarySensorMeasurement)?,
+                0x0F => push_label(labels, ValueLabel::HigherResolutionRegister)?,
+                0x10 => push_label(
+                    labels,
                     ValueLabel::AccumulationOfAbsoluteValueBothPositiveAndNegativeContribution,
-                ),
-                0x11 => labels.push(ValueLabel::DataPresentedWithTypeC),
-                0x12 => labels.push(ValueLabel::DataPresentedWithTypeD),
-                0x13 => labels.push(ValueLabel::EndDate),
-                0x14 => labels.push(ValueLabel::DirectionFromCommunicationPartnerToMeter),
-                0x15 => labels.push(ValueLabel::DirectionFromMeterToCommunicationPartner),
-                _ => labels.push(ValueLabel::Reserved),
+                )?,
+                0x11 => push_label(labels, ValueLabel::DataPresentedWithTypeC)?,
+                0x12 => push_label(labels, ValueLabel::DataPresentedWithTypeD)?,
+                0x13 => push_label(labels, ValueLabel::EndDate)?,
+                0x14 => push_label(labels, ValueLabel::DirectionFromCommunicationPartnerToMeter)?,
+                0x15 => push_label(labels, ValueLabel::DirectionFromMeterToCommunicationPartner)?,
+                _ => push_label(labels, ValueLabel::Reserved)?,
             }
         } else {
             match v.data & 0x7F {
-                0x00..=0x0F => labels.push(ValueLabel::ReservedForObjectActions),
-                0x10..=0x11 => labels.push(ValueLabel::Reserved),
-                0x12 => labels.push(ValueLabel::Averaged),
-                0x13 => labels.push(ValueLabel::InverseCompactProfile),
-                0x14 => labels.push(ValueLabel::RelativeDeviation),
-                0x15..=0x1C => labels.push(ValueLabel::RecordErrorCodes),
-                0x1D => labels.push(ValueLabel::StandardConformDataContent),
-                0x1E => labels.push(ValueLabel::CompactProfileWithRegisterNumbers),
-                0x1F => labels.push(ValueLabel::CompactProfile),
+                0x00..=0x0F => push_label(labels, ValueLabel::ReservedForObjectActions)?,
+                0x10..=0x11 => push_label(labels, ValueLabel::Reserved)?,
+                0x12 => push_label(labels, ValueLabel::Averaged)?,
+                0x13 => push_label(labels, ValueLabel::InverseCompactProfile)?,
+                0x14 => push_label(labels, ValueLabel::RelativeDeviation)?,
+                0x15 => push_label(labels, ValueLabel::NoDataAvailable)?,
+                0x16 => push_label(labels, ValueLabel::DataOverflow)?,
+                0x17 => push_label(labels, ValueLabel::DataUnderflow)?,
+                0x18 => push_label(labels, ValueLabel::DataError)?,
+                0x1C => push_label(labels, ValueLabel::PrematureEndOfRecord)?,
+                0x1D => push_label(labels, ValueLabel::StandardConformDataContent)?,
+                0x1E => push_label(labels, ValueLabel::CompactProfileWithRegisterNumbers)?,
+                0x1F => push_label(labels, ValueLabel::CompactProfile)?,
                 0x20 => units.push(unit!(Second ^ -1)),
                 0x21 => units.push(unit!(Minute ^ -1)),
                 0x22 => units.push(unit!(Hour ^ -1)),
@@ -821,151 +907,175 @@ fn consume_orthhogonal_vife(
                     units.push(unit!(Second ^ 1));
                     units.push(unit!(Ampere ^ -1));
                 }
-                0x39 => labels.push(ValueLabel::StartDateOf),
-                0x3A => labels.push(ValueLabel::VifContainsUncorrectedUnitOrValue),
-                0x3B => labels.push(ValueLabel::AccumulationOnlyIfValueIsPositive),
-                0x3C => labels.push(ValueLabel::AccumulationOnlyIfValueIsNegative),
-                0x3D => labels.push(ValueLabel::NonMetricUnits),
-                0x3E => labels.push(ValueLabel::ValueAtBaseConditions),
-                0x3F => labels.push(ValueLabel::ObisDeclaration),
+                0x39 => push_label(labels, ValueLabel::StartDateOf)?,
+                0x3A => push_label(labels, ValueLabel::VifContainsUncorrectedUnitOrValue)?,
+                0x3B => push_label(labels, ValueLabel::AccumulationOnlyIfValueIsPositive)?,
+                0x3C => push_label(labels, ValueLabel::AccumulationOnlyIfValueIsNegative)?,
+                0x3D => push_label(labels, ValueLabel::NonMetricUnits)?,
+                0x3E => push_label(labels, ValueLabel::ValueAtBaseConditions)?,
+                0x3F => push_label(labels, ValueLabel::ObisDeclaration)?,
                 // E100 u000 where u = 0: Lower; u = 1: Upper
-                0x40 => labels.push(ValueLabel::LowerLimitValue),
-                0x48 => labels.push(ValueLabel::UpperLimitValue),
+                0x40 => push_label(labels, ValueLabel::LowerLimitValue)?,
+                0x48 => push_label(labels, ValueLabel::UpperLimitValue)?,
                 // E100 u001 where u = 0: Lower; u = 1: Upper
-                0x41 => labels.push(ValueLabel::NumberOfExceedsOfLowerLimitValue),
-                0x49 => labels.push(ValueLabel::NumberOfExceedsOfUpperLimitValue),
+                0x41 => push_label(labels, ValueLabel::NumberOfExceedsOfLowerLimitValue)?,
+                0x49 => push_label(labels, ValueLabel::NumberOfExceedsOfUpperLimitValue)?,
                 /* E100 uf1b where
                 b = 0: Begin; b = 1: End
                 f = 0: First; b = 1: Last
                 u = 0: Lower; u = 1: Upper
                 */
-                0x42 => labels.push(ValueLabel::DateOfBeginFirstLowerLimitExceed),
-                0x43 => labels.push(ValueLabel::DateOfEndFirstLowerLimitExceed),
-                0x46 => labels.push(ValueLabel::DateOfBeginLastLowerLimitExceed),
-                0x47 => labels.push(ValueLabel::DateOfEndLastLowerLimitExceed),
-                0x4A => labels.push(ValueLabel::DateOfBeginFirstUpperLimitExceed),
-                0x4B => labels.push(ValueLabel::DateOfEndFirstUpperLimitExceed),
-                0x4E => labels.push(ValueLabel::DateOfBeginLastUpperLimitExceed),
-                0x4F => labels.push(ValueLabel::DateOfEndLastUpperLimitExceed),
+                0x42 => push_label(labels, ValueLabel::DateOfBeginFirstLowerLimitExceed)?,
+                0x43 => push_label(labels, ValueLabel::DateOfEndFirstLowerLimitExceed)?,
+                0x46 => push_label(labels, ValueLabel::DateOfBeginLastLowerLimitExceed)?,
+                0x47 => push_label(labels, ValueLabel::DateOfEndLastLowerLimitExceed)?,
+                0x4A => push_label(labels, ValueLabel::DateOfBeginFirstUpperLimitExceed)?,
+                0x4B => push_label(labels, ValueLabel::DateOfEndFirstUpperLimitExceed)?,
+                0x4E => push_label(labels, ValueLabel::DateOfBeginLastUpperLimitExceed)?,
+                0x4F => push_label(labels, ValueLabel::DateOfEndLastUpperLimitExceed)?,
                 0x50 => {
-                    labels.push(ValueLabel::DurationOfFirstLowerLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfFirstLowerLimitExceed)?;
                     units.push(unit!(Second));
                 }
                 0x51 => {
-                    labels.push(ValueLabel::DurationOfFirstLowerLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfFirstLowerLimitExceed)?;
                     units.push(unit!(Minute));
                 }
                 0x52 => {
-                    labels.push(ValueLabel::DurationOfFirstLowerLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfFirstLowerLimitExceed)?;
                     units.push(unit!(Hour));
                 }
                 0x53 => {
-                    labels.push(ValueLabel::DurationOfFirstLowerLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfFirstLowerLimitExceed)?;
                     units.push(unit!(Day));
                 }
                 0x54 => {
-                    labels.push(ValueLabel::DurationOfLastLowerLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfLastLowerLimitExceed)?;
                     units.push(unit!(Second));
                 }
                 0x55 => {
-                    labels.push(ValueLabel::DurationOfLastLowerLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfLastLowerLimitExceed)?;
                     units.push(unit!(Minute));
                 }
                 0x56 => {
-                    labels.push(ValueLabel::DurationOfLastLowerLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfLastLowerLimitExceed)?;
                     units.push(unit!(Hour));
                 }
                 0x57 => {
-                    labels.push(ValueLabel::DurationOfLastLowerLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfLastLowerLimitExceed)?;
                     units.push(unit!(Day));
                 }
                 0x58 => {
-                    labels.push(ValueLabel::DurationOfFirstUpperLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfFirstUpperLimitExceed)?;
                     units.push(unit!(Second));
                 }
                 0x59 => {
-                    labels.push(ValueLabel::DurationOfFirstUpperLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfFirstUpperLimitExceed)?;
                     units.push(unit!(Minute));
                 }
                 0x5A => {
-                    labels.push(ValueLabel::DurationOfFirstUpperLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfFirstUpperLimitExceed)?;
                     units.push(unit!(Hour));
                 }
                 0x5B => {
-                    labels.push(ValueLabel::DurationOfFirstUpperLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfFirstUpperLimitExceed)?;
                     units.push(unit!(Day));
                 }
                 0x5C => {
-                    labels.push(ValueLabel::DurationOfLastUpperLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfLastUpperLimitExceed)?;
                     units.push(unit!(Second));
                 }
                 0x5D => {
-                    labels.push(ValueLabel::DurationOfLastUpperLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfLastUpperLimitExceed)?;
                     units.push(unit!(Minute));
                 }
                 0x5E => {
-                    labels.push(ValueLabel::DurationOfLastUpperLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfLastUpperLimitExceed)?;
                     units.push(unit!(Hour));
                 }
                 0x5F => {
-                    labels.push(ValueLabel::DurationOfLastUpperLimitExceed);
+                    push_label(labels, ValueLabel::DurationOfLastUpperLimitExceed)?;
                     units.push(unit!(Day));
                 }
                 0x60 => {
-                    labels.push(ValueLabel::DurationOfFirst);
+                    push_label(labels, ValueLabel::DurationOfFirst)?;
                     units.push(unit!(Second));
                 }
                 0x61 => {
-                    labels.push(ValueLabel::DurationOfFirst);
+                    push_label(labels, ValueLabel::DurationOfFirst)?;
                     units.push(unit!(Minute));
                 }
                 0x62 => {
-                    labels.push(ValueLabel::DurationOfFirst);
+                    push_label(labels, ValueLabel::DurationOfFirst)?;
                     units.push(unit!(Hour));
                 }
                 0x63 => {
-                    labels.push(ValueLabel::DurationOfFirst);
+                    push_label(labels, ValueLabel::DurationOfFirst)?;
                     units.push(unit!(Day));
                 }
                 0x64 => {
-                    labels.push(ValueLabel::DurationOfLast);
+                    push_label(labels, ValueLabel::DurationOfLast)?;
                     units.push(unit!(Second));
                 }
                 0x65 => {
-                    labels.push(ValueLabel::DurationOfLast);
+                    push_label(labels, ValueLabel::DurationOfLast)?;
                     units.push(unit!(Minute));
                 }
                 0x66 => {
-                    labels.push(ValueLabel::DurationOfLast);
+                    push_label(labels, ValueLabel::DurationOfLast)?;
                     units.push(unit!(Hour));
                 }
                 0x67 => {
-                    labels.push(ValueLabel::DurationOfLast);
+                    push_label(labels, ValueLabel::DurationOfLast)?;
                     units.push(unit!(Day));
                 }
-                0x68 => labels.push(ValueLabel::ValueDuringLowerValueExceed),
-                0x6C => labels.push(ValueLabel::ValueDuringUpperValueExceed),
-                0x69 => labels.push(ValueLabel::LeakageValues),
-                0x6D => labels.push(ValueLabel::OverflowValues),
-                0x6A => labels.push(ValueLabel::DateOfBeginFirst),
-                0x6B => labels.push(ValueLabel::DateOfBeginLast),
-                0x6E => labels.push(ValueLabel::DateOfEndLast),
-                0x6F => labels.push(ValueLabel::DateOfEndFirst),
+                0x68 => push_label(labels, ValueLabel::ValueDuringLowerValueExceed)?,
+                0x6C => push_label(labels, ValueLabel::ValueDuringUpperValueExceed)?,
+                0x69 => push_label(labels, ValueLabel::LeakageValues)?,
+                0x6D => push_label(labels, ValueLabel::OverflowValues)?,
+                0x6A => push_label(labels, ValueLabel::DateOfBeginFirst)?,
+                0x6B => push_label(labels, ValueLabel::DateOfBeginLast)?,
+                0x6E => push_label(labels, ValueLabel::DateOfEndLast)?,
+                0x6F => push_label(labels, ValueLabel::DateOfEndFirst)?,
                 0x70..=0x77 => {
-                    *decimal_scale_exponent += (v.data & 0b111) as isize - 6;
+                    let exponent = (v.data & 0b111) as i8 - 6;
+                    push_label(labels, ValueLabel::MultiplicativeCorrectionFactor(exponent))?;
+                    *decimal_scale_exponent += exponent as isize;
                 }
+                // 10^(nn - 3) in the unit of the VIF, the last one applies
                 0x78..=0x7B => {
-                    *decimal_offset_exponent += (v.data & 0b11) as isize - 3;
+                    let exponent = (v.data & 0b11) as i8 - 3;
+                    push_label(labels, ValueLabel::AdditiveCorrectionConstant(exponent))?;
+                    *decimal_offset_exponent = Some(exponent as isize);
                 }
                 0x7D => {
+                    push_label(labels, ValueLabel::MultiplicativeCorrectionFactor(3))?;
                     *decimal_scale_exponent += 3;
                 }
-                0x7E => labels.push(ValueLabel::FutureValue),
-                0x7F => labels.push(ValueLabel::NextVIFEAndDataOfThisBlockAreManufacturerSpecific),
-                _ => labels.push(ValueLabel::Reserved),
+                0x7E => push_label(labels, ValueLabel::FutureValue)?,
+                0x7F => {
+                    // the following VIFEs are not orthogonal
+                    push_label(
+                        labels,
+                        ValueLabel::NextVIFEAndDataOfThisBlockAreManufacturerSpecific,
+                    )?;
+                    return Ok(());
+                }
+                _ => push_label(labels, ValueLabel::Reserved)?,
             };
         }
     }
+    Ok(())
+}
+
+/// Appends a label, rejecting VIFE chains with more labels than fit.
+fn push_label(
+    labels: &mut ArrayVec<ValueLabel, 10>,
+    label: ValueLabel,
+) -> Result<(), DataInformationError> {
+    labels
+        .try_push(label)
+        .map_err(|_| DataInformationError::InvalidValueInformation)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -986,7 +1096,9 @@ impl From<u8> for ValueInformationField {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Clone)]
 pub struct ValueInformation {
-    pub decimal_offset_exponent: isize,
+    /// Exponent of the additive correction constant `10^(nn - 3)`, absent
+    /// rather than zero without one.
+    pub decimal_offset_exponent: Option<isize>,
     pub labels: ArrayVec<ValueLabel, 10>,
     pub decimal_scale_exponent: isize,
     pub units: ArrayVec<Unit, 10>,
@@ -1028,8 +1140,8 @@ impl defmt::Format for ValueInformation {
 #[cfg(feature = "std")]
 impl fmt::Display for ValueInformation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.decimal_offset_exponent != 0 {
-            write!(f, "+{})", self.decimal_offset_exponent)?;
+        if let Some(exponent) = self.decimal_offset_exponent {
+            write!(f, "+{})", exponent)?;
         } else {
             write!(f, ")")?;
         }
@@ -1058,10 +1170,12 @@ impl fmt::Display for ValueInformation {
     }
 }
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum ValueLabel {
+    /// Any VIF, used to select records for readout.
+    AnyVIF,
     Instantaneous,
     ReservedForObjectActions,
     Reserved,
@@ -1070,7 +1184,11 @@ pub enum ValueLabel {
     Parameter,
     InverseCompactProfile,
     RelativeDeviation,
-    RecordErrorCodes,
+    NoDataAvailable,
+    DataOverflow,
+    DataUnderflow,
+    DataError,
+    PrematureEndOfRecord,
     StandardConformDataContent,
     CompactProfileWithRegisterNumbers,
     CompactProfile,
@@ -1124,6 +1242,10 @@ pub enum ValueLabel {
     DateOfEndLast,
     DateOfEndFirst,
     ExtensionOfCombinableOrthogonalVIFE,
+    /// Multiplicative correction factor `10^n` applied to the scale.
+    MultiplicativeCorrectionFactor(i8),
+    /// Additive correction constant `10^n` in the unit of the VIF.
+    AdditiveCorrectionConstant(i8),
     FutureValue,
     NextVIFEAndDataOfThisBlockAreManufacturerSpecific,
     Credit,
@@ -1238,6 +1360,291 @@ pub enum ValueLabel {
     ExternalTemperature,
 }
 
+impl ValueLabel {
+    /// Returns whether the label comes from a combinable (orthogonal) VIFE and
+    /// modifies the quantity of the VIF instead of naming one.
+    #[must_use]
+    pub const fn is_modifier(&self) -> bool {
+        use ValueLabel::*;
+        matches!(
+            self,
+            ReservedForObjectActions
+                | Averaged
+                | InverseCompactProfile
+                | RelativeDeviation
+                | NoDataAvailable
+                | DataOverflow
+                | DataUnderflow
+                | DataError
+                | PrematureEndOfRecord
+                | StandardConformDataContent
+                | CompactProfileWithRegisterNumbers
+                | CompactProfile
+                | StartDateOf
+                | VifContainsUncorrectedUnitOrValue
+                | AccumulationOnlyIfValueIsPositive
+                | AccumulationOnlyIfValueIsNegative
+                | NonMetricUnits
+                | ValueAtBaseConditions
+                | ObisDeclaration
+                | UpperLimitValue
+                | LowerLimitValue
+                | NumberOfExceedsOfUpperLimitValue
+                | NumberOfExceedsOfLowerLimitValue
+                | DateOfBeginFirstLowerLimitExceed
+                | DateOfBeginFirstUpperLimitExceed
+                | DateOfBeginLastLowerLimitExceed
+                | DateOfBeginLastUpperLimitExceed
+                | DateOfEndLastLowerLimitExceed
+                | DateOfEndLastUpperLimitExceed
+                | DateOfEndFirstLowerLimitExceed
+                | DateOfEndFirstUpperLimitExceed
+                | DurationOfFirstLowerLimitExceed
+                | DurationOfFirstUpperLimitExceed
+                | DurationOfLastLowerLimitExceed
+                | DurationOfLastUpperLimitExceed
+                | DurationOfFirst
+                | DurationOfLast
+                | ValueDuringLowerValueExceed
+                | ValueDuringUpperValueExceed
+                | LeakageValues
+                | OverflowValues
+                | DateOfBeginLast
+                | DateOfBeginFirst
+                | DateOfEndLast
+                | DateOfEndFirst
+                | MultiplicativeCorrectionFactor(_)
+                | AdditiveCorrectionConstant(_)
+                | FutureValue
+                | NextVIFEAndDataOfThisBlockAreManufacturerSpecific
+                | AtPhaseL1
+                | AtPhaseL2
+                | AtPhaseL3
+                | AtNeutral
+                | BetweenPhasesL1L2
+                | BetweenPhasesL2L3
+                | BetweenPhasesL3L1
+                | AtQuadrant1
+                | AtQuadrant2
+                | AtQuadrant3
+                | AtQuadrant4
+                | DeltaBetweenImportAndExport
+                | AlternativeNonMetricUnits
+                | SecondarySensorMeasurement
+                | HigherResolutionRegister
+                | AccumulationOfAbsoluteValueBothPositiveAndNegativeContribution
+                | DataPresentedWithTypeC
+                | DataPresentedWithTypeD
+                | EndDate
+                | DirectionFromCommunicationPartnerToMeter
+                | DirectionFromMeterToCommunicationPartner
+        )
+    }
+
+    /// Returns the stable snake case identifier of the label, e.g. `energy`
+    /// or `at_phase_l1`, used in [`RegisterKey`](crate::register::RegisterKey)
+    /// identifiers. Parameters of correction labels are not part of it.
+    #[must_use]
+    pub const fn id(&self) -> &'static str {
+        use ValueLabel::*;
+        match self {
+            AnyVIF => "any_vif",
+            Instantaneous => "instantaneous",
+            ReservedForObjectActions => "reserved_for_object_actions",
+            Reserved => "reserved",
+            Averaged => "averaged",
+            Integral => "integral",
+            Parameter => "parameter",
+            InverseCompactProfile => "inverse_compact_profile",
+            RelativeDeviation => "relative_deviation",
+            NoDataAvailable => "no_data_available",
+            DataOverflow => "data_overflow",
+            DataUnderflow => "data_underflow",
+            DataError => "data_error",
+            PrematureEndOfRecord => "premature_end_of_record",
+            StandardConformDataContent => "standard_conform_data_content",
+            CompactProfileWithRegisterNumbers => "compact_profile_with_register_numbers",
+            CompactProfile => "compact_profile",
+            ActualityDuration => "actuality_duration",
+            AveragingDuration => "averaging_duration",
+            Date => "date",
+            Time => "time",
+            DateTime => "date_time",
+            DateTimeWithSeconds => "date_time_with_seconds",
+            FabricationNumber => "fabrication_number",
+            EnhancedIdentification => "enhanced_identification",
+            Address => "address",
+            PlainText => "plain_text",
+            RevolutionOrMeasurement => "revolution_or_measurement",
+            IncrementPerInputPulseOnChannelP => "increment_per_input_pulse_on_channel_p",
+            IncrementPerOutputPulseOnChannelP => "increment_per_output_pulse_on_channel_p",
+            HourMinuteSecond => "hour_minute_second",
+            DayMonthYear => "day_month_year",
+            StartDateOf => "start_date_of",
+            VifContainsUncorrectedUnitOrValue => "vif_contains_uncorrected_unit_or_value",
+            AccumulationOnlyIfValueIsPositive => "accumulation_only_if_value_is_positive",
+            AccumulationOnlyIfValueIsNegative => "accumulation_only_if_value_is_negative",
+            NonMetricUnits => "non_metric_units",
+            AlternativeNonMetricUnits => "alternative_non_metric_units",
+            ValueAtBaseConditions => "value_at_base_conditions",
+            ObisDeclaration => "obis_declaration",
+            UpperLimitValue => "upper_limit_value",
+            LowerLimitValue => "lower_limit_value",
+            NumberOfExceedsOfUpperLimitValue => "number_of_exceeds_of_upper_limit_value",
+            NumberOfExceedsOfLowerLimitValue => "number_of_exceeds_of_lower_limit_value",
+            DateOfBeginFirstLowerLimitExceed => "date_of_begin_first_lower_limit_exceed",
+            DateOfBeginFirstUpperLimitExceed => "date_of_begin_first_upper_limit_exceed",
+            DateOfBeginLastLowerLimitExceed => "date_of_begin_last_lower_limit_exceed",
+            DateOfBeginLastUpperLimitExceed => "date_of_begin_last_upper_limit_exceed",
+            DateOfEndLastLowerLimitExceed => "date_of_end_last_lower_limit_exceed",
+            DateOfEndLastUpperLimitExceed => "date_of_end_last_upper_limit_exceed",
+            DateOfEndFirstLowerLimitExceed => "date_of_end_first_lower_limit_exceed",
+            DateOfEndFirstUpperLimitExceed => "date_of_end_first_upper_limit_exceed",
+            DurationOfFirstLowerLimitExceed => "duration_of_first_lower_limit_exceed",
+            DurationOfFirstUpperLimitExceed => "duration_of_first_upper_limit_exceed",
+            DurationOfLastLowerLimitExceed => "duration_of_last_lower_limit_exceed",
+            DurationOfLastUpperLimitExceed => "duration_of_last_upper_limit_exceed",
+            DurationOfFirst => "duration_of_first",
+            DurationOfLast => "duration_of_last",
+            ValueDuringLowerValueExceed => "value_during_lower_value_exceed",
+            ValueDuringUpperValueExceed => "value_during_upper_value_exceed",
+            LeakageValues => "leakage_values",
+            OverflowValues => "overflow_values",
+            DateOfBeginLast => "date_of_begin_last",
+            DateOfBeginFirst => "date_of_begin_first",
+            DateOfEndLast => "date_of_end_last",
+            DateOfEndFirst => "date_of_end_first",
+            ExtensionOfCombinableOrthogonalVIFE => "extension_of_combinable_orthogonal_vife",
+            MultiplicativeCorrectionFactor(_) => "multiplicative_correction_factor",
+            AdditiveCorrectionConstant(_) => "additive_correction_constant",
+            FutureValue => "future_value",
+            NextVIFEAndDataOfThisBlockAreManufacturerSpecific => {
+                "next_vife_and_data_of_this_block_are_manufacturer_specific"
+            }
+            Credit => "credit",
+            Debit => "debit",
+            UniqueMessageIdentificationOrAccessNumber => {
+                "unique_message_identification_or_access_number"
+            }
+            DeviceType => "device_type",
+            Manufacturer => "manufacturer",
+            ParameterSetIdentification => "parameter_set_identification",
+            ModelOrVersion => "model_or_version",
+            HardwareVersion => "hardware_version",
+            MetrologyFirmwareVersion => "metrology_firmware_version",
+            OtherSoftwareVersion => "other_software_version",
+            CustomerLocation => "customer_location",
+            Customer => "customer",
+            AccessCodeUser => "access_code_user",
+            AccessCodeOperator => "access_code_operator",
+            AccessCodeSystemOperator => "access_code_system_operator",
+            AccessCodeDeveloper => "access_code_developer",
+            Password => "password",
+            ErrorFlags => "error_flags",
+            ErrorMask => "error_mask",
+            SecurityKey => "security_key",
+            DigitalInput => "digital_input",
+            DigitalOutput => "digital_output",
+            Binary => "binary",
+            BaudRate => "baud_rate",
+            ResponseDelayTime => "response_delay_time",
+            Retry => "retry",
+            RemoteControl => "remote_control",
+            FirstStorageForCycleStorage => "first_storage_for_cycle_storage",
+            LastStorageForCycleStorage => "last_storage_for_cycle_storage",
+            SizeOfStorageBlock => "size_of_storage_block",
+            DescriptionOfTariffAndSubunit => "description_of_tariff_and_subunit",
+            StorageInterval => "storage_interval",
+            Dimensionless => "dimensionless",
+            DimensionlessHCA => "dimensionless_hca",
+            DataContainerForWmbusProtocol => "data_container_for_wmbus_protocol",
+            PeriodOfNormalDataTransmission => "period_of_normal_data_transmission",
+            ResetCounter => "reset_counter",
+            CumulationCounter => "cumulation_counter",
+            ControlSignal => "control_signal",
+            DayOfWeek => "day_of_week",
+            WeekNumber => "week_number",
+            TimePointOfChangeOfTariff => "time_point_of_change_of_tariff",
+            StateOfParameterActivation => "state_of_parameter_activation",
+            SpecialSupplierInformation => "special_supplier_information",
+            DurationSinceLastCumulation => "duration_since_last_cumulation",
+            OperatingTimeBattery => "operating_time_battery",
+            DateAndTimeOfBatteryChange => "date_and_time_of_battery_change",
+            RFPowerLevel => "rf_power_level",
+            DaylightSavingBeginningEndingDeviation => "daylight_saving_beginning_ending_deviation",
+            ListeningWindowManagementData => "listening_window_management_data",
+            RemainingBatteryLifeTime => "remaining_battery_life_time",
+            NumberOfTimesTheMeterWasStopped => "number_of_times_the_meter_was_stopped",
+            DataContainerForManufacturerSpecificProtocol => {
+                "data_container_for_manufacturer_specific_protocol"
+            }
+            CurrentlySelectedApplication => "currently_selected_application",
+            Energy => "energy",
+            ReactiveEnergy => "reactive_energy",
+            ApparentEnergy => "apparent_energy",
+            CoefficientOfPerformance => "coefficient_of_performance",
+            ReactivePower => "reactive_power",
+            Frequency => "frequency",
+            ApparentPower => "apparent_power",
+            AtPhaseL1 => "at_phase_l1",
+            AtPhaseL2 => "at_phase_l2",
+            AtPhaseL3 => "at_phase_l3",
+            AtNeutral => "at_neutral",
+            BetweenPhasesL1L2 => "between_phases_l1_l2",
+            BetweenPhasesL2L3 => "between_phases_l2_l3",
+            BetweenPhasesL3L1 => "between_phases_l3_l1",
+            AtQuadrant1 => "at_quadrant_1",
+            AtQuadrant2 => "at_quadrant_2",
+            AtQuadrant3 => "at_quadrant_3",
+            AtQuadrant4 => "at_quadrant_4",
+            DeltaBetweenImportAndExport => "delta_between_import_and_export",
+            AccumulationOfAbsoluteValueBothPositiveAndNegativeContribution => {
+                "accumulation_of_absolute_value_both_positive_and_negative_contribution"
+            }
+            SecondarySensorMeasurement => "secondary_sensor_measurement",
+            HigherResolutionRegister => "higher_resolution_register",
+            DataPresentedWithTypeC => "data_presented_with_type_c",
+            DataPresentedWithTypeD => "data_presented_with_type_d",
+            EndDate => "end_date",
+            DirectionFromCommunicationPartnerToMeter => {
+                "direction_from_communication_partner_to_meter"
+            }
+            DirectionFromMeterToCommunicationPartner => {
+                "direction_from_meter_to_communication_partner"
+            }
+            RelativeHumidity => "relative_humidity",
+            MoistureLevel => "moisture_level",
+            PhaseUtoU => "phase_u_to_u",
+            PhaseUtoI => "phase_u_to_i",
+            PhaseItoU => "phase_i_to_u",
+            ColdWarmTemperatureLimit => "cold_warm_temperature_limit",
+            CumulativeMaximumOfActivePower => "cumulative_maximum_of_active_power",
+            ResultingRatingFactor => "resulting_rating_factor",
+            ThermalOutputRatingFactor => "thermal_output_rating_factor",
+            ThermalCouplingRatingFactorOverall => "thermal_coupling_rating_factor_overall",
+            ThermalCouplingRatingRoomSide => "thermal_coupling_rating_room_side",
+            ThermalCouplingRatingFactorHeatingSide => "thermal_coupling_rating_factor_heating_side",
+            LowTemperatureRatingFactor => "low_temperature_rating_factor",
+            DisplayOutputScalingFactor => "display_output_scaling_factor",
+            ManufacturerSpecific => "manufacturer_specific",
+            OnTime => "on_time",
+            OperatingTime => "operating_time",
+            Volume => "volume",
+            Mass => "mass",
+            Power => "power",
+            VolumeFlow => "volume_flow",
+            MassFlow => "mass_flow",
+            Pressure => "pressure",
+            Voltage => "voltage",
+            Current => "current",
+            FlowTemperature => "flow_temperature",
+            ReturnTemperature => "return_temperature",
+            TemperatureDifference => "temperature_difference",
+            ExternalTemperature => "external_temperature",
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl fmt::Display for Unit {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -1301,6 +1708,7 @@ pub enum UnitName {
     Celsius,
     Kelvin,
     Bar,
+    Pascal,
     HCA,
     Reserved,
     WithoutUnits,
@@ -1348,6 +1756,7 @@ impl fmt::Display for UnitName {
             UnitName::Celsius => write!(f, "°C"),
             UnitName::Kelvin => write!(f, "°K"),
             UnitName::Bar => write!(f, "Bar"),
+            UnitName::Pascal => write!(f, "Pa"),
             UnitName::HCA => write!(f, "HCA"),
             UnitName::Reserved => write!(f, "Reserved"),
             UnitName::WithoutUnits => write!(f, "-"),
@@ -1407,7 +1816,7 @@ mod tests {
         assert_eq!(
             ValueInformation::try_from(&result).unwrap(),
             ValueInformation {
-                decimal_offset_exponent: 0,
+                decimal_offset_exponent: None,
                 decimal_scale_exponent: -3,
                 units: {
                     let mut x = ArrayVec::<Unit, 10>::new();
@@ -1437,7 +1846,7 @@ mod tests {
         assert_eq!(
             ValueInformation::try_from(&result).unwrap(),
             ValueInformation {
-                decimal_offset_exponent: 0,
+                decimal_offset_exponent: None,
                 decimal_scale_exponent: -2,
                 units: {
                     let mut x = ArrayVec::<Unit, 10>::new();
@@ -1468,7 +1877,7 @@ mod tests {
         assert_eq!(
             ValueInformation::try_from(&result).unwrap(),
             ValueInformation {
-                decimal_offset_exponent: 0,
+                decimal_offset_exponent: None,
                 decimal_scale_exponent: -1,
                 units: {
                     let mut x = ArrayVec::<Unit, 10>::new();
@@ -1538,7 +1947,7 @@ mod tests {
                     x.push(ValueLabel::Averaged);
                     x
                 },
-                decimal_offset_exponent: 0,
+                decimal_offset_exponent: None,
                 decimal_scale_exponent: 0,
                 units: {
                     let mut x = ArrayVec::<Unit, 10>::new();
@@ -1568,7 +1977,7 @@ mod tests {
                     x.push(ValueLabel::Averaged);
                     x
                 },
-                decimal_offset_exponent: 0,
+                decimal_offset_exponent: None,
                 decimal_scale_exponent: 0,
                 units: {
                     let mut x = ArrayVec::<Unit, 10>::new();
@@ -1606,12 +2015,13 @@ mod tests {
         assert_eq!(
             ValueInformation::try_from(&result).unwrap(),
             ValueInformation {
-                decimal_offset_exponent: 0,
+                decimal_offset_exponent: None,
                 decimal_scale_exponent: -2,
                 units: { ArrayVec::<Unit, 10>::new() },
                 labels: {
                     let mut x = ArrayVec::<ValueLabel, 10>::new();
                     x.push(ValueLabel::PlainText);
+                    x.push(ValueLabel::MultiplicativeCorrectionFactor(-2));
                     x
                 }
             }
@@ -1628,6 +2038,32 @@ mod tests {
         // not aftter the ASCII plain text and its size
     }
 
+    #[test]
+    fn test_plain_text_vif_order_at_runtime() {
+        use crate::value_information::{PlaintextVifOrder, ValueInformationBlock};
+
+        let norm_conform = [0xFC, 0x74, 0x03, 0x48, 0x52, 0x25];
+        let before_extension = [0xFC, 0x03, 0x48, 0x52, 0x25, 0x74];
+        for (data, order) in [
+            (norm_conform, PlaintextVifOrder::AfterExtensions),
+            (before_extension, PlaintextVifOrder::BeforeExtensions),
+            (norm_conform, PlaintextVifOrder::Detect),
+            (before_extension, PlaintextVifOrder::Detect),
+        ] {
+            let result = ValueInformationBlock::parse(data.as_slice(), order).unwrap();
+            assert_eq!(result.get_size(), 6);
+            assert_eq!(result.plaintext_vife.unwrap().as_slice(), ['H', 'R', '%']);
+            assert_eq!(result.value_information_extension.unwrap()[0].data, 0x74);
+        }
+        // The length 0x48 of the standard order exceeds the 9 characters a
+        // plaintext unit can have.
+        assert!(ValueInformationBlock::parse(
+            before_extension.as_slice(),
+            PlaintextVifOrder::AfterExtensions
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_short_vif_with_vife() {
         use crate::value_information::ValueInformationBlock;
@@ -1721,6 +2157,107 @@ mod tests {
         assert_eq!(vi.units[0].name, UnitName::Day);
     }
 
+    #[test]
+    fn test_primary_vif_table_is_complete() {
+        use crate::value_information::{
+            UnitName, ValueInformation, ValueInformationBlock, ValueLabel,
+        };
+
+        let parse = |data: &[u8]| {
+            ValueInformation::try_from(&ValueInformationBlock::try_from(data).unwrap())
+        };
+        for vif in (0x00..=0x7B).chain([0x7D]) {
+            assert!(parse(&[vif]).is_ok(), "VIF {vif:#04X}");
+        }
+
+        let vi = parse(&[0x6F]).unwrap();
+        assert_eq!(vi.labels.as_slice(), [ValueLabel::Reserved]);
+
+        // VIF 0x72 = averaging duration, nn=10 => hours
+        let vi = parse(&[0x72]).unwrap();
+        assert!(vi.labels.contains(&ValueLabel::AveragingDuration));
+        assert_eq!(vi.units[0].name, UnitName::Hour);
+
+        // VIF 0x74 = actuality duration, nn=00 => seconds
+        let vi = parse(&[0x74]).unwrap();
+        assert!(vi.labels.contains(&ValueLabel::ActualityDuration));
+        assert_eq!(vi.units[0].name, UnitName::Second);
+    }
+
+    #[test]
+    fn test_any_vif_for_readout_selection() {
+        use crate::value_information::{ValueInformation, ValueInformationBlock, ValueLabel};
+
+        let vi = ValueInformation::try_from(
+            &ValueInformationBlock::try_from([0x7E].as_slice()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(vi.labels.as_slice(), [ValueLabel::AnyVIF]);
+        assert!(vi.units.is_empty());
+
+        // any VIF followed by the orthogonal VIFE "per second"
+        let vi = ValueInformation::try_from(
+            &ValueInformationBlock::try_from([0xFE, 0x20].as_slice()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(vi.labels[0], ValueLabel::AnyVIF);
+        assert_eq!(vi.units.len(), 1);
+    }
+
+    #[test]
+    fn test_orthogonal_vife_correction_factors_and_record_errors() {
+        use crate::value_information::{ValueInformation, ValueInformationBlock, ValueLabel};
+
+        let parse = |data: &[u8]| {
+            ValueInformation::try_from(&ValueInformationBlock::try_from(data).unwrap()).unwrap()
+        };
+
+        // energy in Wh times 10^-1 with an additive correction of 10^0 Wh
+        let vi = parse(&[0x83, 0xF5, 0x7B]);
+        assert_eq!(vi.decimal_scale_exponent, -1);
+        assert_eq!(vi.decimal_offset_exponent, Some(0));
+        assert_eq!(
+            vi.labels.as_slice(),
+            [
+                ValueLabel::Energy,
+                ValueLabel::MultiplicativeCorrectionFactor(-1),
+                ValueLabel::AdditiveCorrectionConstant(0),
+            ]
+        );
+
+        // volume in 10^-3 m³ times 10^3
+        let vi = parse(&[0x93, 0x7D]);
+        assert_eq!(vi.decimal_scale_exponent, 0);
+        assert_eq!(vi.decimal_offset_exponent, None);
+
+        // VIFEs after the manufacturer specific VIFE are not orthogonal
+        let vi = parse(&[0x93, 0xFF, 0x75]);
+        assert_eq!(vi.decimal_scale_exponent, -3);
+        assert_eq!(
+            vi.labels.as_slice(),
+            [
+                ValueLabel::Volume,
+                ValueLabel::NextVIFEAndDataOfThisBlockAreManufacturerSpecific,
+            ]
+        );
+
+        assert!(parse(&[0x93, 0x18]).labels.contains(&ValueLabel::DataError));
+        assert!(parse(&[0x93, 0x1C])
+            .labels
+            .contains(&ValueLabel::PrematureEndOfRecord));
+        assert!(parse(&[0x93, 0x19]).labels.contains(&ValueLabel::Reserved));
+
+        // more correction factors than labels fit in are rejected
+        let mut data = vec![0x04, 0x93];
+        data.extend([0xF6; 9]);
+        data.extend([0x76, 0x01, 0x00, 0x00, 0x00]);
+        assert!(crate::data_record::DataRecord::try_from(data.as_slice()).is_err());
+
+        assert!(ValueLabel::AtPhaseL1.is_modifier());
+        assert!(ValueLabel::MultiplicativeCorrectionFactor(-1).is_modifier());
+        assert!(!ValueLabel::Volume.is_modifier());
+    }
+
     #[test]
     fn test_fb_cumulative_maximum_of_active_power() {
         use crate::value_information::{
@@ -2006,4 +2543,22 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_orthogonal_vife_labels_exceeding_capacity() {
+        use crate::value_information::{
+            DataInformationError, ValueInformation, ValueInformationBlock,
+        };
+
+        // volume followed by ten multiplicative correction factors 10^3: the
+        // VIFE chain fits, its labels together with the volume label do not
+        let data = [
+            0x93, 0xFD, 0xFD, 0xFD, 0xFD, 0xFD, 0xFD, 0xFD, 0xFD, 0xFD, 0x7D,
+        ];
+        let vib = ValueInformationBlock::try_from(data.as_slice()).unwrap();
+        assert_eq!(
+            ValueInformation::try_from(&vib),
+            Err(DataInformationError::InvalidValueInformation)
+        );
+    }
 }
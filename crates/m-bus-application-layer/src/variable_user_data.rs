@@ -85,14 +85,14 @@ mod tests {
             let code = get_data_field_coding(record);
             assert_eq!(code, DataFieldCoding::VariableLength);
             let value = record.data.value.clone().unwrap();
-            assert_eq!(value, DataType::Number(12.0))
+            assert_eq!(value, DataType::Integer(12))
         }
         {
             let record = records.get(1).unwrap();
             let code = get_data_field_coding(record);
             assert_eq!(code, DataFieldCoding::VariableLength);
             let value = record.data.value.clone().unwrap();
-            assert_eq!(value, DataType::Number(-563412.0))
+            assert_eq!(value, DataType::Integer(-563412))
         }
         {
             let record = records.get(2).unwrap();
@@ -106,7 +106,7 @@ mod tests {
             let code = get_data_field_coding(record);
             assert_eq!(code, DataFieldCoding::VariableLength);
             let value = record.data.value.clone().unwrap();
-            assert_eq!(value, DataType::Number(-1.0))
+            assert_eq!(value, DataType::Integer(-1))
         }
         {
             let record = records.get(4).unwrap();
@@ -150,7 +150,7 @@ mod tests {
             let code = get_data_field_coding(record);
             assert_eq!(code, DataFieldCoding::VariableLength);
             let value = record.data.value.clone().unwrap();
-            assert_eq!(value, DataType::LossyNumber(-1.0))
+            assert_eq!(value, DataType::LargeInteger(-1))
         }
         {
             let record = records.get(1).unwrap();
@@ -164,7 +164,7 @@ mod tests {
             let code = get_data_field_coding(record);
             assert_eq!(code, DataFieldCoding::VariableLength);
             let value = record.data.value.clone().unwrap();
-            assert_eq!(value, DataType::LossyNumber(-1.0))
+            assert_eq!(value, DataType::LargeInteger(-1))
         }
         {
             let record = records.get(3).unwrap();
@@ -256,6 +256,30 @@ mod tests {
         let _data = DataRecords::from(data.as_slice());
     }
 
+    #[test]
+    fn test_parse_plaintext_vif_in_either_order() {
+        use crate::data_information::DataType;
+        use crate::value_information::PlaintextVifOrder;
+        use crate::DataRecords;
+        let data = &[
+            0x02, 0xFC, 0x03, 0x48, 0x52, 0x25, 0x74, 0x44, 0x0D, // unit before the VIFE
+            0x02, 0xFC, 0x74, 0x03, 0x48, 0x52, 0x25, 0x44, 0x0D, // unit after the VIFE
+            0x02, 0x65, 0xB4, 0x09,
+        ];
+        let records: Vec<_> = DataRecords::new(data, None)
+            .with_plaintext_vif_order(PlaintextVifOrder::Detect)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 3);
+        for record in &records[..2] {
+            assert_eq!(record.data.value, Some(DataType::Integer(3396)));
+            assert_eq!(
+                record.value_information().unwrap().decimal_scale_exponent,
+                -2
+            );
+        }
+    }
+
     const fn _test_parse_variable_data2() {
         /* Data block 2: unit 0, storage No 5, no tariff, maximum volume flow, 113 l/h (4 digit BCD) */
         let _data = &[0xDA, 0x02, 0x3B, 0x13, 0x01];
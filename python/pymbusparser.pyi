@@ -13,6 +13,7 @@ OutputFormat = Literal[
     "annotated",
     "annotated-text",
     "hexview",
+    "history",
 ]
 
 class MbusParserError(ValueError): ...
@@ -25,6 +26,7 @@ def parse(
     *,
     key: Optional[KeyInput] = None,
     include_enrichment: bool = True,
+    utc_offset: Optional[int] = None,
 ) -> Dict[str, Any]: ...
 def parse_records(data: DataInput) -> List[Any]: ...
 def render(
@@ -34,6 +36,7 @@ def render(
     key: Optional[KeyInput] = None,
     width: Optional[int] = None,
     include_enrichment: bool = True,
+    utc_offset: Optional[int] = None,
 ) -> str: ...
 def parse_application_layer(data_record: str) -> str: ...
 def m_bus_parse(data: str, format: OutputFormat, key: Optional[str] = None) -> str: ...
@@ -54,6 +54,16 @@ fn extract_key(key: Option<&Bound<'_, PyAny>>) -> PyResult<Option<[u8; 16]>> {
     })
 }
 
+fn check_utc_offset(utc_offset: Option<i16>) -> PyResult<Option<i16>> {
+    match utc_offset {
+        Some(minutes) if !(-14 * 60..=14 * 60).contains(&minutes) => Err(binding_error(
+            "option.invalid",
+            format!("utc_offset must be within 14 hours of UTC, received {minutes} minutes"),
+        )),
+        utc_offset => Ok(utc_offset),
+    }
+}
+
 fn json_to_python(py: Python<'_>, json: &str) -> PyResult<Py<PyAny>> {
     PyModule::import(py, "json")?
         .call_method1("loads", (json,))
@@ -61,7 +71,7 @@ fn json_to_python(py: Python<'_>, json: &str) -> PyResult<Py<PyAny>> {
 }
 
 fn records_json(data: &[u8]) -> PyResult<String> {
-    let records = decode_data_records(data).map_err(parser_error)?;
+    let records = decode_data_records(data, &DecodeOptions::default()).map_err(parser_error)?;
 
     serde_json::to_string(&records).map_err(|error| {
         binding_error(
@@ -75,21 +85,23 @@ fn records_json(data: &[u8]) -> PyResult<String> {
 ///
 /// ``data`` may be strict hexadecimal text or a bytes-like object. Pass a
 /// 16-byte AES key as bytes or hexadecimal text for supported encrypted frames.
+/// ``utc_offset`` renders timestamps with an offset to UTC in minutes.
 #[pyfunction]
-#[pyo3(signature = (data, *, key=None, include_enrichment=true))]
+#[pyo3(signature = (data, *, key=None, include_enrichment=true, utc_offset=None))]
 fn parse(
     py: Python<'_>,
     data: &Bound<'_, PyAny>,
     key: Option<&Bound<'_, PyAny>>,
     include_enrichment: bool,
+    utc_offset: Option<i16>,
 ) -> PyResult<Py<PyAny>> {
     let data = extract_bytes(data, "data")?;
     let decoded = decode_bytes(
         &data,
-        &DecodeOptions {
-            key: extract_key(key)?,
-            include_enrichment,
-        },
+        &DecodeOptions::default()
+            .with_key(extract_key(key)?)
+            .with_enrichment(include_enrichment)
+            .with_utc_offset(check_utc_offset(utc_offset)?),
     )
     .map_err(parser_error)?;
     let json = serde_json::to_string(&decoded)
@@ -106,13 +118,22 @@ fn parse_records(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>>
 
 /// Render a complete M-Bus frame in one of the canonical text formats.
 #[pyfunction]
-#[pyo3(signature = (data, format="json", *, key=None, width=None, include_enrichment=true))]
+#[pyo3(signature = (
+    data,
+    format="json",
+    *,
+    key=None,
+    width=None,
+    include_enrichment=true,
+    utc_offset=None
+))]
 fn render(
     data: &Bound<'_, PyAny>,
     format: &str,
     key: Option<&Bound<'_, PyAny>>,
     width: Option<usize>,
     include_enrichment: bool,
+    utc_offset: Option<i16>,
 ) -> PyResult<String> {
     let data = extract_bytes(data, "data")?;
     let format = OutputFormat::from_str(format).map_err(parser_error)?;
@@ -120,10 +141,10 @@ fn render(
         &data,
         format,
         &RenderOptions {
-            decode: DecodeOptions {
-                key: extract_key(key)?,
-                include_enrichment,
-            },
+            decode: DecodeOptions::default()
+                .with_key(extract_key(key)?)
+                .with_enrichment(include_enrichment)
+                .with_utc_offset(check_utc_offset(utc_offset)?),
             table_width: width,
         },
     )
@@ -160,10 +181,7 @@ fn m_bus_parse(data: &str, format: &str, key: Option<&str>) -> PyResult<String>
         data,
         format,
         &RenderOptions {
-            decode: DecodeOptions {
-                key,
-                include_enrichment: true,
-            },
+            decode: DecodeOptions::default().with_key(key),
             ..RenderOptions::default()
         },
     )
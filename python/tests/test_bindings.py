@@ -93,6 +93,11 @@ class BindingsTests(unittest.TestCase):
         parsed = pymbusparser.parse(WIRED_FRAME, include_enrichment=False)
         self.assertNotIn("enrichment", parsed)
 
+    def test_utc_offset_option(self):
+        self.assertIsInstance(pymbusparser.parse(WIRED_FRAME, utc_offset=60), dict)
+        with self.assertRaises(pymbusparser.MbusParserError):
+            pymbusparser.render(WIRED_FRAME, utc_offset=15 * 60)
+
     def test_decryption_is_compiled_into_the_wheel(self):
         rendered = pymbusparser.render(
             ENCRYPTED_FRAME, "hexview", key=DECRYPTION_KEY
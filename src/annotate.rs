@@ -6,6 +6,7 @@
 //! related fields (e.g., all bytes of a data record).
 
 use crate::user_data::data_record::DataRecord;
+use crate::user_data::value_information::PlaintextVifOrder;
 use crate::MbusError;
 use std::borrow::Cow;
 use std::fmt;
@@ -129,21 +130,34 @@ pub struct ByteSegment {
 ///
 /// Returns `MbusError` if the frame cannot be parsed as either wired or wireless.
 pub fn annotate_frame(data: &[u8]) -> Result<Vec<ByteSegment>, MbusError> {
+    annotate_frame_with_plaintext_vif_order(data, PlaintextVifOrder::default())
+}
+
+/// Annotate every byte in a raw M-Bus frame like [`annotate_frame`], reading
+/// plaintext VIFs of the data records in the given order.
+///
+/// # Errors
+///
+/// Returns `MbusError` if the frame cannot be parsed as either wired or wireless.
+pub fn annotate_frame_with_plaintext_vif_order(
+    data: &[u8],
+    plaintext_vif_order: PlaintextVifOrder,
+) -> Result<Vec<ByteSegment>, MbusError> {
     // Try wired first
-    if let Ok(segments) = annotate_wired(data) {
+    if let Ok(segments) = annotate_wired(data, plaintext_vif_order) {
         return Ok(segments);
     }
 
     // Try wireless with Format A CRC stripping
     let mut crc_buf = [0u8; 512];
     if let Some(stripped) = wireless_mbus_link_layer::strip_format_a_crcs(data, &mut crc_buf) {
-        if let Ok(segments) = annotate_wireless_format_a(data, stripped) {
+        if let Ok(segments) = annotate_wireless_format_a(data, stripped, plaintext_vif_order) {
             return Ok(segments);
         }
     }
 
     // Try wireless without CRC stripping (already stripped / Format B)
-    if let Ok(segments) = annotate_wireless_inner(data) {
+    if let Ok(segments) = annotate_wireless_inner(data, plaintext_vif_order) {
         return Ok(segments);
     }
 
@@ -154,7 +168,10 @@ pub fn annotate_frame(data: &[u8]) -> Result<Vec<ByteSegment>, MbusError> {
 
 // ── Wired frame annotation ──────────────────────────────────────────────────
 
-fn annotate_wired(data: &[u8]) -> Result<Vec<ByteSegment>, MbusError> {
+fn annotate_wired(
+    data: &[u8],
+    plaintext_vif_order: PlaintextVifOrder,
+) -> Result<Vec<ByteSegment>, MbusError> {
     let frame = WiredFrame::try_from(data)?;
     let mut segments = Vec::new();
 
@@ -231,6 +248,7 @@ fn annotate_wired(data: &[u8]) -> Result<Vec<ByteSegment>, MbusError> {
                 &address,
                 user_data_slice,
                 is_control,
+                plaintext_vif_order,
             );
         }
         WiredFrame::ControlFrame {
@@ -246,6 +264,7 @@ fn annotate_wired(data: &[u8]) -> Result<Vec<ByteSegment>, MbusError> {
                 &address,
                 user_data_slice,
                 is_control,
+                plaintext_vif_order,
             );
         }
         _ => {
@@ -270,6 +289,7 @@ fn annotate_long_or_control_frame(
     address: &wired_mbus_link_layer::Address,
     user_data_slice: &[u8],
     is_control: bool,
+    plaintext_vif_order: PlaintextVifOrder,
 ) {
     let l = data.get(1).copied().unwrap_or(0) as usize;
 
@@ -324,7 +344,13 @@ fn annotate_long_or_control_frame(
     let user_data_end = user_data_start + user_data_slice.len();
 
     if !is_control && !user_data_slice.is_empty() {
-        annotate_application_layer(segments, data, user_data_start, user_data_slice);
+        annotate_application_layer(
+            segments,
+            data,
+            user_data_start,
+            user_data_slice,
+            plaintext_vif_order,
+        );
     } else if !user_data_slice.is_empty() {
         segments.push(ByteSegment {
             start: user_data_start,
@@ -372,6 +398,7 @@ fn annotate_application_layer(
     frame_data: &[u8],
     base: usize,
     app_data: &[u8],
+    plaintext_vif_order: PlaintextVifOrder,
 ) {
     let Some(&ci) = app_data.first() else {
         return;
@@ -409,15 +436,14 @@ fn annotate_application_layer(
                     });
                 }
             } else {
-                annotate_data_records(segments, records_start, records_data);
+                annotate_data_records(segments, records_start, records_data, plaintext_vif_order);
             }
         }
 
-        // Short TPL header (CI=0x7A or encrypted CI=0xA0..=0xAF)
-        0x7A | 0xA0..=0xAF => {
-            let has_enc_config = ci == 0xA0;
-            let skip_count: usize = if has_enc_config { 2 } else { 1 };
-            let data_block_offset: usize = if has_enc_config { 6 } else { 5 };
+        // Short TPL header (CI=0x7A)
+        0x7A => {
+            let skip_count: usize = 1;
+            let data_block_offset: usize = 5;
 
             if app_data.len() < data_block_offset {
                 segments.push(ByteSegment {
@@ -443,22 +469,6 @@ fn annotate_application_layer(
 
             let mut offset = base + 1;
 
-            // Extra encryption config byte for CI=0xA0
-            if has_enc_config {
-                segments.push(ByteSegment {
-                    start: offset,
-                    end: offset + 1,
-                    kind: SegmentKind::EncryptionConfigByte,
-                    detail: Cow::Owned(format!(
-                        "Encryption config: 0x{:02X}",
-                        app_data.get(1).copied().unwrap_or(0)
-                    )),
-                    group: None,
-                    layer: Layer::AppHeader,
-                });
-                offset += 1;
-            }
-
             // Access Number
             segments.push(ByteSegment {
                 start: offset,
@@ -517,7 +527,32 @@ fn annotate_application_layer(
                     });
                 }
             } else {
-                annotate_data_records(segments, offset, records_data);
+                annotate_data_records(segments, offset, records_data, plaintext_vif_order);
+            }
+        }
+
+        // Proprietary application layer (CI=0xA0..=0xAF)
+        0xA0..=0xAF => {
+            segments.push(ByteSegment {
+                start: base,
+                end: base + 1,
+                kind: SegmentKind::CiField,
+                detail: Cow::Owned(format!("CI: 0x{:02X} (Manufacturer specific)", ci)),
+                group: None,
+                layer: Layer::AppHeader,
+            });
+            if app_data.len() > 1 {
+                segments.push(ByteSegment {
+                    start: base + 1,
+                    end: base + app_data.len(),
+                    kind: SegmentKind::ManufacturerSpecific,
+                    detail: Cow::Owned(format!(
+                        "Manufacturer specific data ({} bytes)",
+                        app_data.len() - 1
+                    )),
+                    group: None,
+                    layer: Layer::RecordField,
+                });
             }
         }
 
@@ -534,7 +569,7 @@ fn annotate_application_layer(
 
             let records_data = &app_data[1..];
             if !records_data.is_empty() {
-                annotate_data_records(segments, base + 1, records_data);
+                annotate_data_records(segments, base + 1, records_data, plaintext_vif_order);
             }
         }
 
@@ -578,7 +613,13 @@ fn annotate_application_layer(
             let inner_data = &app_data[total_header..];
             let inner_base = base + total_header;
             if !inner_data.is_empty() {
-                annotate_application_layer(segments, frame_data, inner_base, inner_data);
+                annotate_application_layer(
+                    segments,
+                    frame_data,
+                    inner_base,
+                    inner_data,
+                    plaintext_vif_order,
+                );
             }
         }
 
@@ -620,7 +661,7 @@ fn annotate_application_layer(
             let remaining = &app_data[total_header..];
             let remaining_base = base + total_header;
             if !remaining.is_empty() {
-                annotate_data_records(segments, remaining_base, remaining);
+                annotate_data_records(segments, remaining_base, remaining, plaintext_vif_order);
             }
         }
 
@@ -661,7 +702,7 @@ fn annotate_application_layer(
             let remaining = &app_data[total_header..];
             let remaining_base = base + total_header;
             if !remaining.is_empty() {
-                annotate_data_records(segments, remaining_base, remaining);
+                annotate_data_records(segments, remaining_base, remaining, plaintext_vif_order);
             }
         }
 
@@ -789,7 +830,12 @@ fn annotate_long_tpl_header(
 
 // ── Data record annotation ──────────────────────────────────────────────────
 
-pub(crate) fn annotate_data_records(segments: &mut Vec<ByteSegment>, base: usize, data: &[u8]) {
+pub(crate) fn annotate_data_records(
+    segments: &mut Vec<ByteSegment>,
+    base: usize,
+    data: &[u8],
+    plaintext_vif_order: PlaintextVifOrder,
+) {
     let mut offset = 0usize;
     let mut record_index = 0usize;
 
@@ -856,7 +902,7 @@ pub(crate) fn annotate_data_records(segments: &mut Vec<ByteSegment>, base: usize
 
         // Try to parse the data record to get accurate sizes
         let remaining = &data[offset..];
-        let record_result = DataRecord::try_from(remaining);
+        let record_result = DataRecord::parse(remaining, None, plaintext_vif_order);
 
         match record_result {
             Ok(record) => {
@@ -994,12 +1040,13 @@ pub(crate) fn annotate_data_records(segments: &mut Vec<ByteSegment>, base: usize
 fn annotate_wireless_format_a(
     original: &[u8],
     stripped: &[u8],
+    plaintext_vif_order: PlaintextVifOrder,
 ) -> Result<Vec<ByteSegment>, MbusError> {
     // Build an offset map: for each byte in the stripped buffer, what's its original offset?
     let offset_map = build_format_a_offset_map(original);
 
     // First annotate the stripped frame
-    let stripped_segments = annotate_wireless_inner(stripped)?;
+    let stripped_segments = annotate_wireless_inner(stripped, plaintext_vif_order)?;
 
     let mut segments = Vec::new();
 
@@ -1132,7 +1179,10 @@ fn annotate_wireless_format_a(
 }
 
 /// Annotate a wireless frame directly (already stripped or Format B).
-fn annotate_wireless_inner(data: &[u8]) -> Result<Vec<ByteSegment>, MbusError> {
+fn annotate_wireless_inner(
+    data: &[u8],
+    plaintext_vif_order: PlaintextVifOrder,
+) -> Result<Vec<ByteSegment>, MbusError> {
     // Validate it parses as wireless
     let _frame = wireless_mbus_link_layer::WirelessFrame::try_from(data)?;
 
@@ -1214,7 +1264,7 @@ fn annotate_wireless_inner(data: &[u8]) -> Result<Vec<ByteSegment>, MbusError> {
     // Application layer starts at byte 10
     if app_end > 10 {
         let app_data = &data[10..app_end];
-        annotate_application_layer(&mut segments, data, 10, app_data);
+        annotate_application_layer(&mut segments, data, 10, app_data, plaintext_vif_order);
     }
 
     if let Some(start) = crc_start {
@@ -17,6 +17,9 @@ pub struct MbusData<'a, F> {
     pub frame: F,
     pub user_data: Option<user_data::UserDataBlock<'a>>,
     pub data_records: Option<user_data::DataRecords<'a>>,
+    /// Network layer header of a frame relayed by repeaters. The user data
+    /// holds the block that follows it.
+    pub network_layer: Option<user_data::network_layer::NetworkLayerHeader>,
     /// Application-layer parsing is deliberately non-fatal: callers can still
     /// inspect a valid link-layer frame and report a partial decode.
     #[cfg_attr(feature = "serde", serde(skip_serializing))]
@@ -30,11 +33,15 @@ impl<'a> TryFrom<&'a [u8]> for MbusData<'a, frames::WiredFrame<'a>> {
         let frame = frames::WiredFrame::try_from(data)?;
         let mut user_data = None;
         let mut data_records = None;
+        let mut network_layer = None;
         let mut application_error = None;
         match &frame {
             frames::WiredFrame::LongFrame { data, .. } => {
-                match user_data::UserDataBlock::try_from(*data) {
-                    Ok(x) => {
+                match user_data::UserDataBlock::try_from(*data)
+                    .and_then(user_data::UserDataBlock::split_network_layer)
+                {
+                    Ok((network, x)) => {
+                        network_layer = network;
                         match &x {
                             user_data::UserDataBlock::VariableDataStructureWithLongTplHeader {
                                 variable_data_block,
@@ -50,6 +57,19 @@ impl<'a> TryFrom<&'a [u8]> for MbusData<'a, frames::WiredFrame<'a>> {
                             } => {
                                 data_records = Some((*variable_data_block).into());
                             }
+                            user_data::UserDataBlock::ObisDataStructureWithLongTplHeader {
+                                long_tpl_header,
+                                obis_data_block,
+                            } if !long_tpl_header.is_encrypted() => {
+                                data_records = Some((*obis_data_block).into());
+                            }
+                            user_data::UserDataBlock::ObisDataStructureWithShortTplHeader {
+                                short_tpl_header,
+                                obis_data_block,
+                                ..
+                            } if !short_tpl_header.is_encrypted() => {
+                                data_records = Some((*obis_data_block).into());
+                            }
                             _ => {}
                         }
                         user_data = Some(x);
@@ -67,6 +87,7 @@ impl<'a> TryFrom<&'a [u8]> for MbusData<'a, frames::WiredFrame<'a>> {
             frame,
             user_data,
             data_records,
+            network_layer,
             application_error,
         })
     }
@@ -79,12 +100,16 @@ impl<'a> TryFrom<&'a [u8]> for MbusData<'a, WirelessFrame<'a>> {
         let frame = wireless_mbus_link_layer::WirelessFrame::try_from(data)?;
         let mut user_data = None;
         let mut data_records = None;
+        let mut network_layer = None;
         let mut application_error = None;
         // Extract application layer data from wireless frame
         let wireless_mbus_link_layer::WirelessFrame { data, .. } = &frame;
 
-        match user_data::UserDataBlock::try_from(*data) {
-            Ok(user_data_block) => {
+        match user_data::UserDataBlock::try_from(*data)
+            .and_then(user_data::UserDataBlock::split_network_layer)
+        {
+            Ok((network, user_data_block)) => {
+                network_layer = network;
                 match &user_data_block {
                     user_data::UserDataBlock::VariableDataStructureWithLongTplHeader {
                         variable_data_block,
@@ -104,6 +129,19 @@ impl<'a> TryFrom<&'a [u8]> for MbusData<'a, WirelessFrame<'a>> {
                     } => {
                         data_records = Some((*variable_data_block).into());
                     }
+                    user_data::UserDataBlock::ObisDataStructureWithLongTplHeader {
+                        long_tpl_header,
+                        obis_data_block,
+                    } if !long_tpl_header.is_encrypted() => {
+                        data_records = Some((*obis_data_block).into());
+                    }
+                    user_data::UserDataBlock::ObisDataStructureWithShortTplHeader {
+                        short_tpl_header,
+                        obis_data_block,
+                        ..
+                    } if !short_tpl_header.is_encrypted() => {
+                        data_records = Some((*obis_data_block).into());
+                    }
                     _ => {}
                 }
                 user_data = Some(user_data_block);
@@ -115,6 +153,7 @@ impl<'a> TryFrom<&'a [u8]> for MbusData<'a, WirelessFrame<'a>> {
             frame,
             user_data,
             data_records,
+            network_layer,
             application_error,
         })
     }
@@ -150,10 +189,7 @@ pub fn serialize_mbus_data(data: &str, format: &str, key: Option<&[u8; 16]>) ->
                 data,
                 output_format,
                 &crate::output::RenderOptions {
-                    decode: crate::output::DecodeOptions {
-                        key: key.copied(),
-                        include_enrichment: true,
-                    },
+                    decode: crate::output::DecodeOptions::default().with_key(key.copied()),
                     table_width: Some(100),
                 },
             )
@@ -340,6 +376,8 @@ fn record_summaries(records: &user_data::DataRecords) -> Vec<RecordSummary> {
             };
             let value = match record.data.value {
                 Some(DataType::Number(n)) | Some(DataType::LossyNumber(n)) => Some(n),
+                Some(DataType::Integer(n)) => Some(n as f64),
+                Some(DataType::LargeInteger(n)) => Some(n as f64),
                 _ => None,
             };
             let unit = value_information
@@ -1181,7 +1219,11 @@ fn parse_to_hexview(input: &str, key: Option<&[u8; 16]>) -> String {
         if let Some(display_data) = decrypted_hexview_data(&data, key_bytes) {
             return match crate::annotate::annotate_frame(&display_data) {
                 Ok(mut segments) => {
-                    replace_encrypted_payload_segments(&mut segments, &display_data);
+                    replace_encrypted_payload_segments(
+                        &mut segments,
+                        &display_data,
+                        user_data::value_information::PlaintextVifOrder::default(),
+                    );
                     serde_json::to_string_pretty(&serde_json::json!({
                         "bytes": display_data,
                         "segments": segments,
@@ -1204,25 +1246,27 @@ fn parse_to_hexview(input: &str, key: Option<&[u8; 16]>) -> String {
 pub(crate) fn render_annotated_bytes(
     data: &[u8],
     key: Option<&[u8; 16]>,
+    plaintext_vif_order: user_data::value_information::PlaintextVifOrder,
 ) -> Result<String, crate::output::OutputError> {
-    let original_segments = crate::annotate::annotate_frame(data).map_err(|error| {
-        crate::output::OutputError::Rendering {
-            code: "annotation.failed",
-            message: error.to_string(),
-        }
-    })?;
+    let annotate = |data: &[u8]| {
+        crate::annotate::annotate_frame_with_plaintext_vif_order(data, plaintext_vif_order).map_err(
+            |error| crate::output::OutputError::Rendering {
+                code: "annotation.failed",
+                message: error.to_string(),
+            },
+        )
+    };
+    let original_segments = annotate(data)?;
 
     #[cfg(feature = "decryption")]
     if let Some(key_bytes) = key {
         if let Some(display_data) = decrypted_hexview_data(data, key_bytes) {
-            let mut display_segments =
-                crate::annotate::annotate_frame(&display_data).map_err(|error| {
-                    crate::output::OutputError::Rendering {
-                        code: "annotation.failed",
-                        message: error.to_string(),
-                    }
-                })?;
-            replace_encrypted_payload_segments(&mut display_segments, &display_data);
+            let mut display_segments = annotate(&display_data)?;
+            replace_encrypted_payload_segments(
+                &mut display_segments,
+                &display_data,
+                plaintext_vif_order,
+            );
             return serde_json::to_string_pretty(&serde_json::json!({
                 "schema_version": 3,
                 "bytes": display_data,
@@ -1273,6 +1317,7 @@ fn decrypted_hexview_data(data: &[u8], key: &[u8; 16]) -> Option<Vec<u8>> {
 fn replace_encrypted_payload_segments(
     segments: &mut Vec<crate::annotate::ByteSegment>,
     display_data: &[u8],
+    plaintext_vif_order: user_data::value_information::PlaintextVifOrder,
 ) {
     let mut rewritten = Vec::with_capacity(segments.len());
 
@@ -1280,7 +1325,12 @@ fn replace_encrypted_payload_segments(
         if segment.kind == crate::annotate::SegmentKind::EncryptedPayload {
             let before_len = rewritten.len();
             if let Some(data) = display_data.get(segment.start..segment.end) {
-                crate::annotate::annotate_data_records(&mut rewritten, segment.start, data);
+                crate::annotate::annotate_data_records(
+                    &mut rewritten,
+                    segment.start,
+                    data,
+                    plaintext_vif_order,
+                );
             }
             if rewritten.len() == before_len {
                 rewritten.push(segment);
@@ -1470,7 +1520,7 @@ mod tests {
         let input = "68 3D 3D 68 08 01 72 00 51 20 02 82 4D 02 04 00 88 00 00 04 07 00 00 00 00 0C 15 03 00 00 00 0B 2E 00 00 00 0B 3B 00 00 00 0A 5A 88 12 0A 5E 16 05 0B 61 23 77 00 02 6C 8C 11 02 27 37 0D 0F 60 00 67 16";
         let csv_output = parse_to_csv(input, None);
 
-        let expected = "FrameType,Function,Address,Identification Number,Manufacturer,Manufacturer Name,Access Number,Status,Security Mode,Version,Device Type,DataPoint1_Value,DataPoint1_Info,DataPoint2_Value,DataPoint2_Info,DataPoint3_Value,DataPoint3_Info,DataPoint4_Value,DataPoint4_Info,DataPoint5_Value,DataPoint5_Info,DataPoint6_Value,DataPoint6_Info,DataPoint7_Value,DataPoint7_Info,DataPoint8_Value,DataPoint8_Info,DataPoint9_Value,DataPoint9_Info,DataPoint10_Value,DataPoint10_Info\nLongFrame,\"RspUd (ACD: false, DFC: false)\",Primary (1),02205100,SLB,Schlumberger Industries,0,\"Permanent error, Manufacturer specific 3\",No encryption used,2,Heat Meter (Return),(0)e4[Wh](Energy),\"0,Inst,32-bit Integer\",(3)e-1[m³](Volume),\"0,Inst,BCD 8-digit\",(0)e3[W](Power),\"0,Inst,BCD 6-digit\",(0)e-3[m³h⁻¹](VolumeFlow),\"0,Inst,BCD 6-digit\",(1288)e-1[°C](FlowTemperature),\"0,Inst,BCD 4-digit\",(516)e-1[°C](ReturnTemperature),\"0,Inst,BCD 4-digit\",(7723)e-2[°K](TemperatureDifference),\"0,Inst,BCD 6-digit\",(12/Jan/12)(Date),\"0,Inst,Date Type G\",(3383)[day](OperatingTime),\"0,Inst,16-bit Integer\",\"(Manufacturer Specific: [96, 0])\",\"0,Inst,Special Functions (ManufacturerSpecific)\"\n";
+        let expected = "FrameType,Function,Address,Identification Number,Manufacturer,Manufacturer Name,Access Number,Status,Security Mode,Version,Device Type,DataPoint1_Value,DataPoint1_Info,DataPoint2_Value,DataPoint2_Info,DataPoint3_Value,DataPoint3_Info,DataPoint4_Value,DataPoint4_Info,DataPoint5_Value,DataPoint5_Info,DataPoint6_Value,DataPoint6_Info,DataPoint7_Value,DataPoint7_Info,DataPoint8_Value,DataPoint8_Info,DataPoint9_Value,DataPoint9_Info,DataPoint10_Value,DataPoint10_Info\nLongFrame,\"RspUd (ACD: false, DFC: false)\",Primary (1),02205100,SLB,Schlumberger Industries,0,\"Permanent error, Manufacturer specific 3\",No encryption used,2,Heat Meter (Return),(0)e4[Wh](Energy),\"0,Inst,32-bit Integer\",(3)e-1[m³](Volume),\"0,Inst,BCD 8-digit\",(0)e3[W](Power),\"0,Inst,BCD 6-digit\",(0)e-3[m³h⁻¹](VolumeFlow),\"0,Inst,BCD 6-digit\",(1288)e-1[°C](FlowTemperature),\"0,Inst,BCD 4-digit\",(516)e-1[°C](ReturnTemperature),\"0,Inst,BCD 4-digit\",(7723)e-2[°K](TemperatureDifference),\"0,Inst,BCD 6-digit\",(12/Jan/2012)(Date),\"0,Inst,Date Type G\",(3383)[day](OperatingTime),\"0,Inst,16-bit Integer\",\"(Manufacturer Specific: [96, 0])\",\"0,Inst,Special Functions (ManufacturerSpecific)\"\n";
 
         assert_eq!(csv_output, expected);
     }
@@ -1708,7 +1758,7 @@ mod tests {
         assert!(date.get("value").is_none());
         assert_eq!(
             date.get("display").and_then(|v| v.as_str()),
-            Some("(12/Jan/12)(Date)")
+            Some("(12/Jan/2012)(Date)")
         );
 
         // A manufacturer-specific record has neither value information nor a
@@ -1860,7 +1910,7 @@ mod tests {
         assert!(table_output.contains("(0)e4[Wh]"));
         assert!(table_output.contains("(3)e-1[m³](Volume)"));
         assert!(table_output.contains("(1288)e-1[°C](FlowTemperature)"));
-        assert!(table_output.contains("(12/Jan/12)(Date)"));
+        assert!(table_output.contains("(12/Jan/2012)(Date)"));
         assert!(table_output.contains("(3383)[day]"));
     }
 
@@ -5,7 +5,9 @@
 //! renderers.
 
 use core::str::FromStr;
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::{Arc, Mutex};
 
 use m_bus_core::SecurityMode;
 use serde::Serialize;
@@ -15,10 +17,23 @@ use wireless_mbus_link_layer as wireless;
 
 use crate::mbus_data::MbusData;
 use crate::user_data;
+use crate::user_data::compact_frame::FormatSignatureCache;
+use crate::user_data::compact_profile::{
+    CompactProfileError, ExpandedProfile, IncrementMode, ProfileKind, Spacing,
+};
 use crate::user_data::data_information::{
-    DataFieldCoding, DataType, FunctionField, Month, SingleEveryOrInvalid, SpecialFunctions,
+    DataFieldCoding, DataType, Decimal, FunctionField, Month, SingleEveryOrInvalid,
+    SpecialFunctions, TimeFlags,
+};
+use crate::user_data::heat_cost_allocator::{BillingPeriodReading, HeatCostAllocation};
+use crate::user_data::manufacturer_specific::{
+    self, DecoderRegistry, ManufacturerRegistry, ManufacturerValue,
 };
-use crate::user_data::value_information::{Unit, UnitName, ValueLabel};
+use crate::user_data::register::RegisterKey;
+use crate::user_data::scaled_value::{ScaledNumber, ScaledValue};
+use crate::user_data::status::{ConditionSource, Conditions, DetectedCondition};
+use crate::user_data::unit_conversion::UnitSystem;
+use crate::user_data::value_information::{PlaintextVifOrder, Unit, UnitName, ValueLabel};
 
 const SCHEMA_VERSION: u8 = 3;
 const DEFAULT_TABLE_WIDTH: usize = 100;
@@ -36,6 +51,7 @@ pub enum OutputFormat {
     Xml,
     Annotated,
     AnnotatedText,
+    History,
 }
 
 impl OutputFormat {
@@ -50,6 +66,7 @@ impl OutputFormat {
             Self::Xml => "xml",
             Self::Annotated => "annotated",
             Self::AnnotatedText => "annotated-text",
+            Self::History => "history",
         }
     }
 }
@@ -73,6 +90,7 @@ impl FromStr for OutputFormat {
             "xml" => Ok(Self::Xml),
             "annotated" | "hexview" => Ok(Self::Annotated),
             "annotated-text" => Ok(Self::AnnotatedText),
+            "history" => Ok(Self::History),
             _ => Err(OutputError::UnsupportedFormat {
                 format: value.to_string(),
             }),
@@ -80,11 +98,36 @@ impl FromStr for OutputFormat {
     }
 }
 
-/// Options controlling protocol decoding.
+/// Record layouts of full and format frames by format signature, shared
+/// between the decodes of one meter.
+pub type FormatCache = Mutex<HashMap<u16, Vec<u8>>>;
+
+/// Options controlling protocol decoding. Start from
+/// [`DecodeOptions::default`] and set options with the `with_` methods.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct DecodeOptions {
     pub key: Option<[u8; 16]>,
     pub include_enrichment: bool,
+    /// Decoders for manufacturer specific records. Each value a decoder
+    /// extracts is reported as a record of its own. Defaults to the decoders
+    /// shipped with the parser.
+    pub manufacturer_decoders: Arc<ManufacturerRegistry>,
+    /// Converts record values to the units of this system.
+    pub unit_system: Option<UnitSystem>,
+    /// Fixed UTC offset of the standard time of the meter in minutes, not a
+    /// time zone with its own daylight saving rules. Timestamps are rendered
+    /// with this offset, one hour more if they carry the summer time flag.
+    /// Without it timestamps are rendered as local time without offset. The
+    /// summer time flag is reported in either case.
+    pub utc_offset: Option<i16>,
+    /// Position of the unit of plaintext VIFs relative to the VIFEs. Defaults
+    /// to the order the `plaintext-before-extension` feature selects.
+    pub plaintext_vif_order: PlaintextVifOrder,
+    /// Learns the record layouts of decoded full and format frames and
+    /// expands later compact frames with them. Without a cache compact frames
+    /// are reported without records.
+    pub format_cache: Option<Arc<FormatCache>>,
 }
 
 impl Default for DecodeOptions {
@@ -92,10 +135,61 @@ impl Default for DecodeOptions {
         Self {
             key: None,
             include_enrichment: true,
+            manufacturer_decoders: Arc::new(ManufacturerRegistry::with_builtin_decoders()),
+            unit_system: None,
+            utc_offset: None,
+            plaintext_vif_order: PlaintextVifOrder::default(),
+            format_cache: None,
         }
     }
 }
 
+impl DecodeOptions {
+    /// Sets the AES-128 key encrypted frames are decrypted with.
+    #[must_use]
+    pub const fn with_key(mut self, key: Option<[u8; 16]>) -> Self {
+        self.key = key;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_enrichment(mut self, include_enrichment: bool) -> Self {
+        self.include_enrichment = include_enrichment;
+        self
+    }
+
+    #[must_use]
+    pub fn with_manufacturer_decoders(mut self, registry: Arc<ManufacturerRegistry>) -> Self {
+        self.manufacturer_decoders = registry;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_unit_system(mut self, unit_system: Option<UnitSystem>) -> Self {
+        self.unit_system = unit_system;
+        self
+    }
+
+    /// Sets the fixed UTC offset of the standard time of the meter in minutes.
+    #[must_use]
+    pub const fn with_utc_offset(mut self, utc_offset: Option<i16>) -> Self {
+        self.utc_offset = utc_offset;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_plaintext_vif_order(mut self, order: PlaintextVifOrder) -> Self {
+        self.plaintext_vif_order = order;
+        self
+    }
+
+    #[must_use]
+    pub fn with_format_cache(mut self, format_cache: Option<Arc<FormatCache>>) -> Self {
+        self.format_cache = format_cache;
+        self
+    }
+}
+
 /// Options controlling decoding and human-oriented rendering.
 #[derive(Debug, Clone)]
 pub struct RenderOptions {
@@ -195,7 +289,7 @@ impl fmt::Display for OutputError {
             ),
             Self::UnsupportedFormat { format } => write!(
                 formatter,
-                "unsupported output format {format:?}; expected table, json, yaml, csv, mermaid, xml, annotated, or annotated-text"
+                "unsupported output format {format:?}; expected table, json, yaml, csv, mermaid, xml, annotated, annotated-text, or history"
             ),
             Self::InvalidOption { option, message } => {
                 write!(formatter, "invalid {option}: {message}")
@@ -223,6 +317,18 @@ pub struct DecodedOutput {
     pub diagnostics: Vec<Diagnostic>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enrichment: Option<EnrichmentOutput>,
+    /// Billing periods of heat cost allocators.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heat_cost_allocation: Option<HeatCostAllocationOutput>,
+    /// Readings expanded from compact profile records.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<ProfileOutput>,
+    /// Conditions named by the status byte, error flags and manufacturer
+    /// flags.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<ConditionOutput>,
+    #[serde(skip)]
+    time_series: Vec<TimeSeriesOutput>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -304,6 +410,28 @@ pub struct TransportOutput {
     pub configuration: Option<ConfigurationOutput>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ell: Option<EllOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkOutput>,
+    /// CI of the bidirectional transport layer header (`80`, `8A`, `8B`) the
+    /// application layer followed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport_control_information: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkOutput {
+    pub network_control: String,
+    pub hop_count: u8,
+    pub repeated: bool,
+    /// Repeaters the frame passed, first repeater first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repeater_path: Vec<RepeaterOutput>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepeaterOutput {
+    pub manufacturer: String,
+    pub address: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -324,11 +452,23 @@ pub struct SecurityOutput {
 #[derive(Debug, Clone, Serialize)]
 pub struct RecordOutput {
     pub index: usize,
+    /// OBIS code `A-B:C.D.E*F` of COSEM values and declared M-Bus records.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obis: Option<String>,
+    /// Stable identifier of the logical register, e.g.
+    /// `energy.tariff1.storage0`, to match the same register across frames
+    /// and meters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register: Option<String>,
     pub function: String,
     pub storage_number: u64,
     pub tariff: u64,
     pub subunit: u64,
     pub quantities: Vec<String>,
+    /// Combinable VIFE modifiers of the quantities, e.g. `At phase L1` or a
+    /// correction factor.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
     pub value: ValueOutput,
     /// Standardized unit notation when available, otherwise a readable symbol.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -348,6 +488,116 @@ pub struct ValueOutput {
     /// partial temporal values use a compact component object.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<serde_json::Value>,
+    /// The timestamp is summer (daylight saving) time, whether or not it is
+    /// rendered with its UTC offset.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub summer_time: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HeatCostAllocationOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<BillingPeriodOutput>,
+    /// Reading at the last set day.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<BillingPeriodOutput>,
+    /// Resulting rating factor applied to the units.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating_factor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BillingPeriodOutput {
+    pub storage_number: u64,
+    /// Consumption units as displayed by the allocator.
+    pub units: String,
+    /// Units multiplied by the rating factor.
+    pub consumption: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<ValueOutput>,
+}
+
+/// Readings expanded from a compact profile record.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileOutput {
+    /// Index of the profile record in [`DecodedOutput::records`].
+    pub record_index: usize,
+    /// `compact`, `compact_with_register_numbers` or `inverse`.
+    pub kind: String,
+    /// `absolute`, `increments`, `decrements` or `signed_differences`.
+    pub increment_mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing_seconds: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing_months: Option<u8>,
+    pub quantities: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Date of the storage number of the profile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_time: Option<ValueOutput>,
+    /// Register the increments are applied to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_value: Option<ValueOutput>,
+    pub points: Vec<ProfilePointOutput>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfilePointOutput {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<ValueOutput>,
+    /// Reading at the timestamp; `none` for invalid elements and for
+    /// increments without base value.
+    pub value: ValueOutput,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConditionOutput {
+    /// Stable identifier, e.g. `low_battery`.
+    pub id: String,
+    pub name: String,
+    /// `status`, `error_flags` or `manufacturer_flags`.
+    pub source: String,
+}
+
+/// Readings of one quantity by storage number, see
+/// [`DecodedOutput::time_series`].
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeSeriesOutput {
+    pub quantities: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
+    pub function: String,
+    pub tariff: u64,
+    pub subunit: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    pub points: Vec<TimeSeriesPointOutput>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeSeriesPointOutput {
+    pub storage_number: u64,
+    /// Value of the date record with the same storage number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<serde_json::Value>,
+    pub value: ValueOutput,
+}
+
+impl DecodedOutput {
+    /// Readings of the data records grouped by quantity into series ordered
+    /// by storage number, see [`user_data::history::TimeSeries`]. Each point
+    /// carries the value of the date or date and time record of its storage
+    /// number; compact profiles and records without quantity are left out.
+    #[must_use]
+    pub fn time_series(&self) -> &[TimeSeriesOutput] {
+        &self.time_series
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -421,12 +671,19 @@ pub fn decode_bytes(data: &[u8], options: &DecodeOptions) -> Result<DecodedOutpu
             } else if security.encrypted {
                 parsed.data_records = None;
             }
-            return Ok(build_wired_output(
-                data,
-                &parsed,
-                security.clone(),
-                options.include_enrichment,
-            ));
+            let mut expanded_buffer = [0u8; 512];
+            if let Some(expanded) = apply_format_cache(
+                parsed.user_data.as_ref(),
+                security.decrypted_payload.as_deref(),
+                options,
+                &mut expanded_buffer,
+            ) {
+                parsed.data_records = Some(expanded);
+            }
+            parsed.data_records = parsed
+                .data_records
+                .map(|records| records.with_plaintext_vif_order(options.plaintext_vif_order));
+            return Ok(build_wired_output(data, &parsed, security.clone(), options));
         }
         Err(error) => error.to_string(),
     };
@@ -448,13 +705,25 @@ pub fn decode_bytes(data: &[u8], options: &DecodeOptions) -> Result<DecodedOutpu
             } else if security.encrypted {
                 parsed.data_records = None;
             }
+            let mut expanded_buffer = [0u8; 512];
+            if let Some(expanded) = apply_format_cache(
+                parsed.user_data.as_ref(),
+                security.decrypted_payload.as_deref(),
+                options,
+                &mut expanded_buffer,
+            ) {
+                parsed.data_records = Some(expanded);
+            }
+            parsed.data_records = parsed
+                .data_records
+                .map(|records| records.with_plaintext_vif_order(options.plaintext_vif_order));
             Ok(build_wireless_output(
                 data,
                 normalized,
                 stripped,
                 &parsed,
                 security.clone(),
-                options.include_enrichment,
+                options,
             ))
         }
         Err(error) => Err(OutputError::InvalidFrame {
@@ -465,25 +734,35 @@ pub fn decode_bytes(data: &[u8], options: &DecodeOptions) -> Result<DecodedOutpu
 }
 
 /// Decode DIF/VIF records after link and transport headers have been removed.
-pub fn decode_data_records(data: &[u8]) -> Result<Vec<RecordOutput>, OutputError> {
+pub fn decode_data_records(
+    data: &[u8],
+    options: &DecodeOptions,
+) -> Result<Vec<RecordOutput>, OutputError> {
     if data.is_empty() {
         return Err(OutputError::EmptyInput);
     }
-    let records = user_data::DataRecords::from(data);
-    let (output, error) = collect_records(Some(&records));
-    if let Some((offset, message)) = error {
+    let records =
+        user_data::DataRecords::from(data).with_plaintext_vif_order(options.plaintext_vif_order);
+    let (output, skipped) = collect_records(Some(&records), None, options);
+    if let Some(skipped) = skipped.first() {
         return Err(OutputError::Rendering {
             code: "application.records_invalid",
-            message: format!("failed to parse data record at byte offset {offset}: {message}"),
+            message: format!(
+                "failed to parse data record at byte offset {}: {}",
+                skipped.start, skipped.error
+            ),
         });
     }
     Ok(output)
 }
 
 /// Decode human-readable hexadecimal DIF/VIF records.
-pub fn decode_data_records_hex(input: &str) -> Result<Vec<RecordOutput>, OutputError> {
+pub fn decode_data_records_hex(
+    input: &str,
+    options: &DecodeOptions,
+) -> Result<Vec<RecordOutput>, OutputError> {
     let data = decode_hex_bytes(input)?;
-    decode_data_records(&data)
+    decode_data_records(&data, options)
 }
 
 /// Render a human-readable hexadecimal frame.
@@ -503,23 +782,29 @@ pub fn render_bytes(
     options: &RenderOptions,
 ) -> Result<String, OutputError> {
     match format {
-        OutputFormat::Xml => {
-            crate::rscada_xml::render_from_bytes(data, options.decode.key.as_ref()).map_err(
-                |message| OutputError::Rendering {
-                    code: "xml.unsupported",
-                    message,
-                },
-            )
-        }
-        OutputFormat::Annotated => {
-            crate::mbus_data::render_annotated_bytes(data, options.decode.key.as_ref())
-        }
-        OutputFormat::AnnotatedText => {
-            crate::annotate::annotate_and_render(data).map_err(|error| OutputError::Rendering {
-                code: "annotation.failed",
-                message: error.to_string(),
-            })
-        }
+        OutputFormat::Xml => crate::rscada_xml::render_from_bytes(
+            data,
+            options.decode.key.as_ref(),
+            options.decode.plaintext_vif_order,
+        )
+        .map_err(|message| OutputError::Rendering {
+            code: "xml.unsupported",
+            message,
+        }),
+        OutputFormat::Annotated => crate::mbus_data::render_annotated_bytes(
+            data,
+            options.decode.key.as_ref(),
+            options.decode.plaintext_vif_order,
+        ),
+        OutputFormat::AnnotatedText => crate::annotate::annotate_frame_with_plaintext_vif_order(
+            data,
+            options.decode.plaintext_vif_order,
+        )
+        .map(|segments| crate::annotate::render_annotations(&segments, data))
+        .map_err(|error| OutputError::Rendering {
+            code: "annotation.failed",
+            message: error.to_string(),
+        }),
         _ => {
             let decoded = decode_bytes(data, &options.decode)?;
             match format {
@@ -540,6 +825,9 @@ pub fn render_bytes(
                     render_table(&decoded, options.table_width.unwrap_or(DEFAULT_TABLE_WIDTH))
                 }
                 OutputFormat::Mermaid => Ok(render_mermaid(&decoded)),
+                OutputFormat::History => {
+                    render_history(&decoded, options.table_width.unwrap_or(DEFAULT_TABLE_WIDTH))
+                }
                 _ => unreachable!("specialized formats handled above"),
             }
         }
@@ -727,11 +1015,18 @@ fn security_fields(
                 false,
             )
         }
-        Some(UserDataBlock::VariableDataStructureWithShortTplHeader {
-            extended_link_layer,
-            short_tpl_header,
-            ..
-        }) => {
+        Some(
+            UserDataBlock::VariableDataStructureWithShortTplHeader {
+                extended_link_layer,
+                short_tpl_header,
+                ..
+            }
+            | UserDataBlock::CompactDataStructureWithShortTplHeader {
+                extended_link_layer,
+                short_tpl_header,
+                ..
+            },
+        ) => {
             let ell_encrypted = extended_link_layer
                 .as_ref()
                 .is_some_and(|ell| ell.encryption.is_some());
@@ -775,6 +1070,9 @@ pub(crate) fn decrypt_user_data(
         }
         UserDataBlock::VariableDataStructureWithShortTplHeader {
             short_tpl_header, ..
+        }
+        | UserDataBlock::CompactDataStructureWithShortTplHeader {
+            short_tpl_header, ..
         } => {
             if matches!(
                 short_tpl_header.configuration_field.security_mode(),
@@ -824,11 +1122,38 @@ fn decrypted_records<'a>(
     }
 }
 
+/// Learns the record layout of a full or format frame into the format cache
+/// of `options`, or expands a compact frame with it, from its decrypted
+/// payload if it was encrypted.
+fn apply_format_cache<'a>(
+    block: Option<&user_data::UserDataBlock<'_>>,
+    decrypted: Option<&[u8]>,
+    options: &DecodeOptions,
+    buffer: &'a mut [u8],
+) -> Option<user_data::DataRecords<'a>> {
+    let block = block?;
+    let mut cache = options.format_cache.as_ref()?.lock().ok()?;
+    let cache: &mut HashMap<u16, Vec<u8>> = &mut cache;
+    let expanded = match decrypted {
+        Some(payload) => block.expand_decrypted_compact_data(payload, cache, buffer),
+        None => block.expand_compact_data(cache, buffer),
+    };
+    if let Ok(length) = expanded {
+        return Some(user_data::DataRecords::new(buffer.get(..length)?, None));
+    }
+    // Frames without records or with an unreadable layout teach nothing.
+    let _ = match decrypted {
+        Some(payload) if decrypted_records(Some(block), payload).is_some() => cache.learn(payload),
+        _ => block.learn_format(cache),
+    };
+    None
+}
+
 fn build_wired_output(
     original: &[u8],
     parsed: &MbusData<wired::WiredFrame<'_>>,
     security_context: SecurityContext,
-    include_enrichment: bool,
+    options: &DecodeOptions,
 ) -> DecodedOutput {
     let (kind, function, address, payload) = match &parsed.frame {
         wired::WiredFrame::LongFrame {
@@ -876,9 +1201,10 @@ fn build_wired_output(
         parsed.user_data.as_ref(),
         parsed.data_records.as_ref(),
         parsed.application_error.as_ref(),
+        parsed.network_layer.as_ref(),
         None,
         security_context,
-        include_enrichment,
+        options,
     )
 }
 
@@ -888,7 +1214,7 @@ fn build_wireless_output(
     format_a_crc_stripped: bool,
     parsed: &MbusData<wireless::WirelessFrame<'_>>,
     security_context: SecurityContext,
-    include_enrichment: bool,
+    options: &DecodeOptions,
 ) -> DecodedOutput {
     build_output(
         "wireless",
@@ -908,9 +1234,10 @@ fn build_wireless_output(
         parsed.user_data.as_ref(),
         parsed.data_records.as_ref(),
         parsed.application_error.as_ref(),
+        parsed.network_layer.as_ref(),
         Some(&parsed.frame.manufacturer_id),
         security_context,
-        include_enrichment,
+        options,
     )
 }
 
@@ -925,9 +1252,10 @@ fn build_output(
     user_data: Option<&user_data::UserDataBlock<'_>>,
     records: Option<&user_data::DataRecords<'_>>,
     application_error: Option<&m_bus_core::ApplicationLayerError>,
+    network_layer: Option<&user_data::network_layer::NetworkLayerHeader>,
     wireless_id: Option<&wireless::ManufacturerId>,
     security_context: SecurityContext,
-    include_enrichment: bool,
+    options: &DecodeOptions,
 ) -> DecodedOutput {
     let mut diagnostics = Vec::new();
     if let Some(error) = application_error {
@@ -940,6 +1268,25 @@ fn build_output(
             offset_end: None,
         });
     }
+    let compact_format_missing = records.is_none()
+        && matches!(
+            user_data,
+            Some(
+                user_data::UserDataBlock::CompactDataStructureWithShortTplHeader { .. }
+                    | user_data::UserDataBlock::CompactDataStructureWithoutTplHeader { .. }
+            )
+        );
+    if compact_format_missing {
+        diagnostics.push(Diagnostic {
+            severity: "warning".to_string(),
+            code: "application.compact_format_missing".to_string(),
+            layer: "application".to_string(),
+            message: "compact frame records require the format of an earlier full frame"
+                .to_string(),
+            offset_start: None,
+            offset_end: None,
+        });
+    }
     if security_context.encrypted && !security_context.decrypted {
         diagnostics.push(Diagnostic {
             severity: "warning".to_string(),
@@ -970,20 +1317,130 @@ fn build_output(
         });
     }
 
-    let (record_outputs, record_error) = collect_records(records);
-    if let Some((offset, message)) = record_error.as_ref() {
+    let meter = user_data
+        .and_then(user_data::UserDataBlock::meter_identity)
+        .or_else(|| {
+            wireless_id.map(|id| {
+                manufacturer_specific::MeterIdentity::new(
+                    id.manufacturer_code,
+                    id.version,
+                    id.device_type,
+                )
+            })
+        });
+    let heat_cost_allocation = records
+        .and_then(|records| {
+            let device_type = records.meter_identity().or(meter)?.device_type;
+            HeatCostAllocation::collect(device_type, records.clone())
+        })
+        .map(|allocation| heat_cost_allocation_output(&allocation, options.utc_offset));
+    let conditions = Conditions::collect(
+        &*options.manufacturer_decoders,
+        meter.as_ref(),
+        user_data.and_then(user_data::UserDataBlock::status),
+        records.cloned(),
+    )
+    .iter()
+    .map(condition_output)
+    .collect();
+    let (mut record_outputs, skipped_records) = collect_records(records, meter, options);
+    let (profiles, profile_errors) = records
+        .map(|records| profile_outputs(records, &record_outputs, options))
+        .unwrap_or_default();
+    let time_series = records
+        .map(|records| time_series_outputs(records, options))
+        .unwrap_or_default();
+    if let Some(fixed) = user_data.and_then(|block| block.fixed_data_records()) {
+        for record in &fixed {
+            record_outputs.push(fixed_record_output(
+                record_outputs.len(),
+                record,
+                options.unit_system,
+            ));
+        }
+    }
+    if let Some(payload) = user_data.and_then(user_data::UserDataBlock::manufacturer_payload) {
+        let payload = match (wireless_id, normalized.get(2..10)) {
+            (Some(_), Some(address)) => address
+                .try_into()
+                .map_or(payload, |address| payload.with_link_address(address)),
+            _ => payload,
+        };
+        let values = meter.as_ref().and_then(|meter| {
+            options
+                .manufacturer_decoders
+                .decode_payload(meter, &payload)
+        });
+        if let Some(values) = values {
+            for value in &values {
+                record_outputs.push(manufacturer_value_output(
+                    record_outputs.len(),
+                    value,
+                    options,
+                ));
+            }
+        } else {
+            diagnostics.push(Diagnostic {
+                severity: "info".to_string(),
+                code: "application.manufacturer_specific".to_string(),
+                layer: "application".to_string(),
+                message: format!(
+                    "no decoder recognises the manufacturer specific payload with CI 0x{:02X}",
+                    payload.control_information
+                ),
+                offset_start: None,
+                offset_end: None,
+            });
+        }
+    }
+    let mut cosem_error = None;
+    for record in user_data
+        .and_then(|block| block.cosem_records())
+        .into_iter()
+        .flatten()
+    {
+        match record {
+            Ok(record) => record_outputs.push(cosem_record_output(record_outputs.len(), &record)),
+            Err(error) => cosem_error = Some(error),
+        }
+    }
+    if let Some(error) = cosem_error.as_ref() {
         diagnostics.push(Diagnostic {
             severity: "warning".to_string(),
-            code: "application.record_partial".to_string(),
+            code: "application.cosem_partial".to_string(),
+            layer: "application".to_string(),
+            message: error.to_string(),
+            offset_start: None,
+            offset_end: None,
+        });
+    }
+    for (record_index, error) in &profile_errors {
+        diagnostics.push(Diagnostic {
+            severity: "warning".to_string(),
+            code: "application.compact_profile_invalid".to_string(),
             layer: "application".to_string(),
-            message: message.clone(),
-            offset_start: Some(*offset),
+            message: format!("record {record_index}: {error}"),
+            offset_start: None,
             offset_end: None,
         });
     }
+    for skipped in &skipped_records {
+        diagnostics.push(Diagnostic {
+            severity: "warning".to_string(),
+            code: "application.record_partial".to_string(),
+            layer: "application".to_string(),
+            message: format!(
+                "skipped data record at record bytes {}..{}: {}",
+                skipped.start, skipped.end, skipped.error
+            ),
+            offset_start: Some(skipped.start),
+            offset_end: Some(skipped.end),
+        });
+    }
 
-    let (meter, transport) = meter_and_transport(user_data, wireless_id);
-    let enrichment = if include_enrichment {
+    let (meter, mut transport) = meter_and_transport(user_data, wireless_id);
+    transport.network = network_layer.map(network_output);
+    let enrichment = if options.include_enrichment {
         meter
             .identity
             .as_ref()
@@ -1017,7 +1474,9 @@ fn build_output(
     };
     let mode = security_context.mode;
     let partial = application_error.is_some()
-        || record_error.is_some()
+        || compact_format_missing
+        || !skipped_records.is_empty()
+        || cosem_error.is_some()
         || (security_context.encrypted && !security_context.decrypted);
 
     DecodedOutput {
@@ -1047,9 +1506,208 @@ fn build_output(
         },
         diagnostics,
         enrichment,
+        heat_cost_allocation,
+        profiles,
+        conditions,
+        time_series,
+    }
+}
+
+fn condition_output(detected: &DetectedCondition) -> ConditionOutput {
+    ConditionOutput {
+        id: detected.condition.id().to_string(),
+        name: detected.condition.name().to_string(),
+        source: match detected.source {
+            ConditionSource::Status => "status",
+            ConditionSource::ErrorFlags => "error_flags",
+            ConditionSource::ManufacturerFlags => "manufacturer_flags",
+        }
+        .to_string(),
+    }
+}
+
+fn heat_cost_allocation_output(
+    allocation: &HeatCostAllocation<'_>,
+    utc_offset: Option<i16>,
+) -> HeatCostAllocationOutput {
+    let period = |reading: &BillingPeriodReading<'_>| BillingPeriodOutput {
+        storage_number: reading.storage_number,
+        units: reading.units.to_string(),
+        consumption: reading.consumption.to_string(),
+        date: reading
+            .date
+            .as_ref()
+            .map(|date| data_value_output(Some(date), None, utc_offset)),
+    };
+    HeatCostAllocationOutput {
+        current: allocation.current.as_ref().map(period),
+        previous: allocation.previous.as_ref().map(period),
+        rating_factor: allocation
+            .rating_factors
+            .resulting_factor()
+            .map(|factor| factor.to_string()),
+    }
+}
+
+/// Expands the compact profile records, in the order `record_output` marked
+/// them with the value kind `compact_profile`.
+fn profile_outputs(
+    records: &user_data::DataRecords<'_>,
+    record_outputs: &[RecordOutput],
+    options: &DecodeOptions,
+) -> (Vec<ProfileOutput>, Vec<(usize, CompactProfileError)>) {
+    let timestamps = records.storage_timestamps();
+    let profile_records = records
+        .clone()
+        .recovering()
+        .filter_map(Result::ok)
+        .filter(|record| ProfileKind::of(record).is_some());
+    let outputs = record_outputs
+        .iter()
+        .filter(|record| record.value.kind == "compact_profile");
+    let mut profiles = Vec::new();
+    let mut errors = Vec::new();
+    for (record, output) in profile_records.zip(outputs) {
+        match ExpandedProfile::new(record, records.clone(), &timestamps) {
+            Ok(profile) => profiles.push(profile_output(output, &profile, options)),
+            Err(error) => errors.push((output.index, error)),
+        }
+    }
+    (profiles, errors)
+}
+
+fn time_series_outputs(
+    records: &user_data::DataRecords<'_>,
+    options: &DecodeOptions,
+) -> Vec<TimeSeriesOutput> {
+    records
+        .time_series()
+        .iter()
+        .filter_map(|series| {
+            let first = &series.points.first()?.record;
+            if ProfileKind::of(first).is_some() {
+                return None;
+            }
+            let output = record_output(0, first, options);
+            if output.quantities.is_empty() {
+                return None;
+            }
+            let points = series
+                .points
+                .iter()
+                .map(|point| TimeSeriesPointOutput {
+                    storage_number: point.storage_number,
+                    timestamp: point.timestamp.as_ref().and_then(|timestamp| {
+                        data_value_output(Some(timestamp), None, options.utc_offset).value
+                    }),
+                    value: record_output(0, &point.record, options).value,
+                })
+                .collect();
+            Some(TimeSeriesOutput {
+                quantities: output.quantities,
+                modifiers: output.modifiers,
+                function: output.function,
+                tariff: series.key.tariff,
+                subunit: series.key.subunit,
+                unit: output.unit,
+                points,
+            })
+        })
+        .collect()
+}
+
+fn profile_output(
+    record: &RecordOutput,
+    profile: &ExpandedProfile<'_>,
+    options: &DecodeOptions,
+) -> ProfileOutput {
+    let value_information = profile.record.value_information();
+    let reading = |value: Option<ScaledNumber>| {
+        let Some(value) = value else {
+            let none = ValueOutput {
+                kind: "none".to_string(),
+                value: None,
+                summer_time: false,
+            };
+            return (none, record.unit.clone());
+        };
+        match (options.unit_system, value_information) {
+            (Some(system), Some(information)) => converted_output(
+                &ScaledValue {
+                    value,
+                    units: information.units.clone(),
+                    labels: information.labels.clone(),
+                },
+                system,
+            ),
+            _ => (number_output(value), record.unit.clone()),
+        }
+    };
+    let timestamp = |time: Option<_>| {
+        time.map(|time: crate::user_data::compact_profile::Timestamp| {
+            data_value_output(Some(&time.to_data_type()), None, options.utc_offset)
+        })
+    };
+    let (spacing_seconds, spacing_months) = match profile.profile.spacing {
+        Spacing::Seconds(seconds) => (Some(seconds), None),
+        Spacing::Months(months) => (None, Some(months)),
+    };
+    let modifiers = record
+        .modifiers
+        .iter()
+        .filter(|modifier| !is_profile_modifier(modifier))
+        .cloned()
+        .collect();
+    ProfileOutput {
+        record_index: record.index,
+        kind: match profile.profile.kind {
+            ProfileKind::Compact => "compact",
+            ProfileKind::CompactWithRegisterNumbers => "compact_with_register_numbers",
+            ProfileKind::Inverse => "inverse",
+        }
+        .to_string(),
+        increment_mode: match profile.profile.increment_mode {
+            IncrementMode::Absolute => "absolute",
+            IncrementMode::Increments => "increments",
+            IncrementMode::Decrements => "decrements",
+            IncrementMode::SignedDifferences => "signed_differences",
+        }
+        .to_string(),
+        spacing_seconds,
+        spacing_months,
+        quantities: record.quantities.clone(),
+        modifiers,
+        unit: reading(
+            profile
+                .base_value
+                .or_else(|| profile.points.iter().find_map(|point| point.value)),
+        )
+        .1,
+        base_time: timestamp(profile.base_time),
+        base_value: profile.base_value.map(|value| reading(Some(value)).0),
+        points: profile
+            .points
+            .iter()
+            .map(|point| ProfilePointOutput {
+                index: point.index,
+                storage_number: point.storage_number,
+                timestamp: timestamp(point.timestamp),
+                value: reading(point.value).0,
+            })
+            .collect(),
     }
 }
 
+fn is_profile_modifier(name: &str) -> bool {
+    [
+        ValueLabel::CompactProfile,
+        ValueLabel::CompactProfileWithRegisterNumbers,
+        ValueLabel::InverseCompactProfile,
+    ]
+    .iter()
+    .any(|label| label_names(&[*label]).1.iter().any(|known| known == name))
+}
+
 fn meter_and_transport(
     user_data: Option<&user_data::UserDataBlock<'_>>,
     wireless_id: Option<&wireless::ManufacturerId>,
@@ -1064,6 +1722,10 @@ fn meter_and_transport(
         statuses: Vec::new(),
         configuration: None,
         ell: None,
+        network: None,
+        transport_control_information: user_data
+            .and_then(UserDataBlock::transport_control_information)
+            .map(|control_information| format!("{control_information:02X}")),
     };
 
     match user_data {
@@ -1144,7 +1806,12 @@ fn meter_and_transport(
                 id: Some(identification_number.to_string()),
                 manufacturer_code: None,
                 version: None,
-                device_type: Some(format!("fixed type/unit 0x{device_type_and_unit:04X}")),
+                device_type: Some(
+                    user_data::fixed_data_structure::FixedMedium::from_medium_and_unit(
+                        *device_type_and_unit,
+                    )
+                    .to_string(),
+                ),
                 device_type_code: None,
             });
             transport.header_kind = Some("fixed".to_string());
@@ -1158,28 +1825,187 @@ fn meter_and_transport(
                 display: status.to_string(),
             });
         }
-        Some(UserDataBlock::ResetAtApplicationLevel { .. }) => {
-            transport.header_kind = Some("application_reset".to_string());
+        Some(UserDataBlock::CompactDataStructureWithShortTplHeader {
+            extended_link_layer,
+            short_tpl_header,
+            ..
+        }) => {
+            transport.header_kind = Some("compact_short_tpl".to_string());
+            add_short_transport(&mut transport, "tpl.short", short_tpl_header);
+            transport.ell = extended_link_layer.as_ref().map(ell_output);
         }
-        None => {}
-        Some(_) => {}
-    }
-
-    (
-        MeterOutput {
-            identity: primary,
-            alternate_identities: alternates,
-        },
-        transport,
-    )
-}
-
-fn add_short_transport(
-    transport: &mut TransportOutput,
-    source: &str,
-    header: &user_data::ShortTplHeader,
-) {
-    transport.access_numbers.push(SourcedU8 {
+        Some(UserDataBlock::CompactDataStructureWithoutTplHeader {
+            extended_link_layer,
+            ..
+        }) => {
+            transport.header_kind = Some("compact".to_string());
+            if let Some(ell) = extended_link_layer {
+                transport.access_numbers.push(SourcedU8 {
+                    source: "ell".to_string(),
+                    value: ell.access_number,
+                });
+                transport.ell = Some(ell_output(ell));
+            }
+        }
+        Some(UserDataBlock::FormatFrameWithLongTplHeader {
+            long_tpl_header, ..
+        }) => {
+            let application_identity = identity_from_long(long_tpl_header);
+            if let Some(link) = link_identity {
+                if link != application_identity {
+                    alternates.push(link);
+                }
+            }
+            primary = Some(application_identity);
+            transport.header_kind = Some("format_long_tpl".to_string());
+            add_short_transport(
+                &mut transport,
+                "tpl.long",
+                &long_tpl_header.short_tpl_header,
+            );
+        }
+        Some(UserDataBlock::FormatFrameWithShortTplHeader {
+            extended_link_layer,
+            short_tpl_header,
+            ..
+        }) => {
+            transport.header_kind = Some("format_short_tpl".to_string());
+            add_short_transport(&mut transport, "tpl.short", short_tpl_header);
+            transport.ell = extended_link_layer.as_ref().map(ell_output);
+        }
+        Some(UserDataBlock::FormatFrameWithoutTplHeader {
+            extended_link_layer,
+            ..
+        }) => {
+            transport.header_kind = Some("format".to_string());
+            if let Some(ell) = extended_link_layer {
+                transport.access_numbers.push(SourcedU8 {
+                    source: "ell".to_string(),
+                    value: ell.access_number,
+                });
+                transport.ell = Some(ell_output(ell));
+            }
+        }
+        Some(UserDataBlock::CosemDataStructureWithLongTplHeader {
+            long_tpl_header, ..
+        }) => {
+            let application_identity = identity_from_long(long_tpl_header);
+            if let Some(link) = link_identity {
+                if link != application_identity {
+                    alternates.push(link);
+                }
+            }
+            primary = Some(application_identity);
+            transport.header_kind = Some("cosem_long_tpl".to_string());
+            add_short_transport(
+                &mut transport,
+                "tpl.long",
+                &long_tpl_header.short_tpl_header,
+            );
+        }
+        Some(UserDataBlock::CosemDataStructureWithShortTplHeader {
+            extended_link_layer,
+            short_tpl_header,
+            ..
+        }) => {
+            transport.header_kind = Some("cosem_short_tpl".to_string());
+            add_short_transport(&mut transport, "tpl.short", short_tpl_header);
+            transport.ell = extended_link_layer.as_ref().map(ell_output);
+        }
+        Some(UserDataBlock::ObisDataStructureWithLongTplHeader {
+            long_tpl_header, ..
+        }) => {
+            let application_identity = identity_from_long(long_tpl_header);
+            if let Some(link) = link_identity {
+                if link != application_identity {
+                    alternates.push(link);
+                }
+            }
+            primary = Some(application_identity);
+            transport.header_kind = Some("obis_long_tpl".to_string());
+            add_short_transport(
+                &mut transport,
+                "tpl.long",
+                &long_tpl_header.short_tpl_header,
+            );
+        }
+        Some(UserDataBlock::ObisDataStructureWithShortTplHeader {
+            extended_link_layer,
+            short_tpl_header,
+            ..
+        }) => {
+            transport.header_kind = Some("obis_short_tpl".to_string());
+            add_short_transport(&mut transport, "tpl.short", short_tpl_header);
+            transport.ell = extended_link_layer.as_ref().map(ell_output);
+        }
+        Some(UserDataBlock::NetworkManagement {
+            extended_link_layer,
+            ..
+        }) => {
+            transport.header_kind = Some("network_management".to_string());
+            transport.ell = extended_link_layer.as_ref().map(ell_output);
+        }
+        Some(UserDataBlock::ManufacturerSpecific {
+            extended_link_layer,
+            ..
+        }) => {
+            transport.header_kind = Some("manufacturer_specific".to_string());
+            transport.ell = extended_link_layer.as_ref().map(ell_output);
+        }
+        Some(UserDataBlock::TransportLayer {
+            extended_link_layer,
+            transport_layer_header,
+            ..
+        }) => {
+            use user_data::TransportLayerHeader;
+            if let Some(long_tpl_header) = transport_layer_header.long_tpl_header() {
+                let application_identity = identity_from_long(long_tpl_header);
+                if let Some(link) = link_identity {
+                    if link != application_identity {
+                        alternates.push(link);
+                    }
+                }
+                primary = Some(application_identity);
+            }
+            let (header_kind, source) = match transport_layer_header {
+                TransportLayerHeader::LongReadoutToMeter(_) => {
+                    ("transport_long_readout_to_meter", "tpl.long")
+                }
+                TransportLayerHeader::ShortMeterToReadout(_) => {
+                    ("transport_short_meter_to_readout", "tpl.short")
+                }
+                _ => ("transport_long_meter_to_readout", "tpl.long"),
+            };
+            transport.header_kind = Some(header_kind.to_string());
+            add_short_transport(
+                &mut transport,
+                source,
+                transport_layer_header.short_tpl_header(),
+            );
+            transport.ell = extended_link_layer.as_ref().map(ell_output);
+        }
+        Some(UserDataBlock::ResetAtApplicationLevel { .. }) => {
+            transport.header_kind = Some("application_reset".to_string());
+        }
+        None => {}
+        Some(_) => {}
+    }
+
+    (
+        MeterOutput {
+            identity: primary,
+            alternate_identities: alternates,
+        },
+        transport,
+    )
+}
+
+fn add_short_transport(
+    transport: &mut TransportOutput,
+    source: &str,
+    header: &user_data::ShortTplHeader,
+) {
+    transport.access_numbers.push(SourcedU8 {
         source: source.to_string(),
         value: header.access_number,
     });
@@ -1194,6 +2020,22 @@ fn add_short_transport(
     });
 }
 
+fn network_output(header: &user_data::network_layer::NetworkLayerHeader) -> NetworkOutput {
+    NetworkOutput {
+        network_control: format!("{:02X}", header.network_control),
+        hop_count: header.hop_count(),
+        repeated: header.is_repeated(),
+        repeater_path: header
+            .repeater_path
+            .iter()
+            .map(|repeater| RepeaterOutput {
+                manufacturer: format!("{:04X}", repeater.manufacturer),
+                address: hex_string(&repeater.address),
+            })
+            .collect(),
+    }
+}
+
 fn ell_output(ell: &user_data::extended_link_layer::ExtendedLinkLayer) -> EllOutput {
     EllOutput {
         communication_control: format!("{:02X}", ell.communication_control),
@@ -1249,28 +2091,55 @@ fn enrichment_for(code: &str) -> Option<EnrichmentOutput> {
     })
 }
 
+/// Renders the records of a frame, skipping records that fail to parse.
+///
+/// Manufacturer specific records of the meter are replaced by the values a
+/// registered decoder extracts from them. The meter is taken from the long
+/// transport header, or else from `meter`.
 fn collect_records(
     records: Option<&user_data::DataRecords<'_>>,
-) -> (Vec<RecordOutput>, Option<(usize, String)>) {
+    meter: Option<manufacturer_specific::MeterIdentity>,
+    options: &DecodeOptions,
+) -> (Vec<RecordOutput>, Vec<user_data::SkippedRecord>) {
     let Some(records) = records else {
-        return (Vec::new(), None);
+        return (Vec::new(), Vec::new());
     };
+    let meter = records.meter_identity().or(meter);
+    let declarations = user_data::obis::ObisDeclarations::collect(records.clone());
     let mut output = Vec::new();
-    let mut offset = 0usize;
-    for item in records.clone() {
+    let mut skipped = Vec::new();
+    for item in records.clone().recovering() {
         match item {
             Ok(record) => {
-                let size = record.raw_bytes().len();
-                output.push(record_output(output.len(), &record));
-                offset += size;
+                let decoded = meter
+                    .as_ref()
+                    .and_then(|meter| options.manufacturer_decoders.decode(meter, &record));
+                if let Some(values) = decoded {
+                    for value in &values {
+                        output.push(manufacturer_record_output(
+                            output.len(),
+                            &record,
+                            value,
+                            options,
+                        ));
+                    }
+                    continue;
+                }
+                let mut record_output = record_output(output.len(), &record, options);
+                record_output.obis = declarations.obis_code(&record).map(|obis| obis.to_string());
+                output.push(record_output);
             }
-            Err(error) => return (output, Some((offset, error.to_string()))),
+            Err(error) => skipped.push(error),
         }
     }
-    (output, None)
+    (output, skipped)
 }
 
-fn record_output(index: usize, record: &user_data::DataRecord<'_>) -> RecordOutput {
+fn record_output(
+    index: usize,
+    record: &user_data::DataRecord<'_>,
+    options: &DecodeOptions,
+) -> RecordOutput {
     let information = record.data_information();
     let value_information = record.value_information();
     let function = information
@@ -1285,20 +2154,35 @@ fn record_output(index: usize, record: &user_data::DataRecord<'_>) -> RecordOutp
     let data_coding = information
         .map(|value| data_coding_name(value.data_field_coding))
         .unwrap_or_else(|| special_record_name(record));
-    let quantities = value_information
-        .map(|value| value.labels.iter().map(quantity_name).collect())
+    let (quantities, modifiers) = value_information
+        .map(|value| label_names(&value.labels))
         .unwrap_or_default();
     let unit = value_information
         .and_then(|value| (!value.units.is_empty()).then(|| unit_output(value.units.as_slice())));
+    let (value, unit) = match (options.unit_system, record.scaled_value()) {
+        _ if ProfileKind::of(record).is_some() => (
+            ValueOutput {
+                kind: "compact_profile".to_string(),
+                value: None,
+                summer_time: false,
+            },
+            unit,
+        ),
+        (Some(system), Some(scaled)) => converted_output(&scaled, system),
+        _ => (value_output(record, options.utc_offset), unit),
+    };
 
     RecordOutput {
         index,
+        obis: None,
+        register: RegisterKey::of(record).map(|key| key.to_string()),
         function,
         storage_number,
         tariff,
         subunit,
         quantities,
-        value: value_output(record),
+        modifiers,
+        value,
         unit,
         data_coding,
         header_hex: record.data_record_header_hex(),
@@ -1306,6 +2190,311 @@ fn record_output(index: usize, record: &user_data::DataRecord<'_>) -> RecordOutp
     }
 }
 
+/// Record of a value a manufacturer decoder extracted from `record`.
+fn manufacturer_record_output(
+    index: usize,
+    record: &user_data::DataRecord<'_>,
+    value: &ManufacturerValue<'_>,
+    options: &DecodeOptions,
+) -> RecordOutput {
+    let output = manufacturer_value_output(index, value, options);
+    RecordOutput {
+        register: None,
+        quantities: output.quantities,
+        modifiers: output.modifiers,
+        value: output.value,
+        unit: output.unit,
+        ..record_output(index, record, options)
+    }
+}
+
+/// Record of a value a manufacturer decoder extracted from a proprietary
+/// application layer.
+fn manufacturer_value_output(
+    index: usize,
+    value: &ManufacturerValue<'_>,
+    options: &DecodeOptions,
+) -> RecordOutput {
+    let unit = (!value.units.is_empty()).then(|| unit_output(value.units.as_slice()));
+    let (value_output, unit) = match (&value.flags, options.unit_system, value.scaled_value()) {
+        (Some(flags), _, _) => (
+            ValueOutput {
+                kind: "flags".to_string(),
+                value: Some(flags.iter().collect::<Vec<_>>().into()),
+                summer_time: false,
+            },
+            unit,
+        ),
+        (None, Some(system), Some(scaled)) => converted_output(&scaled, system),
+        (None, _, scaled) => (
+            data_value_output(
+                Some(&value.value),
+                scaled.map(|scaled| scaled.value),
+                options.utc_offset,
+            ),
+            unit,
+        ),
+    };
+    RecordOutput {
+        index,
+        obis: None,
+        register: None,
+        function: record_function_name(FunctionField::InstantaneousValue).to_string(),
+        storage_number: value.storage_number,
+        tariff: 0,
+        subunit: 0,
+        quantities: vec![value.name.to_string()],
+        modifiers: Vec::new(),
+        value: value_output,
+        unit,
+        data_coding: "Manufacturer specific".to_string(),
+        header_hex: String::new(),
+        data_hex: String::new(),
+    }
+}
+
+fn fixed_record_output(
+    index: usize,
+    record: &user_data::fixed_data_structure::FixedDataRecord,
+    unit_system: Option<UnitSystem>,
+) -> RecordOutput {
+    let value_information = record.value_information.as_ref();
+    let scale = value_information.map_or(0, |value| value.decimal_scale_exponent);
+    let value = ValueOutput {
+        kind: "decimal".to_string(),
+        value: Some(serde_json::Value::String(scaled_decimal(
+            Decimal::from(record.value),
+            scale,
+        ))),
+        summer_time: false,
+    };
+    let unit = value_information
+        .and_then(|value| (!value.units.is_empty()).then(|| unit_output(value.units.as_slice())));
+    let (value, unit) = match (unit_system, record.scaled_value()) {
+        (Some(system), Some(scaled)) => converted_output(&scaled, system),
+        _ => (value, unit),
+    };
+    let (quantities, modifiers) = value_information
+        .map(|value| label_names(&value.labels))
+        .unwrap_or_default();
+    RecordOutput {
+        index,
+        obis: None,
+        register: None,
+        function: record_function_name(FunctionField::InstantaneousValue).to_string(),
+        storage_number: u64::from(record.historic),
+        tariff: 0,
+        subunit: 0,
+        quantities,
+        modifiers,
+        value,
+        unit,
+        data_coding: data_coding_name(record.data_field_coding),
+        header_hex: String::new(),
+        data_hex: record
+            .raw_bytes
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+fn cosem_record_output(index: usize, record: &user_data::cosem::CosemRecord<'_>) -> RecordOutput {
+    RecordOutput {
+        index,
+        obis: Some(record.obis.to_string()),
+        register: None,
+        function: record_function_name(FunctionField::InstantaneousValue).to_string(),
+        storage_number: 0,
+        tariff: 0,
+        subunit: 0,
+        quantities: Vec::new(),
+        modifiers: Vec::new(),
+        value: axdr_value_output(&record.value, record.scaler_unit.map_or(0, |su| su.scaler)),
+        unit: record.scaler_unit.and_then(|scaler_unit| {
+            scaler_unit
+                .units()
+                .map(unit_output)
+                .or_else(|| scaler_unit.unit_symbol().map(ToString::to_string))
+        }),
+        data_coding: axdr_type_name(&record.value).to_string(),
+        header_hex: String::new(),
+        data_hex: record
+            .data
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+fn axdr_value_output(value: &user_data::cosem::AxdrValue<'_>, scaler: i8) -> ValueOutput {
+    use user_data::cosem::AxdrValue;
+    if let Some(integer) = value.as_i128() {
+        let scale = if matches!(value, AxdrValue::Enum(_)) {
+            0
+        } else {
+            isize::from(scaler)
+        };
+        return ValueOutput {
+            kind: "decimal".to_string(),
+            value: Some(serde_json::Value::String(scaled_decimal(
+                Decimal::from(integer),
+                scale,
+            ))),
+            summer_time: false,
+        };
+    }
+    match value {
+        AxdrValue::Float32(_) | AxdrValue::Float64(_) => {
+            let number = match value {
+                AxdrValue::Float32(number) => f64::from(*number),
+                AxdrValue::Float64(number) => *number,
+                _ => f64::NAN,
+            } * 10f64.powi(i32::from(scaler));
+            float_output(number)
+        }
+        AxdrValue::Boolean(flag) => ValueOutput {
+            kind: "boolean".to_string(),
+            value: Some(serde_json::Value::Bool(*flag)),
+            summer_time: false,
+        },
+        AxdrValue::VisibleString(text) | AxdrValue::Utf8String(text) => ValueOutput {
+            kind: "text".to_string(),
+            value: Some(serde_json::Value::String(
+                String::from_utf8_lossy(text).into_owned(),
+            )),
+            summer_time: false,
+        },
+        AxdrValue::DateTime(_) | AxdrValue::OctetString(_) if value.as_date_time().is_some() => {
+            let date_time = value.as_date_time();
+            cosem_temporal_value(
+                "datetime",
+                date_time.map(|value| value.date),
+                date_time.map(|value| value.time),
+                date_time.and_then(|value| value.deviation),
+            )
+        }
+        AxdrValue::Date(date) => cosem_temporal_value("date", Some(*date), None, None),
+        AxdrValue::Time(time) => cosem_temporal_value("time", None, Some(*time), None),
+        AxdrValue::OctetString(bytes) | AxdrValue::BitString { data: bytes, .. } => ValueOutput {
+            kind: "bytes".to_string(),
+            value: Some(serde_json::Value::String(hex_string(bytes))),
+            summer_time: false,
+        },
+        AxdrValue::Bcd(byte) => ValueOutput {
+            kind: "decimal".to_string(),
+            value: Some(serde_json::Value::String(format!("{byte:X}"))),
+            summer_time: false,
+        },
+        AxdrValue::Null => ValueOutput {
+            kind: "none".to_string(),
+            value: None,
+            summer_time: false,
+        },
+        _ => ValueOutput {
+            kind: "unknown".to_string(),
+            value: None,
+            summer_time: false,
+        },
+    }
+}
+
+fn cosem_temporal_value(
+    kind: &str,
+    date: Option<user_data::cosem::CosemDate>,
+    time: Option<user_data::cosem::CosemTime>,
+    deviation: Option<i16>,
+) -> ValueOutput {
+    let year = date.and_then(|date| date.year);
+    let month = date
+        .and_then(|date| date.month)
+        .filter(|month| (1..=12).contains(month));
+    let day = date
+        .and_then(|date| date.day)
+        .filter(|day| (1..=31).contains(day));
+    let hour = time.and_then(|time| time.hour);
+    let minute = time.and_then(|time| time.minute);
+    let second = time.and_then(|time| time.second);
+    // The deviation is defined from local time to UTC, the ISO offset the
+    // other way round.
+    let offset = deviation.map(|deviation| {
+        let offset = -i32::from(deviation);
+        let sign = if offset < 0 { '-' } else { '+' };
+        format!("{sign}{:02}:{:02}", offset.abs() / 60, offset.abs() % 60)
+    });
+    let iso = (|| match kind {
+        "date" => Some(format!("{:04}-{:02}-{:02}", year?, month?, day?)),
+        "time" => Some(format!("{:02}:{:02}:{:02}", hour?, minute?, second?)),
+        _ => Some(format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+            year?,
+            month?,
+            day?,
+            hour?,
+            minute?,
+            second?,
+            offset.as_deref().unwrap_or("")
+        )),
+    })();
+    let value = iso.map(serde_json::Value::String).unwrap_or_else(|| {
+        let component = |value: Option<u64>| {
+            value.map_or(
+                serde_json::Value::String("unspecified".to_string()),
+                serde_json::Value::from,
+            )
+        };
+        let mut components = serde_json::Map::new();
+        if date.is_some() {
+            components.insert("day".to_string(), component(day.map(u64::from)));
+            components.insert("month".to_string(), component(month.map(u64::from)));
+            components.insert("year".to_string(), component(year.map(u64::from)));
+        }
+        if time.is_some() {
+            components.insert("hour".to_string(), component(hour.map(u64::from)));
+            components.insert("minute".to_string(), component(minute.map(u64::from)));
+            components.insert("second".to_string(), component(second.map(u64::from)));
+        }
+        serde_json::Value::Object(components)
+    });
+    ValueOutput {
+        kind: kind.to_string(),
+        value: Some(value),
+        summer_time: false,
+    }
+}
+
+const fn axdr_type_name(value: &user_data::cosem::AxdrValue<'_>) -> &'static str {
+    use user_data::cosem::AxdrValue;
+    match value {
+        AxdrValue::Null => "null-data",
+        AxdrValue::Array(_) => "array",
+        AxdrValue::Structure(_) => "structure",
+        AxdrValue::Boolean(_) => "boolean",
+        AxdrValue::BitString { .. } => "bit-string",
+        AxdrValue::DoubleLong(_) => "double-long",
+        AxdrValue::DoubleLongUnsigned(_) => "double-long-unsigned",
+        AxdrValue::OctetString(_) => "octet-string",
+        AxdrValue::VisibleString(_) => "visible-string",
+        AxdrValue::Utf8String(_) => "utf8-string",
+        AxdrValue::Bcd(_) => "bcd",
+        AxdrValue::Integer(_) => "integer",
+        AxdrValue::Long(_) => "long",
+        AxdrValue::Unsigned(_) => "unsigned",
+        AxdrValue::LongUnsigned(_) => "long-unsigned",
+        AxdrValue::Long64(_) => "long64",
+        AxdrValue::Long64Unsigned(_) => "long64-unsigned",
+        AxdrValue::Enum(_) => "enum",
+        AxdrValue::Float32(_) => "float32",
+        AxdrValue::Float64(_) => "float64",
+        AxdrValue::DateTime(_) => "date-time",
+        AxdrValue::Date(_) => "date",
+        AxdrValue::Time(_) => "time",
+        _ => "unknown",
+    }
+}
+
 pub(crate) fn frame_function_name(function: &m_bus_core::Function) -> String {
     use m_bus_core::Function;
 
@@ -1369,6 +2558,9 @@ fn data_coding_name(coding: DataFieldCoding) -> String {
         DataFieldCoding::DateTimeTypeF => "Date and time (type F)".to_string(),
         DataFieldCoding::DateTimeTypeJ => "Time (type J)".to_string(),
         DataFieldCoding::DateTimeTypeI => "Date and time (type I)".to_string(),
+        DataFieldCoding::DaylightSavingTypeK => "Daylight saving (type K)".to_string(),
+        DataFieldCoding::ListeningWindowTypeL => "Listening window (type L)".to_string(),
+        DataFieldCoding::DateTimeTypeM => "Date and time with offset (type M)".to_string(),
         DataFieldCoding::SpecialFunctions(function) => special_function_name(function),
         _ => "Unknown".to_string(),
     }
@@ -1385,8 +2577,24 @@ fn special_function_name(function: SpecialFunctions) -> String {
     }
 }
 
+/// Names of the quantity labels and of the modifier labels.
+fn label_names(labels: &[ValueLabel]) -> (Vec<String>, Vec<String>) {
+    let (modifiers, quantities): (Vec<_>, Vec<_>) =
+        labels.iter().partition(|label| label.is_modifier());
+    (
+        quantities.into_iter().map(quantity_name).collect(),
+        modifiers.into_iter().map(quantity_name).collect(),
+    )
+}
+
 fn quantity_name(label: &ValueLabel) -> String {
     match label {
+        ValueLabel::MultiplicativeCorrectionFactor(exponent) => {
+            format!("Multiplicative correction factor 1e{exponent}")
+        }
+        ValueLabel::AdditiveCorrectionConstant(exponent) => {
+            format!("Additive correction constant 1e{exponent}")
+        }
         ValueLabel::Date => "Time point (date)".to_string(),
         ValueLabel::Time => "Time point (time)".to_string(),
         ValueLabel::DateTime | ValueLabel::DateTimeWithSeconds => {
@@ -1450,43 +2658,58 @@ fn standardized_word(word: &str, first: bool) -> String {
     }
 }
 
-fn value_output(record: &user_data::DataRecord<'_>) -> ValueOutput {
-    let information = record.data_information();
-    let value_information = record.value_information();
-    let scale = value_information.map(|value| value.decimal_scale_exponent);
-    let offset = record_offset_power10(record);
-
-    match record.value() {
-        Some(DataType::Number(number)) | Some(DataType::LossyNumber(number)) => {
-            if information
-                .is_some_and(|value| value.data_field_coding == DataFieldCoding::Real32Bit)
-            {
-                let (number_value, special) = finite_number(*number);
-                return ValueOutput {
-                    kind: "float".to_string(),
-                    value: number_value
-                        .and_then(serde_json::Number::from_f64)
-                        .map(serde_json::Value::Number)
-                        .or_else(|| special.map(serde_json::Value::String)),
-                };
-            }
+fn value_output(record: &user_data::DataRecord<'_>, utc_offset: Option<i16>) -> ValueOutput {
+    data_value_output(
+        record.value(),
+        record
+            .scaled_value()
+            .map(|scaled| scaled.value)
+            .or_else(|| record.decimal().map(ScaledNumber::Exact)),
+        utc_offset,
+    )
+}
 
-            let significand =
-                exact_significand(record, *number).unwrap_or_else(|| format!("{number:.0}"));
-            let scale_value = scale.unwrap_or(0);
-            let decimal = scaled_decimal(&significand, scale_value, offset);
+/// Output of a value, using the scaled number for numeric values. Integers
+/// without scaled number are rendered as they are.
+fn data_value_output(
+    value: Option<&DataType<'_>>,
+    scaled: Option<ScaledNumber>,
+    utc_offset: Option<i16>,
+) -> ValueOutput {
+    match value {
+        Some(integer @ (DataType::Integer(_) | DataType::LargeInteger(_))) => ValueOutput {
+            kind: "decimal".to_string(),
+            value: scaled
+                .or_else(|| integer.decimal().map(ScaledNumber::Exact))
+                .map(|scaled| serde_json::Value::String(scaled.to_string())),
+            summer_time: false,
+        },
+        Some(DataType::LossyNumber(_)) if matches!(scaled, Some(ScaledNumber::Exact(_))) => {
             ValueOutput {
                 kind: "decimal".to_string(),
-                value: Some(serde_json::Value::String(decimal)),
+                value: scaled.map(|scaled| serde_json::Value::String(scaled.to_string())),
+                summer_time: false,
             }
         }
+        Some(DataType::Number(number)) | Some(DataType::LossyNumber(number)) => {
+            float_output(scaled.map_or(*number, ScaledNumber::to_f64))
+        }
         Some(DataType::Text(text)) => ValueOutput {
             kind: "text".to_string(),
             value: Some(serde_json::Value::String(text.to_string())),
+            summer_time: false,
         },
-        Some(DataType::Date(day, month, year)) => {
-            temporal_value("date", day, month, year, None, None, None)
-        }
+        Some(DataType::Date(day, month, year)) => temporal_value(
+            "date",
+            day,
+            month,
+            year,
+            None,
+            None,
+            None,
+            None,
+            &TimeFlags::default(),
+        ),
         Some(DataType::Time(second, minute, hour)) => temporal_value(
             "time",
             &SingleEveryOrInvalid::Invalid(),
@@ -1495,11 +2718,21 @@ fn value_output(record: &user_data::DataRecord<'_>) -> ValueOutput {
             Some(hour),
             Some(minute),
             Some(second),
+            None,
+            &TimeFlags::default(),
         ),
-        Some(DataType::DateTime(day, month, year, hour, minute)) => {
-            temporal_value("datetime", day, month, year, Some(hour), Some(minute), None)
-        }
-        Some(DataType::DateTimeWithSeconds(day, month, year, hour, minute, second)) => {
+        Some(DataType::DateTime(day, month, year, hour, minute, flags)) => temporal_value(
+            "datetime",
+            day,
+            month,
+            year,
+            Some(hour),
+            Some(minute),
+            None,
+            meter_offset(flags, utc_offset),
+            flags,
+        ),
+        Some(DataType::DateTimeWithSeconds(day, month, year, hour, minute, second, flags)) => {
             temporal_value(
                 "datetime",
                 day,
@@ -1508,19 +2741,46 @@ fn value_output(record: &user_data::DataRecord<'_>) -> ValueOutput {
                 Some(hour),
                 Some(minute),
                 Some(second),
+                meter_offset(flags, utc_offset),
+                flags,
             )
         }
+        Some(DataType::DateTimeWithOffset(
+            day,
+            month,
+            year,
+            hour,
+            minute,
+            second,
+            offset,
+            flags,
+        )) => temporal_value(
+            "datetime",
+            day,
+            month,
+            year,
+            Some(hour),
+            Some(minute),
+            Some(second),
+            Some(*offset),
+            flags,
+        ),
+        Some(DataType::DaylightSaving(period)) => daylight_saving_value(period),
+        Some(DataType::ListeningWindow(window)) => listening_window_value(window),
         Some(DataType::ManufacturerSpecific(_)) => ValueOutput {
             kind: "manufacturer_specific".to_string(),
             value: None,
+            summer_time: false,
         },
         None => ValueOutput {
             kind: "none".to_string(),
             value: None,
+            summer_time: false,
         },
         Some(_) => ValueOutput {
             kind: "unknown".to_string(),
             value: None,
+            summer_time: false,
         },
     }
 }
@@ -1534,7 +2794,10 @@ fn temporal_value(
     hour: Option<&SingleEveryOrInvalid<u8>>,
     minute: Option<&SingleEveryOrInvalid<u8>>,
     second: Option<&SingleEveryOrInvalid<u8>>,
+    offset: Option<i16>,
+    flags: &TimeFlags,
 ) -> ValueOutput {
+    let invalid = flags.invalid;
     let mut components = serde_json::Map::new();
     if kind != "time" {
         components.insert("day".to_string(), component_json(day));
@@ -1550,13 +2813,22 @@ fn temporal_value(
     if let Some(value) = second {
         components.insert("second".to_string(), component_json(value));
     }
+    if let Some(value) = offset {
+        components.insert("offset".to_string(), iso_offset(value).into());
+    }
+    if invalid {
+        components.insert("invalid".to_string(), true.into());
+    }
 
     let value = temporal_iso(day, month, year, hour, minute, second, kind)
+        .filter(|_| !invalid)
+        .map(|iso| iso + &offset.map(iso_offset).unwrap_or_default())
         .map(serde_json::Value::String)
         .unwrap_or_else(|| serde_json::Value::Object(components));
     ValueOutput {
         kind: kind.to_string(),
         value: Some(value),
+        summer_time: flags.summer_time,
     }
 }
 
@@ -1603,8 +2875,91 @@ fn temporal_iso(
     }
 }
 
-fn single<T: Copy>(component: &SingleEveryOrInvalid<T>) -> Option<T> {
-    match component {
+/// Returns the UTC offset of a type F or I timestamp of a meter whose
+/// standard time is `utc_offset` minutes ahead of UTC, assuming summer time
+/// is one hour ahead of standard time.
+fn meter_offset(flags: &TimeFlags, utc_offset: Option<i16>) -> Option<i16> {
+    utc_offset.map(|offset| offset + if flags.summer_time { 60 } else { 0 })
+}
+
+/// Formats an offset of local time to UTC in minutes, e.g. `+01:00`.
+fn iso_offset(offset: i16) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    format!("{sign}{:02}:{:02}", offset / 60, offset % 60)
+}
+
+/// Renders a type K period as recurring ISO 8601 date-times without year,
+/// e.g. `--03-31T02:00`, and the deviation as duration.
+fn daylight_saving_value(period: &user_data::data_information::DaylightSaving) -> ValueOutput {
+    let point = |month: &SingleEveryOrInvalid<Month>, day: u8, hour: u8| {
+        single(month)
+            .and_then(month_number)
+            .filter(|_| (1..=31).contains(&day) && hour < 24)
+            .map_or(serde_json::Value::String("invalid".to_string()), |month| {
+                serde_json::Value::String(format!("--{month:02}-{day:02}T{hour:02}:00"))
+            })
+    };
+    let sign = if period.deviation < 0 { "-" } else { "" };
+    let mut value = serde_json::Map::new();
+    value.insert(
+        "begin".to_string(),
+        point(&period.begin_month, period.begin_day, period.begin_hour),
+    );
+    value.insert(
+        "end".to_string(),
+        point(&period.end_month, period.end_day, period.end_hour()),
+    );
+    value.insert(
+        "deviation".to_string(),
+        format!("{sign}PT{}H", period.deviation.unsigned_abs()).into(),
+    );
+    ValueOutput {
+        kind: "daylight_saving".to_string(),
+        value: Some(serde_json::Value::Object(value)),
+        summer_time: false,
+    }
+}
+
+/// Renders a type L window with ISO 8601 start time, duration and days of
+/// the week, 1 for Monday to 7 for Sunday.
+fn listening_window_value(window: &user_data::data_information::ListeningWindow) -> ValueOutput {
+    let start = temporal_value(
+        "time",
+        &SingleEveryOrInvalid::Invalid(),
+        &SingleEveryOrInvalid::Invalid(),
+        &SingleEveryOrInvalid::Invalid(),
+        Some(&window.start_hour),
+        Some(&window.start_minute),
+        Some(&window.start_second),
+        None,
+        &TimeFlags::default(),
+    );
+    let mut value = serde_json::Map::new();
+    value.insert(
+        "start".to_string(),
+        start.value.unwrap_or(serde_json::Value::Null),
+    );
+    value.insert(
+        "duration".to_string(),
+        format!("PT{}S", window.duration_seconds).into(),
+    );
+    value.insert(
+        "weekdays".to_string(),
+        (1..=7u8)
+            .filter(|weekday| window.is_open_on(*weekday))
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    ValueOutput {
+        kind: "listening_window".to_string(),
+        value: Some(serde_json::Value::Object(value)),
+        summer_time: false,
+    }
+}
+
+fn single<T: Copy>(component: &SingleEveryOrInvalid<T>) -> Option<T> {
+    match component {
         SingleEveryOrInvalid::Single(value) => Some(*value),
         _ => None,
     }
@@ -1629,255 +2984,60 @@ const fn month_number(month: Month) -> Option<u8> {
     })
 }
 
-fn finite_number(value: f64) -> (Option<f64>, Option<String>) {
-    if value.is_nan() {
-        (None, Some("nan".to_string()))
-    } else if value == f64::INFINITY {
-        (None, Some("positive_infinity".to_string()))
-    } else if value == f64::NEG_INFINITY {
-        (None, Some("negative_infinity".to_string()))
-    } else {
-        (Some(value), None)
-    }
-}
-
-fn exact_significand(record: &user_data::DataRecord<'_>, parsed: f64) -> Option<String> {
-    let information = record.data_information()?;
-    let header_size = record.data_record_header.get_size();
-    let data = record.raw_bytes().get(header_size..)?;
-    match information.data_field_coding {
-        DataFieldCoding::Integer8Bit => signed_le_decimal(data.get(..1)?),
-        DataFieldCoding::Integer16Bit => signed_le_decimal(data.get(..2)?),
-        DataFieldCoding::Integer24Bit => signed_le_decimal(data.get(..3)?),
-        DataFieldCoding::Integer32Bit => signed_le_decimal(data.get(..4)?),
-        DataFieldCoding::Integer48Bit => signed_le_decimal(data.get(..6)?),
-        DataFieldCoding::Integer64Bit => signed_le_decimal(data.get(..8)?),
-        DataFieldCoding::BCD2Digit
-        | DataFieldCoding::BCD4Digit
-        | DataFieldCoding::BCD6Digit
-        | DataFieldCoding::BCD8Digit
-        | DataFieldCoding::BCDDigit12 => Some(format!("{parsed:.0}")),
-        DataFieldCoding::VariableLength => exact_variable_significand(data, parsed),
-        _ => None,
-    }
-}
-
-fn exact_variable_significand(data: &[u8], parsed: f64) -> Option<String> {
-    let marker = *data.first()?;
-    match marker {
-        0xC0..=0xC9 | 0xD0..=0xD9 => Some(format!("{parsed:.0}")),
-        0xE0..=0xEF => {
-            let length = usize::from(marker - 0xE0);
-            signed_le_decimal(data.get(1..=length)?)
-        }
-        0xF0..=0xF4 => {
-            let length = usize::from(marker - 0xEC) * 4;
-            signed_le_decimal(data.get(1..=length)?)
-        }
-        0xF5 => signed_le_decimal(data.get(1..=48)?),
-        0xF6 => signed_le_decimal(data.get(1..=64)?),
-        _ => None,
+fn number_output(number: ScaledNumber) -> ValueOutput {
+    match number {
+        ScaledNumber::Real(value) => float_output(value),
+        exact => ValueOutput {
+            kind: "decimal".to_string(),
+            value: Some(serde_json::Value::String(exact.to_string())),
+            summer_time: false,
+        },
     }
 }
 
-fn signed_le_decimal(bytes: &[u8]) -> Option<String> {
-    if bytes.is_empty() {
-        return None;
-    }
-    let negative = bytes.last().is_some_and(|byte| byte & 0x80 != 0);
-    let magnitude = if negative {
-        let mut value: Vec<u8> = bytes.iter().map(|byte| !byte).collect();
-        let mut carry = 1u16;
-        for byte in &mut value {
-            let sum = u16::from(*byte) + carry;
-            *byte = sum as u8;
-            carry = sum >> 8;
-            if carry == 0 {
-                break;
-            }
-        }
-        value
-    } else {
-        bytes.to_vec()
-    };
-    let mut digits = vec![0u8];
-    for byte in magnitude.iter().rev() {
-        let mut carry = u16::from(*byte);
-        for digit in &mut digits {
-            let value = u16::from(*digit) * 256 + carry;
-            *digit = (value % 10) as u8;
-            carry = value / 10;
-        }
-        while carry != 0 {
-            digits.push((carry % 10) as u8);
-            carry /= 10;
-        }
-    }
-    while digits.len() > 1 && digits.last() == Some(&0) {
-        digits.pop();
-    }
-    let mut value: String = digits
-        .iter()
-        .rev()
-        .map(|digit| char::from(b'0' + *digit))
-        .collect();
-    if negative && value != "0" {
-        value.insert(0, '-');
+fn float_output(number: f64) -> ValueOutput {
+    let (number_value, special) = finite_number(number);
+    ValueOutput {
+        kind: "float".to_string(),
+        value: number_value
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .or_else(|| special.map(serde_json::Value::String)),
+        summer_time: false,
     }
-    Some(value)
 }
 
-fn record_offset_power10(record: &user_data::DataRecord<'_>) -> Option<isize> {
-    let block = record
-        .data_record_header
-        .raw_data_record_header
-        .value_information_block
-        .as_ref()?;
-    block
-        .value_information_extension
-        .as_ref()?
-        .iter()
-        .filter_map(|extension| {
-            let code = extension.data & 0x7F;
-            (0x78..=0x7B)
-                .contains(&code)
-                .then(|| isize::from(extension.data & 0b11) - 3)
-        })
-        .next_back()
-}
-
-fn scaled_decimal(significand: &str, scale: isize, offset: Option<isize>) -> String {
-    let (negative, digits) = split_sign(significand);
-    let minimum_exponent = offset.map_or(scale, |value| value.min(scale));
-    let mut left = digits.to_string();
-    left.push_str(&"0".repeat(usize::try_from(scale - minimum_exponent).unwrap_or(0)));
-    let signed_left = if negative { format!("-{left}") } else { left };
-    let integer = if let Some(offset_exponent) = offset {
-        let mut right = "1".to_string();
-        right.push_str(
-            &"0".repeat(usize::try_from(offset_exponent - minimum_exponent).unwrap_or(0)),
-        );
-        add_signed_decimal_integers(&signed_left, &right)
-    } else {
-        signed_left
+/// Value and unit of a record converted to the units of a unit system.
+fn converted_output(scaled: &ScaledValue, system: UnitSystem) -> (ValueOutput, Option<String>) {
+    let converted = scaled.to_unit_system(system);
+    let value = match converted.value {
+        ScaledNumber::Real(value) => float_output(value),
+        _ => ValueOutput {
+            kind: "decimal".to_string(),
+            value: Some(serde_json::Value::String(converted.value.to_string())),
+            summer_time: false,
+        },
     };
-    apply_power10(&integer, minimum_exponent)
-}
-
-fn split_sign(value: &str) -> (bool, &str) {
-    value
-        .strip_prefix('-')
-        .map_or((false, value), |digits| (true, digits))
+    let unit = (!converted.units.is_empty()).then(|| unit_output(converted.units.as_slice()));
+    (value, unit)
 }
 
-fn add_signed_decimal_integers(left: &str, right: &str) -> String {
-    let (left_negative, left_digits) = split_sign(left);
-    let (right_negative, right_digits) = split_sign(right);
-    if left_negative == right_negative {
-        let result = add_abs(left_digits, right_digits);
-        if left_negative && result != "0" {
-            format!("-{result}")
-        } else {
-            result
-        }
+fn finite_number(value: f64) -> (Option<f64>, Option<String>) {
+    if value.is_nan() {
+        (None, Some("nan".to_string()))
+    } else if value == f64::INFINITY {
+        (None, Some("positive_infinity".to_string()))
+    } else if value == f64::NEG_INFINITY {
+        (None, Some("negative_infinity".to_string()))
     } else {
-        match compare_abs(left_digits, right_digits) {
-            std::cmp::Ordering::Equal => "0".to_string(),
-            std::cmp::Ordering::Greater => {
-                let result = subtract_abs(left_digits, right_digits);
-                if left_negative {
-                    format!("-{result}")
-                } else {
-                    result
-                }
-            }
-            std::cmp::Ordering::Less => {
-                let result = subtract_abs(right_digits, left_digits);
-                if right_negative {
-                    format!("-{result}")
-                } else {
-                    result
-                }
-            }
-        }
-    }
-}
-
-fn add_abs(left: &str, right: &str) -> String {
-    let mut output = Vec::new();
-    let mut carry = 0u8;
-    let mut left = left.bytes().rev();
-    let mut right = right.bytes().rev();
-    loop {
-        let a = left.next().map(|value| value - b'0');
-        let b = right.next().map(|value| value - b'0');
-        if a.is_none() && b.is_none() && carry == 0 {
-            break;
-        }
-        let sum = a.unwrap_or(0) + b.unwrap_or(0) + carry;
-        output.push(b'0' + sum % 10);
-        carry = sum / 10;
-    }
-    output.reverse();
-    String::from_utf8(output).unwrap_or_else(|_| "0".to_string())
-}
-
-fn subtract_abs(larger: &str, smaller: &str) -> String {
-    let mut output = Vec::new();
-    let mut borrow = 0i8;
-    let mut smaller = smaller.bytes().rev();
-    for value in larger.bytes().rev() {
-        let mut digit = (value - b'0') as i8 - borrow;
-        let sub = smaller.next().map_or(0, |item| (item - b'0') as i8);
-        if digit < sub {
-            digit += 10;
-            borrow = 1;
-        } else {
-            borrow = 0;
-        }
-        output.push(b'0' + u8::try_from(digit - sub).unwrap_or(0));
-    }
-    while output.len() > 1 && output.last() == Some(&b'0') {
-        output.pop();
+        (Some(value), None)
     }
-    output.reverse();
-    String::from_utf8(output).unwrap_or_else(|_| "0".to_string())
-}
-
-fn compare_abs(left: &str, right: &str) -> std::cmp::Ordering {
-    let left = left.trim_start_matches('0');
-    let right = right.trim_start_matches('0');
-    left.len().cmp(&right.len()).then_with(|| left.cmp(right))
 }
 
-fn apply_power10(integer: &str, exponent: isize) -> String {
-    let (negative, digits) = split_sign(integer);
-    if digits.bytes().all(|digit| digit == b'0') {
-        return "0".to_string();
-    }
-    let mut output = if exponent >= 0 {
-        let mut value = digits.to_string();
-        value.push_str(&"0".repeat(usize::try_from(exponent).unwrap_or(0)));
-        value
-    } else {
-        let places = exponent.unsigned_abs();
-        if digits.len() > places {
-            let split = digits.len() - places;
-            format!("{}.{}", &digits[..split], &digits[split..])
-        } else {
-            format!("0.{}{}", "0".repeat(places - digits.len()), digits)
-        }
-    };
-    while output.contains('.') && output.ends_with('0') {
-        output.pop();
-    }
-    if output.ends_with('.') {
-        output.pop();
-    }
-    if negative && output != "0" {
-        output.insert(0, '-');
-    }
-    output
+fn scaled_decimal(value: Decimal, scale: isize) -> String {
+    value
+        .scaled(i16::try_from(scale).unwrap_or_default())
+        .to_string()
 }
 
 fn unit_output(units: &[Unit]) -> String {
@@ -1900,6 +3060,7 @@ fn ucum_component(unit: &Unit) -> Option<String> {
         UnitName::Celsius => "Cel",
         UnitName::Kelvin => "K",
         UnitName::Bar => "bar",
+        UnitName::Pascal => "Pa",
         UnitName::Second => "s",
         UnitName::Minute => "min",
         UnitName::Hour => "h",
@@ -1971,6 +3132,13 @@ fn render_csv(decoded: &DecodedOutput) -> Result<String, OutputError> {
                 .map(|field| format!("record_{}_{field}", record.index)),
         );
     }
+    // appended after all records to keep the positions of earlier columns
+    headers.extend(
+        decoded
+            .records
+            .iter()
+            .map(|record| format!("record_{}_modifiers", record.index)),
+    );
     writer
         .write_record(&headers)
         .map_err(|error| OutputError::Serialization {
@@ -1982,6 +3150,12 @@ fn render_csv(decoded: &DecodedOutput) -> Result<String, OutputError> {
     for record in &decoded.records {
         row.extend(csv_record_values(record));
     }
+    row.extend(
+        decoded
+            .records
+            .iter()
+            .map(|record| record.modifiers.join("|")),
+    );
     writer
         .write_record(row)
         .map_err(|error| OutputError::Serialization {
@@ -2087,13 +3261,33 @@ fn value_display(record: &RecordOutput) -> String {
             .collect::<Vec<_>>()
             .join(", "),
         Some(value) => value.to_string(),
-        None if record.value.kind == "manufacturer_specific" => record.data_hex.replace(' ', ""),
+        None if matches!(
+            record.value.kind.as_str(),
+            "manufacturer_specific" | "compact_profile"
+        ) =>
+        {
+            record.data_hex.replace(' ', "")
+        }
         None if record.value.kind == "none" => "No data".to_string(),
         None if record.value.kind == "unknown" => "Unsupported value type".to_string(),
         None => String::new(),
     }
 }
 
+fn billing_period_display(period: &BillingPeriodOutput) -> String {
+    let mut display = format!("{} ({} units)", period.consumption, period.units);
+    if let Some(date) = period
+        .date
+        .as_ref()
+        .and_then(|date| date.value.as_ref())
+        .and_then(serde_json::Value::as_str)
+    {
+        display.push_str(" · ");
+        display.push_str(date);
+    }
+    display
+}
+
 fn record_hex(record: &RecordOutput) -> String {
     match (record.header_hex.is_empty(), record.data_hex.is_empty()) {
         (false, false) => format!("{} {}", record.header_hex, record.data_hex),
@@ -2168,6 +3362,24 @@ fn render_table(decoded: &DecodedOutput, width: usize) -> Result<String, OutputE
             enrichment.manufacturer_description.clone(),
         ));
     }
+    if let Some(allocation) = &decoded.heat_cost_allocation {
+        for (label, period) in [
+            ("Current period", &allocation.current),
+            ("Previous period", &allocation.previous),
+        ] {
+            if let Some(period) = period {
+                summary.push((label.to_string(), billing_period_display(period)));
+            }
+        }
+    }
+    if !decoded.conditions.is_empty() {
+        let names: Vec<_> = decoded
+            .conditions
+            .iter()
+            .map(|condition| condition.name.as_str())
+            .collect();
+        summary.push(("Conditions".to_string(), names.join(", ")));
+    }
     output.push_str(&key_value_box(&summary, width));
 
     if !decoded.records.is_empty() {
@@ -2259,6 +3471,25 @@ fn render_table(decoded: &DecodedOutput, width: usize) -> Result<String, OutputE
             }
         }
     }
+    for profile in &decoded.profiles {
+        output.push_str(&format!(
+            "Profile of record {} · {}\n",
+            profile.record_index,
+            profile_spacing_display(profile)
+        ));
+        let rows = profile
+            .points
+            .iter()
+            .map(|point| {
+                let (timestamp, value) = profile_point_display(profile, point);
+                (
+                    timestamp,
+                    format!("{}: {value}", profile.quantities.join(", ")),
+                )
+            })
+            .collect::<Vec<_>>();
+        output.push_str(&key_value_box(&rows, width));
+    }
     if !decoded.diagnostics.is_empty() {
         output.push_str("Diagnostics\n");
         let rows = decoded
@@ -2276,11 +3507,51 @@ fn render_table(decoded: &DecodedOutput, width: usize) -> Result<String, OutputE
     Ok(output)
 }
 
+fn profile_spacing_display(profile: &ProfileOutput) -> String {
+    let spacing = match (profile.spacing_seconds, profile.spacing_months) {
+        (_, Some(1)) => "every month".to_string(),
+        (_, Some(months)) => format!("every {months} months"),
+        (Some(seconds), None) if seconds % 86_400 == 0 => format!("every {} d", seconds / 86_400),
+        (Some(seconds), None) if seconds % 3600 == 0 => format!("every {} h", seconds / 3600),
+        (Some(seconds), None) if seconds % 60 == 0 => format!("every {} min", seconds / 60),
+        (Some(seconds), None) => format!("every {seconds} s"),
+        (None, None) => String::new(),
+    };
+    format!("{} · {spacing}", profile.increment_mode.replace('_', " "))
+}
+
+/// Returns the timestamp, or the element index without base time, and the
+/// value with unit of a profile point.
+fn profile_point_display(profile: &ProfileOutput, point: &ProfilePointOutput) -> (String, String) {
+    let timestamp = match point
+        .timestamp
+        .as_ref()
+        .and_then(|time| time.value.as_ref())
+    {
+        Some(serde_json::Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+        None => format!("element {}", point.index),
+    };
+    let value = match &point.value.value {
+        Some(serde_json::Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+        None => "—".to_string(),
+    };
+    let unit = profile
+        .unit
+        .as_ref()
+        .map(|unit| format!(" {unit}"))
+        .unwrap_or_default();
+    (timestamp, format!("{value}{unit}"))
+}
+
 fn reading(record: &RecordOutput) -> String {
-    let quantity = if record.quantities.is_empty() {
-        "Reading".to_string()
-    } else {
-        record.quantities.join(", ")
+    let labels = [record.quantities.as_slice(), record.modifiers.as_slice()].concat();
+    let quantity = match (labels.is_empty(), record.obis.as_deref()) {
+        (true, Some(obis)) => obis.to_string(),
+        (true, None) => "Reading".to_string(),
+        (false, Some(obis)) => format!("{} ({obis})", labels.join(", ")),
+        (false, None) => labels.join(", "),
     };
     let unit = record
         .unit
@@ -2290,6 +3561,85 @@ fn reading(record: &RecordOutput) -> String {
     format!("{quantity}: {}{unit}", value_display(record))
 }
 
+/// Renders the time series as one row per reading, by storage number.
+fn render_history(decoded: &DecodedOutput, width: usize) -> Result<String, OutputError> {
+    if width < MINIMUM_TABLE_WIDTH {
+        return Err(OutputError::InvalidOption {
+            option: "table_width",
+            message: format!("must be at least {MINIMUM_TABLE_WIDTH} columns"),
+        });
+    }
+    let series = decoded.time_series();
+    let mut points = series
+        .iter()
+        .flat_map(|series| series.points.iter().map(move |point| (series, point)))
+        .collect::<Vec<_>>();
+    points.sort_by_key(|(_, point)| point.storage_number);
+    let rows = points
+        .into_iter()
+        .map(|(series, point)| {
+            let timestamp = match &point.timestamp {
+                Some(serde_json::Value::String(value)) => value.clone(),
+                Some(value) => value.to_string(),
+                None => format!("storage {}", point.storage_number),
+            };
+            let mut quantity = [series.quantities.as_slice(), series.modifiers.as_slice()]
+                .concat()
+                .join(", ");
+            if series.function != record_function_name(FunctionField::InstantaneousValue) {
+                quantity.push_str(&format!(" · {}", series.function));
+            }
+            if series.tariff != 0 {
+                quantity.push_str(&format!(" · tariff {}", series.tariff));
+            }
+            if series.subunit != 0 {
+                quantity.push_str(&format!(" · subunit {}", series.subunit));
+            }
+            let value = match &point.value.value {
+                Some(serde_json::Value::String(value)) => value.clone(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            };
+            vec![
+                timestamp,
+                quantity,
+                value,
+                series.unit.clone().unwrap_or_default(),
+            ]
+        })
+        .chain(decoded.profiles.iter().flat_map(|profile| {
+            profile.points.iter().map(move |point| {
+                let (timestamp, _) = profile_point_display(profile, point);
+                let value = match &point.value.value {
+                    Some(serde_json::Value::String(value)) => value.clone(),
+                    Some(value) => value.to_string(),
+                    None => String::new(),
+                };
+                vec![
+                    timestamp,
+                    [profile.quantities.as_slice(), profile.modifiers.as_slice()]
+                        .concat()
+                        .join(", "),
+                    value,
+                    profile.unit.clone().unwrap_or_default(),
+                ]
+            })
+        }))
+        .collect::<Vec<_>>();
+    let content = width - 13;
+    let timestamp_width = (content * 28 / 100).clamp(8, 25);
+    let value_width = (content * 20 / 100).max(6);
+    let unit_width = (content * 12 / 100).clamp(4, 10);
+    let quantity_width = content
+        .saturating_sub(timestamp_width + value_width + unit_width)
+        .max(1);
+    Ok(box_table(
+        &["Timestamp", "Quantity", "Value", "Unit"],
+        &rows,
+        &[timestamp_width, quantity_width, value_width, unit_width],
+    ))
+}
+
 fn key_value_box(rows: &[(String, String)], width: usize) -> String {
     let content = width.saturating_sub(7);
     let key_width = (content / 3).clamp(10, 24);
@@ -2648,7 +3998,9 @@ mod tests {
 
     #[test]
     fn standalone_records_use_the_canonical_record_schema() {
-        let records = decode_data_records(&[0x03, 0x13, 0x15, 0x31, 0x00]).unwrap();
+        let records =
+            decode_data_records(&[0x03, 0x13, 0x15, 0x31, 0x00], &DecodeOptions::default())
+                .unwrap();
         assert_eq!(records.len(), 1);
         assert_eq!(records[0].function, "Instantaneous value");
         assert_eq!(records[0].quantities, ["Volume"]);
@@ -2686,18 +4038,1055 @@ mod tests {
     }
 
     #[test]
-    fn arbitrary_signed_integer_conversion_is_exact() {
-        assert_eq!(signed_le_decimal(&[0xFF]), Some("-1".to_string()));
+    fn arbitrary_signed_integer_conversion_is_exact() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x07, 0x03, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F, //
+            0x01, 0x03, 0xFF,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let values: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| record.value.value.clone())
+            .collect();
+        assert_eq!(
+            values,
+            [
+                Some(serde_json::Value::String(i64::MAX.to_string())),
+                Some(serde_json::Value::String("-1".to_string())),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn scaled_decimal_applies_additive_offset() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(scaled_decimal(Decimal::from(876_543i64), -3), "876.543");
+        let value = |data: &[u8]| -> Result<_, user_data::DataRecordError> {
+            Ok(value_output(&user_data::DataRecord::try_from(data)?, None).value)
+        };
+        assert_eq!(
+            value(&[0x01, 0x83, 0x7B, 0x0A])?,
+            Some(serde_json::Value::String("11".to_string()))
+        );
+        assert_eq!(
+            value(&[0x01, 0x82, 0x7A, 0xF6])?,
+            Some(serde_json::Value::String("-0.9".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn integers_without_scaled_number_are_plain_decimals() -> Result<(), Box<dyn std::error::Error>>
+    {
+        assert_eq!(
+            serde_json::to_value(data_value_output(Some(&DataType::Integer(-5)), None, None))?,
+            serde_json::json!({"kind": "decimal", "value": "-5"})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wide_integers_keep_exact_decimals() -> Result<(), Box<dyn std::error::Error>> {
+        let value = |extension: u8| -> Result<_, Box<dyn std::error::Error>> {
+            // volume in 10^-3 m³ as a 48 byte binary integer
+            let mut data = vec![0x0D, 0x13, 0xF5, 0x39, 0x30];
+            data.resize(50, 0x00);
+            data.push(extension);
+            let record = user_data::DataRecord::try_from(data.as_slice())?;
+            Ok(serde_json::to_value(value_output(&record, None))?)
+        };
+        assert_eq!(
+            value(0x00)?,
+            serde_json::json!({"kind": "decimal", "value": "12.345"})
+        );
+        assert_eq!(value(0x01)?.get("kind"), Some(&"float".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn modifiers_are_listed_apart_from_quantities() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            // energy at phase L1
+            0x04, 0x83, 0xFC, 0x01, 0xE8, 0x03, 0x00, 0x00, //
+            // volume in 10^-3 m³ with a correction factor of 10^-1
+            0x02, 0x93, 0x75, 0x0A, 0x00, //
+            // type H flow temperature of 452.0 * 10^-1 °C
+            0x05, 0x5A, 0x00, 0x00, 0xE2, 0x43,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let labels: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| (record.quantities.clone(), record.modifiers.clone()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                (vec!["Energy".to_string()], vec!["At phase L1".to_string()]),
+                (
+                    vec!["Volume".to_string()],
+                    vec!["Multiplicative correction factor 1e-1".to_string()]
+                ),
+                (vec!["Flow temperature".to_string()], vec![]),
+            ]
+        );
+        let values: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| record.value.value.clone())
+            .collect();
+        assert_eq!(
+            values,
+            [
+                Some(serde_json::Value::String("1000".into())),
+                Some(serde_json::Value::String("0.001".into())),
+                Some(serde_json::json!(45.2)),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn registered_decoders_replace_manufacturer_records() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use manufacturer_specific::{ManufacturerDecoder, MeterIdentity};
+        use user_data::data_information::DataType;
+        use user_data::data_record::DataRecord;
+
+        /// Reads a status byte followed by a 16-bit counter.
+        struct Pad;
+
+        impl ManufacturerDecoder for Pad {
+            fn supports(&self, meter: &MeterIdentity) -> bool {
+                meter.manufacturer.code == ['P', 'A', 'D']
+            }
+
+            fn decode<'a>(
+                &self,
+                _meter: &MeterIdentity,
+                record: &DataRecord<'a>,
+                sink: &mut dyn FnMut(ManufacturerValue<'a>),
+            ) -> bool {
+                let Some(&[status, low, high]) = record.manufacturer_data() else {
+                    return false;
+                };
+                sink(
+                    ManufacturerValue::new("Status", DataType::Integer(status.into()))
+                        .with_flags(&[(0x01, "leak"), (0x04, "backflow")]),
+                );
+                sink(
+                    ManufacturerValue::new(
+                        "Counter",
+                        DataType::Integer(u16::from_le_bytes([low, high]).into()),
+                    )
+                    .with_scale(-1),
+                );
+                true
+            }
+        }
+
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x04, 0x13, 0xE8, 0x03, 0x00, 0x00, //
+            0x0F, 0x05, 0x0A, 0x00,
+        ])?;
+        let undecoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(undecoded.records.len(), 2);
+
+        let mut registry = ManufacturerRegistry::new();
+        registry.register(Pad);
+        let options = DecodeOptions {
+            manufacturer_decoders: Arc::new(registry),
+            ..DecodeOptions::default()
+        };
+        let decoded = decode_bytes(&frame, &options)?;
+        let values: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| {
+                (
+                    record.quantities.clone(),
+                    record.value.kind.clone(),
+                    record.value.value.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            values,
+            [
+                (
+                    vec!["Volume".to_string()],
+                    "decimal".to_string(),
+                    Some(serde_json::Value::String("1".into()))
+                ),
+                (
+                    vec!["Status".to_string()],
+                    "flags".to_string(),
+                    Some(serde_json::json!(["leak", "backflow"]))
+                ),
+                (
+                    vec!["Counter".to_string()],
+                    "decimal".to_string(),
+                    Some(serde_json::Value::String("1".into()))
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn kamstrup_info_codes_are_decoded_by_default() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x2D, 0x2C, 0x1B, 0x04, 0x55, 0x00, 0x00, 0x00, //
+            0x04, 0x06, 0xE8, 0x03, 0x00, 0x00, //
+            0x04, 0xFF, 0x22, 0x00, 0x02, 0x00, 0x00,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let info_code = decoded.records.last().ok_or("missing info code")?;
+        assert_eq!(info_code.quantities, ["Info code"]);
+        assert_eq!(info_code.value.kind, "flags");
+        assert_eq!(
+            info_code.value.value,
+            Some(serde_json::json!(["Wrong flow direction"]))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn manufacturer_specific_payloads_are_decoded() -> Result<(), Box<dyn std::error::Error>> {
+        let decoded = decode_hex(
+            "2F446850313233347462A2069F255900B029310000000306060906030609070606050509050505050407040605070500",
+            &DecodeOptions::default(),
+        )?;
+        assert_eq!(
+            decoded.transport.header_kind.as_deref(),
+            Some("manufacturer_specific")
+        );
+        let volumes: Vec<_> = decoded
+            .records
+            .iter()
+            .filter(|record| record.quantities == ["Volume"])
+            .map(|record| {
+                (
+                    record.storage_number,
+                    record.value.value.clone(),
+                    record.unit.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            volumes,
+            [
+                (0, Some("13.8".into()), Some("m3".to_string())),
+                (1, Some("8.9".into()), Some("m3".to_string())),
+            ]
+        );
+
+        let unknown = decode_hex(
+            "2F446850313233347562A2069F255900B029310000000306060906030609070606050509050505050407040605070500",
+            &DecodeOptions::default(),
+        )?;
+        assert!(unknown.records.is_empty());
+        assert!(unknown
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == "application.manufacturer_specific"));
+        Ok(())
+    }
+
+    #[test]
+    fn heat_cost_allocator_billing_periods_are_reported() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x08, 0x55, 0x00, 0x00, 0x00, //
+            0x0B, 0x6E, 0x31, 0x01, 0x00, //
+            0x42, 0x6C, 0x7F, 0x2C, //
+            0x4B, 0x6E, 0x02, 0x10, 0x00, //
+            0x02, 0xFB, 0xE8, 0x73, 0xDC, 0x05,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let allocation = decoded
+            .heat_cost_allocation
+            .as_ref()
+            .ok_or("missing heat cost allocation")?;
+        assert_eq!(allocation.rating_factor.as_deref(), Some("1.5"));
+        let current = allocation
+            .current
+            .as_ref()
+            .ok_or("missing current period")?;
+        assert_eq!(
+            (current.units.as_str(), current.consumption.as_str()),
+            ("131", "196.5")
+        );
+        let previous = allocation
+            .previous
+            .as_ref()
+            .ok_or("missing previous period")?;
+        assert_eq!(
+            billing_period_display(previous),
+            "1503 (1002 units) · 2019-12-31"
+        );
+        assert!(render_table(&decoded, DEFAULT_TABLE_WIDTH)?.contains("Previous period"));
+
+        let water_meter = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x0B, 0x6E, 0x31, 0x01, 0x00,
+        ])?;
+        let decoded = decode_bytes(&water_meter, &DecodeOptions::default())?;
+        assert!(decoded.heat_cost_allocation.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn history_groups_records_by_storage_number() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x04, 0x13, 0xE8, 0x03, 0x00, 0x00, //
+            0x02, 0x6C, 0x21, 0x3C, //
+            0x44, 0x13, 0x84, 0x03, 0x00, 0x00, //
+            0x42, 0x6C, 0x3F, 0x3A, //
+            0x84, 0x01, 0x13, 0x20, 0x03, 0x00, 0x00, //
+            0x02, 0x5A, 0xC4, 0x01,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let series = decoded.time_series();
+        assert_eq!(series.len(), 2);
+        let volume = series.first().ok_or("missing volume series")?;
+        assert_eq!(volume.quantities, ["Volume"]);
+        let points: Vec<_> = volume
+            .points
+            .iter()
+            .map(|point| {
+                (
+                    point.storage_number,
+                    point.timestamp.clone(),
+                    point.value.value.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            points,
+            [
+                (0, Some("2025-12-01".into()), Some("1".into())),
+                (1, Some("2025-10-31".into()), Some("0.9".into())),
+                (2, None, Some("0.8".into())),
+            ]
+        );
+
+        let table = render_history(&decoded, DEFAULT_TABLE_WIDTH)?;
+        let rows: Vec<_> = table
+            .lines()
+            .filter(|line| line.starts_with('│'))
+            .map(|line| {
+                line.split('│')
+                    .map(str::trim)
+                    .filter(|cell| !cell.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                vec!["Timestamp", "Quantity", "Value", "Unit"],
+                vec!["2025-12-01", "Volume", "1", "m3"],
+                vec!["2025-12-01", "Flow temperature", "45.2", "Cel"],
+                vec!["2025-10-31", "Volume", "0.9", "m3"],
+                vec!["storage 2", "Volume", "0.8", "m3"],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn records_carry_register_keys() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x04, 0x55, 0x00, 0x00, 0x00, //
+            0x04, 0x06, 0x10, 0x27, 0x00, 0x00, //
+            0x84, 0x10, 0x06, 0x10, 0x27, 0x00, 0x00, //
+            0x44, 0x0E, 0x84, 0x03, 0x00, 0x00, //
+            0x0F, 0x01, 0x02,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let registers: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| record.register.as_deref())
+            .collect();
+        assert_eq!(
+            registers,
+            [
+                Some("energy.storage0"),
+                Some("energy.tariff1.storage0"),
+                Some("energy.storage1"),
+                None,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plaintext_vif_order_is_a_decode_option() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x02, 0xFC, 0x03, 0x48, 0x52, 0x25, 0x74, 0x44, 0x0D, //
+            0x02, 0xFC, 0x74, 0x03, 0x48, 0x52, 0x25, 0x44, 0x0D,
+        ])?;
+        let values = |order| -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
+            let options = DecodeOptions {
+                plaintext_vif_order: order,
+                ..DecodeOptions::default()
+            };
+            Ok(decode_bytes(&frame, &options)?
+                .records
+                .iter()
+                .filter_map(|record| record.value.value.clone())
+                .collect())
+        };
+        let reading = serde_json::Value::from("33.96");
+        assert_eq!(
+            values(PlaintextVifOrder::Detect)?,
+            [reading.clone(), reading]
+        );
+        assert_eq!(values(PlaintextVifOrder::BeforeExtensions)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn plaintext_vif_order_applies_to_xml_annotations_and_records(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let records = [
+            0x02, 0xFC, 0x03, 0x48, 0x52, 0x25, 0x74, 0x44, 0x0D, //
+            0x02, 0xFC, 0x74, 0x03, 0x48, 0x52, 0x25, 0x44, 0x0D,
+        ];
+        let mut user_data = vec![
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00,
+        ];
+        user_data.extend_from_slice(&records);
+        let frame = long_frame(&user_data)?;
+        let counts = |order| -> Result<[usize; 3], Box<dyn std::error::Error>> {
+            let options = RenderOptions {
+                decode: DecodeOptions {
+                    plaintext_vif_order: order,
+                    ..DecodeOptions::default()
+                },
+                ..RenderOptions::default()
+            };
+            let xml = render_bytes(&frame, OutputFormat::Xml, &options)?;
+            let segments = crate::annotate::annotate_frame_with_plaintext_vif_order(&frame, order)?;
+            let decoded = decode_data_records(&records, &options.decode);
+            Ok([
+                xml.matches("<DataRecord id=").count(),
+                segments
+                    .iter()
+                    .filter(|segment| segment.kind == crate::annotate::SegmentKind::Dif)
+                    .count(),
+                decoded.map_or(0, |records| records.len()),
+            ])
+        };
+        assert_eq!(counts(PlaintextVifOrder::Detect)?, [2, 2, 2]);
+        assert_eq!(counts(PlaintextVifOrder::BeforeExtensions)?, [1, 1, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn status_bits_are_named_conditions() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x12, 0x00, 0x00, //
+            0x04, 0x13, 0x10, 0x27, 0x00, 0x00,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let conditions: Vec<_> = decoded
+            .conditions
+            .iter()
+            .map(|condition| (condition.id.as_str(), condition.source.as_str()))
+            .collect();
+        assert_eq!(
+            conditions,
+            [
+                ("application_error", "status"),
+                ("temporary_error", "status"),
+            ]
+        );
+        assert!(render_table(&decoded, DEFAULT_TABLE_WIDTH)?
+            .contains("Application error, Temporary error"));
+        Ok(())
+    }
+
+    #[test]
+    fn compact_profiles_are_expanded() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x02, 0x55, 0x00, 0x00, 0x00, //
+            0x04, 0x03, 0x10, 0x27, 0x00, 0x00, //
+            0x04, 0x6D, 0x00, 0x00, 0x21, 0x31, //
+            0x0D, 0x83, 0x1F, 0x06, 0x62, 0x0F, 0x64, 0x00, 0xFF, 0xFF, //
+            0x0D, 0x83, 0x1F, 0x03, 0x01, 0xFB, 0x00,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let record = decoded.records.get(2).ok_or("missing profile record")?;
+        assert_eq!(record.value.kind, "compact_profile");
+        assert!(decoded
+            .time_series()
+            .iter()
+            .all(|series| series.modifiers.is_empty()));
+
+        let profile = decoded.profiles.first().ok_or("missing profile")?;
+        assert_eq!(decoded.profiles.len(), 1);
+        assert_eq!(
+            (
+                profile.record_index,
+                profile.kind.as_str(),
+                profile.increment_mode.as_str()
+            ),
+            (2, "compact", "increments")
+        );
+        assert_eq!(profile.spacing_seconds, Some(54_000));
+        assert_eq!(profile.quantities, ["Energy"]);
+        assert!(profile.modifiers.is_empty());
+        let points: Vec<_> = profile
+            .points
+            .iter()
+            .map(|point| {
+                (
+                    point.timestamp.as_ref().and_then(|time| time.value.clone()),
+                    point.value.value.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            points,
+            [
+                (Some("2024-12-31T09:00:00".into()), Some("9900".into())),
+                (Some("2024-12-30T18:00:00".into()), None),
+            ]
+        );
+        assert!(decoded
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == "application.compact_profile_invalid"));
+
+        let table = render_history(&decoded, DEFAULT_TABLE_WIDTH)?;
+        assert!(table.contains("2024-12-31T09:00:00"));
+        Ok(())
+    }
+
+    #[test]
+    fn unit_system_converts_record_values() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x04, 0x06, 0xE8, 0x03, 0x00, 0x00, //
+            0x02, 0x5A, 0xC4, 0x01,
+        ])?;
+        let values = |unit_system| -> Result<Vec<_>, OutputError> {
+            let decoded = decode_bytes(
+                &frame,
+                &DecodeOptions {
+                    unit_system,
+                    ..DecodeOptions::default()
+                },
+            )?;
+            Ok(decoded
+                .records
+                .iter()
+                .map(|record| (record.value.value.clone(), record.unit.clone()))
+                .collect())
+        };
+        let decimal = |value: &str| Some(serde_json::Value::String(value.to_string()));
+        let unit = |value: &str| Some(value.to_string());
+
+        assert_eq!(
+            values(None)?,
+            [
+                (decimal("1000000"), unit("W.h")),
+                (decimal("45.2"), unit("Cel")),
+            ]
+        );
+        assert_eq!(
+            values(Some(UnitSystem::Si))?,
+            [
+                (decimal("3600000000"), unit("J")),
+                (decimal("318.35"), unit("K")),
+            ]
+        );
+        assert_eq!(
+            values(Some(UnitSystem::Us))?,
+            [
+                (decimal("1000000"), unit("W.h")),
+                (decimal("113.36"), unit("[degF]")),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn inexact_unit_conversion_is_reported_as_float() -> Result<(), Box<dyn std::error::Error>> {
+        // 1 m³/h is 1/3600 m³/s, which has no finite decimal expansion.
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x02, 0x3E, 0x01, 0x00,
+        ])?;
+        let decoded = decode_bytes(
+            &frame,
+            &DecodeOptions {
+                unit_system: Some(UnitSystem::Si),
+                ..DecodeOptions::default()
+            },
+        )?;
+        let record = decoded.records.first().ok_or("missing record")?;
+        assert_eq!(record.value.kind, "float");
+        let value = record
+            .value
+            .value
+            .as_ref()
+            .and_then(serde_json::Value::as_f64)
+            .ok_or("missing value")?;
+        assert!((value - 1.0 / 3600.0).abs() < 1e-12);
+        assert_eq!(record.unit.as_deref(), Some("m3.s-1"));
+        Ok(())
+    }
+
+    #[test]
+    fn records_after_invalid_record_are_decoded() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x04, 0x06, 0xE8, 0x03, 0x00, 0x00, //
+            0x0A, 0x13, 0xAB, 0x0C, //
+            0x02, 0x5A, 0xC4, 0x01,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(decoded.decode_state, "partial");
+        let values: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| (record.index, record.value.value.clone()))
+            .collect();
+        assert_eq!(
+            values,
+            [(0, Some("1000000".into())), (1, Some("45.2".into()))]
+        );
+        let skipped: Vec<_> = decoded
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.code == "application.record_partial")
+            .map(|diagnostic| (diagnostic.offset_start, diagnostic.offset_end))
+            .collect();
+        assert_eq!(skipped, [(Some(6), Some(10))]);
+        Ok(())
+    }
+
+    #[test]
+    fn timestamps_apply_time_flags_and_offsets() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            // type F 15.03.2024 12:30, summer time
+            0x04, 0x6D, 0x1E, 0x8C, 0x0F, 0x33, //
+            // type F with the time invalid flag
+            0x04, 0x6D, 0x9E, 0x0C, 0x0F, 0x33, //
+            // type M 15.03.2024 12:30:45 at UTC+01:00
+            0x0D, 0x6D, 0x07, 0x2D, 0x1E, 0x0C, 0x0F, 0x33, 0x00, 0x04, //
+            // type M at UTC-00:30 with the time invalid flag
+            0x0D, 0x6D, 0x07, 0x2D, 0x9E, 0x0C, 0x0F, 0x33, 0x00, 0xFE,
+        ])?;
+        let decode = |utc_offset| {
+            decode_bytes(
+                &frame,
+                &DecodeOptions {
+                    utc_offset,
+                    ..DecodeOptions::default()
+                },
+            )
+        };
+        let values = |utc_offset| -> Result<Vec<_>, OutputError> {
+            Ok(decode(utc_offset)?
+                .records
+                .iter()
+                .map(|record| record.value.value.clone())
+                .collect())
+        };
+        // the summer time flag is reported with and without UTC offset
+        for utc_offset in [None, Some(60)] {
+            let summer_time: Vec<_> = decode(utc_offset)?
+                .records
+                .iter()
+                .map(|record| record.value.summer_time)
+                .collect();
+            assert_eq!(summer_time, [true, false, false, false]);
+        }
+        let invalid = serde_json::json!({
+            "day": 15, "month": "March", "year": 2024, "hour": 12, "minute": 30, "invalid": true,
+        });
+        let invalid_with_offset = serde_json::json!({
+            "day": 15, "month": "March", "year": 2024, "hour": 12, "minute": 30, "second": 45,
+            "offset": "-00:30", "invalid": true,
+        });
+
+        assert_eq!(
+            values(None)?,
+            [
+                Some("2024-03-15T12:30:00".into()),
+                Some(invalid.clone()),
+                Some("2024-03-15T12:30:45+01:00".into()),
+                Some(invalid_with_offset.clone()),
+            ]
+        );
+        assert_eq!(
+            values(Some(60))?,
+            [
+                Some("2024-03-15T12:30:00+02:00".into()),
+                Some(serde_json::json!({
+                    "day": 15, "month": "March", "year": 2024, "hour": 12, "minute": 30,
+                    "offset": "+01:00", "invalid": true,
+                })),
+                Some("2024-03-15T12:30:45+01:00".into()),
+                Some(invalid_with_offset),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn daylight_saving_and_listening_window_render_iso() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x72, 0x78, 0x56, 0x34, 0x12, 0x24, 0x40, 0x01, 0x07, 0x55, 0x00, 0x00, 0x00, //
+            0x04, 0xFD, 0x72, 0x42, 0x1F, 0xA3, 0x1B, //
+            0x06, 0xFD, 0x73, 0x00, 0x1E, 0x08, 0x1E, 0x00, 0x1F,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        let values: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| {
+                (
+                    record.value.kind.as_str(),
+                    record.value.value.clone(),
+                    record.data_coding.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            values,
+            [
+                (
+                    "daylight_saving",
+                    Some(serde_json::json!({
+                        "begin": "--03-31T02:00",
+                        "end": "--10-27T03:00",
+                        "deviation": "PT1H",
+                    })),
+                    "Daylight saving (type K)"
+                ),
+                (
+                    "listening_window",
+                    Some(serde_json::json!({
+                        "start": "08:30:00",
+                        "duration": "PT30S",
+                        "weekdays": [1, 2, 3, 4, 5],
+                    })),
+                    "Listening window (type L)"
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    fn long_frame(user_data: &[u8]) -> Result<Vec<u8>, std::num::TryFromIntError> {
+        let length = u8::try_from(user_data.len() + 2)?;
+        let mut frame = vec![0x68, length, length, 0x68, 0x08, 0x01];
+        frame.extend_from_slice(user_data);
+        let checksum = frame
+            .iter()
+            .skip(4)
+            .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        frame.extend_from_slice(&[checksum, 0x16]);
+        Ok(frame)
+    }
+
+    #[test]
+    fn compact_frame_reports_missing_format() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x7B, 0x02, 0x00, 0x00, 0x00, 0x34, 0x12, 0x78, 0x56, 0x2C, 0x01,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(decoded.decode_state, "partial");
+        assert_eq!(
+            decoded.transport.header_kind.as_deref(),
+            Some("compact_short_tpl")
+        );
+        assert!(decoded
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == "application.compact_format_missing"));
+        Ok(())
+    }
+
+    #[test]
+    fn compact_frame_is_expanded_with_format_cache() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::user_data::compact_frame::format_signature;
+
+        let records = [0x0C, 0x13, 0x78, 0x56, 0x34, 0x12, 0x02, 0x5A, 0x2C, 0x01];
+        let signature = format_signature(&[0x0C, 0x13, 0x02, 0x5A]);
+        let mut full = vec![0x7A, 0x01, 0x00, 0x00, 0x00];
+        full.extend_from_slice(&records);
+        let mut compact = vec![0x7B, 0x02, 0x00, 0x00, 0x00];
+        compact.extend_from_slice(&signature.to_le_bytes());
+        compact.extend_from_slice(&format_signature(&records).to_le_bytes());
+        compact.extend_from_slice(&[0x78, 0x56, 0x34, 0x12, 0x2C, 0x01]);
+        let options = DecodeOptions {
+            format_cache: Some(Arc::default()),
+            ..DecodeOptions::default()
+        };
+
+        let decoded = decode_bytes(&long_frame(&compact)?, &options)?;
+        assert!(decoded.records.is_empty());
+        decode_bytes(&long_frame(&full)?, &options)?;
+        let decoded = decode_bytes(&long_frame(&compact)?, &options)?;
+        assert_eq!(decoded.decode_state, "complete");
+        assert!(!decoded
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == "application.compact_format_missing"));
+        let values: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| record.value.value.clone())
+            .collect();
         assert_eq!(
-            signed_le_decimal(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F]),
-            Some(i64::MAX.to_string())
+            values,
+            [
+                Some(serde_json::Value::from("12345.678")),
+                Some(serde_json::Value::from("30")),
+            ]
         );
+        Ok(())
     }
 
+    #[cfg(feature = "decryption")]
     #[test]
-    fn scaled_decimal_applies_additive_offset() {
-        assert_eq!(scaled_decimal("876543", -3, None), "876.543");
-        assert_eq!(scaled_decimal("10", 0, Some(0)), "11");
-        assert_eq!(scaled_decimal("-10", -1, Some(-1)), "-0.9");
+    fn encrypted_compact_frame_is_expanded_with_format_cache(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::user_data::compact_frame::format_signature;
+        use cbc::cipher::{block_padding::NoPadding, BlockModeEncrypt, KeyIvInit};
+
+        let key = [0x42; 16];
+        let header = [0x44, 0x2D, 0x2C, 0x78, 0x56, 0x34, 0x12, 0x01, 0x07];
+        let access_number = 0x21;
+        let records = [0x0C, 0x13, 0x78, 0x56, 0x34, 0x12, 0x02, 0x5A, 0x2C, 0x01];
+        let format = [0x0C, 0x13, 0x02, 0x5A];
+        let mut payload = vec![0x2F, 0x2F];
+        payload.extend_from_slice(&format_signature(&format).to_le_bytes());
+        payload.extend_from_slice(&format_signature(&records).to_le_bytes());
+        payload.extend_from_slice(&[0x78, 0x56, 0x34, 0x12, 0x2C, 0x01]);
+        payload.resize(16, 0x2F);
+        let mut iv = [access_number; 16];
+        iv[..8].copy_from_slice(&header[1..]);
+        cbc::Encryptor::<aes::Aes128>::new(&key.into(), &iv.into())
+            .encrypt_padded::<NoPadding>(&mut payload, 16)
+            .map_err(|error| error.to_string())?;
+        // CI 0x7B, mode 5 with one encrypted block
+        let length = u8::try_from(header.len() + 5 + payload.len())?;
+        let mut frame = vec![length];
+        frame.extend_from_slice(&header);
+        frame.extend_from_slice(&[0x7B, access_number, 0x00, 0x10, 0x05]);
+        frame.extend_from_slice(&payload);
+
+        let cache = FormatCache::default();
+        cache
+            .lock()
+            .map_err(|error| error.to_string())?
+            .insert(format_signature(&format), format.to_vec());
+        let options = DecodeOptions {
+            key: Some(key),
+            format_cache: Some(Arc::new(cache)),
+            ..DecodeOptions::default()
+        };
+        let decoded = decode_bytes(&frame, &options)?;
+        assert_eq!(decoded.decode_state, "complete");
+        assert_eq!(decoded.security.decryption_state, "decrypted_unverified");
+        let values: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| record.value.value.clone())
+            .collect();
+        assert_eq!(
+            values,
+            [
+                Some(serde_json::Value::from("12345.678")),
+                Some(serde_json::Value::from("30")),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn format_frame_reports_header_kind() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[0x6A, 0x03, 0x00, 0x00, 0x00, 0x02, 0x34, 0x12, 0x04, 0x13])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(decoded.decode_state, "complete");
+        assert_eq!(
+            decoded.transport.header_kind.as_deref(),
+            Some("format_short_tpl")
+        );
+        assert!(decoded.records.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_data_structure_emits_records() -> Result<(), Box<dyn std::error::Error>> {
+        let decoded = decode_hex(
+            "68 13 13 68 08 05 73 78 56 34 12 0A 00 E9 7E 01 00 00 00 35 01 00 00 3C 16",
+            &DecodeOptions::default(),
+        )?;
+        let identity = decoded.meter.identity.ok_or("missing identity")?;
+        assert_eq!(identity.device_type.as_deref(), Some("Water"));
+        let values: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| {
+                (
+                    record.storage_number,
+                    record.value.value.clone(),
+                    record.unit.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            values,
+            [
+                (0, Some("0.001".into()), Some("m3".to_string())),
+                (1, Some("0.135".into()), Some("m3".to_string())),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn transport_layer_header_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[0x8A, 0x2A, 0x00, 0x00, 0x00])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(
+            decoded.transport.header_kind.as_deref(),
+            Some("transport_short_meter_to_readout")
+        );
+        assert_eq!(
+            decoded.transport.transport_control_information.as_deref(),
+            Some("8A")
+        );
+        let access_numbers: Vec<_> = decoded
+            .transport
+            .access_numbers
+            .iter()
+            .map(|access| (access.source.as_str(), access.value))
+            .collect();
+        assert_eq!(access_numbers, [("tpl.short", 0x2A)]);
+
+        let frame = long_frame(&[
+            0x8A, 0x2A, 0x00, 0x00, 0x00, 0x78, 0x03, 0x13, 0x15, 0x31, 0x00,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(decoded.transport.header_kind.as_deref(), Some("short_tpl"));
+        assert_eq!(
+            decoded.transport.transport_control_information.as_deref(),
+            Some("8A")
+        );
+        assert_eq!(decoded.records.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn cosem_values_are_reported_as_obis_records() -> Result<(), Box<dyn std::error::Error>> {
+        let mut user_data = vec![0x7D, 0x01, 0x00, 0x00, 0x00];
+        user_data.extend_from_slice(&[
+            0x0F, 0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x05, //
+            0x09, 0x06, 0x01, 0x00, 0x01, 0x08, 0x00, 0xFF, //
+            0x06, 0x00, 0x00, 0x30, 0x39, //
+            0x02, 0x02, 0x0F, 0xFD, 0x16, 0x1E, //
+            0x09, 0x06, 0x00, 0x00, 0x01, 0x00, 0x00, 0xFF, //
+            0x09, 0x0C, 0x07, 0xE8, 0x03, 0x0F, 0x05, 0x0C, 0x1E, 0x00, 0xFF, 0xFF, 0xC4, 0x00,
+        ]);
+        let frame = long_frame(&user_data)?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(
+            decoded.transport.header_kind.as_deref(),
+            Some("cosem_short_tpl")
+        );
+        let records: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| {
+                (
+                    record.obis.as_deref(),
+                    record.value.kind.as_str(),
+                    record.value.value.clone(),
+                    record.unit.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            records,
+            [
+                (
+                    Some("1-0:1.8.0*255"),
+                    "decimal",
+                    Some("12.345".into()),
+                    Some("W.h")
+                ),
+                (
+                    Some("0-0:1.0.0*255"),
+                    "datetime",
+                    Some("2024-03-15T12:30:00+01:00".into()),
+                    None
+                ),
+            ]
+        );
+        assert_eq!(decoded.decode_state, "complete");
+        Ok(())
+    }
+
+    #[test]
+    fn obis_frame_records_are_keyed_by_declared_code() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x7F, 0x01, 0x00, 0x00, 0x00, //
+            0x06, 0x83, 0x3F, 0xFF, 0x00, 0x08, 0x01, 0x00, 0x01, //
+            0x04, 0x03, 0x39, 0x30, 0x00, 0x00,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(
+            decoded.transport.header_kind.as_deref(),
+            Some("obis_short_tpl")
+        );
+        let records: Vec<_> = decoded
+            .records
+            .iter()
+            .map(|record| (record.obis.as_deref(), record.value.value.clone()))
+            .collect();
+        assert_eq!(
+            records.last(),
+            Some(&(Some("1-0:1.8.0*255"), Some("12345".into())))
+        );
+        assert_eq!(decoded.decode_state, "complete");
+        Ok(())
+    }
+
+    #[test]
+    fn relayed_frame_reports_repeater_path() -> Result<(), Box<dyn std::error::Error>> {
+        let frame = long_frame(&[
+            0x81, 0x81, 0x2D, 0x2C, 0x01, 0x00, 0x00, 0x10, 0x01, 0x32, //
+            0x7A, 0x2B, 0x00, 0x00, 0x00, 0x03, 0x13, 0x15, 0x31, 0x00,
+        ])?;
+        let decoded = decode_bytes(&frame, &DecodeOptions::default())?;
+        assert_eq!(decoded.transport.header_kind.as_deref(), Some("short_tpl"));
+        let network = decoded.transport.network.ok_or("missing network layer")?;
+        assert_eq!(network.hop_count, 1);
+        assert!(network.repeated);
+        let path: Vec<_> = network
+            .repeater_path
+            .iter()
+            .map(|repeater| (repeater.manufacturer.as_str(), repeater.address.as_str()))
+            .collect();
+        assert_eq!(path, [("2C2D", "010000100132")]);
+        assert_eq!(decoded.records.len(), 1);
+        Ok(())
     }
 }
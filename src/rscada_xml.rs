@@ -6,6 +6,7 @@
 
 use crate::mbus_data::MbusData;
 use crate::user_data;
+use crate::user_data::value_information::PlaintextVifOrder;
 use wired_mbus_link_layer as frames;
 use wireless_mbus_link_layer as wireless;
 
@@ -13,8 +14,16 @@ const XML_PROCESSING_INSTRUCTION: &str = "<?xml version=\"1.0\" encoding=\"ISO-8
 
 /// Render a raw wired frame in the byte-compatible libmbus vocabulary, or a
 /// wireless frame in the same documented project vocabulary.
-pub(crate) fn render_from_bytes(data: &[u8], key: Option<&[u8; 16]>) -> Result<String, String> {
+pub(crate) fn render_from_bytes(
+    data: &[u8],
+    key: Option<&[u8; 16]>,
+    plaintext_vif_order: PlaintextVifOrder,
+) -> Result<String, String> {
     if let Ok(parsed) = MbusData::<frames::WiredFrame>::try_from(data) {
+        let records = parsed
+            .data_records
+            .clone()
+            .map(|records| records.with_plaintext_vif_order(plaintext_vif_order));
         return match &parsed.user_data {
             Some(user_data::UserDataBlock::VariableDataStructureWithLongTplHeader {
                 long_tpl_header,
@@ -24,17 +33,15 @@ pub(crate) fn render_from_bytes(data: &[u8], key: Option<&[u8; 16]>) -> Result<S
                 // requested, including meters whose configuration bits claim
                 // encryption while their records remain parseable.
                 if key.is_none() {
-                    Ok(render_variable(
-                        long_tpl_header,
-                        parsed.data_records.as_ref(),
-                    ))
+                    Ok(render_variable(long_tpl_header, records.as_ref()))
                 } else {
                     render_with_optional_decryption(
                         long_tpl_header,
                         parsed.user_data.as_ref(),
-                        parsed.data_records.as_ref(),
+                        records.as_ref(),
                         None,
                         key,
+                        plaintext_vif_order,
                     )
                 }
             }
@@ -61,23 +68,29 @@ pub(crate) fn render_from_bytes(data: &[u8], key: Option<&[u8; 16]>) -> Result<S
     let normalized = wireless::strip_format_a_crcs(data, &mut crc_buffer).unwrap_or(data);
     let parsed = MbusData::<wireless::WirelessFrame>::try_from(normalized)
         .map_err(|error| format!("could not parse data as wired or wireless M-Bus: {error:?}"))?;
-    render_wireless(&parsed, key)
+    render_wireless(&parsed, key, plaintext_vif_order)
 }
 
 fn render_wireless(
     parsed: &MbusData<wireless::WirelessFrame<'_>>,
     key: Option<&[u8; 16]>,
+    plaintext_vif_order: PlaintextVifOrder,
 ) -> Result<String, String> {
     use user_data::UserDataBlock;
+    let records = parsed
+        .data_records
+        .clone()
+        .map(|records| records.with_plaintext_vif_order(plaintext_vif_order));
     match &parsed.user_data {
         Some(UserDataBlock::VariableDataStructureWithLongTplHeader {
             long_tpl_header, ..
         }) => render_with_optional_decryption(
             long_tpl_header,
             parsed.user_data.as_ref(),
-            parsed.data_records.as_ref(),
+            records.as_ref(),
             Some(&parsed.frame.manufacturer_id),
             key,
+            plaintext_vif_order,
         ),
         Some(UserDataBlock::VariableDataStructureWithShortTplHeader {
             extended_link_layer,
@@ -98,7 +111,15 @@ fn render_wireless(
             };
             let status = (!synthetic).then_some(short_tpl_header.status.bits());
             let signature = (!synthetic).then_some(short_tpl_header.configuration_field.raw());
-            render_wireless_with_optional_decryption(parsed, access, status, signature, key)
+            render_wireless_with_optional_decryption(
+                parsed,
+                records.as_ref(),
+                access,
+                status,
+                signature,
+                key,
+                plaintext_vif_order,
+            )
         }
         Some(UserDataBlock::VariableDataStructureWithoutTplHeader {
             extended_link_layer,
@@ -108,7 +129,7 @@ fn render_wireless(
             extended_link_layer.as_ref().map(|ell| ell.access_number),
             None,
             None,
-            parsed.data_records.as_ref(),
+            records.as_ref(),
         )),
         Some(UserDataBlock::FixedDataStructure {
             identification_number,
@@ -141,6 +162,7 @@ fn render_with_optional_decryption(
     records: Option<&user_data::DataRecords<'_>>,
     wireless_id: Option<&wireless::ManufacturerId>,
     key: Option<&[u8; 16]>,
+    plaintext_vif_order: PlaintextVifOrder,
 ) -> Result<String, String> {
     if !header.is_encrypted() {
         return Ok(render_variable(header, records));
@@ -157,22 +179,25 @@ fn render_with_optional_decryption(
         let data = decrypted
             .get(..len)
             .ok_or_else(|| "decryption returned an invalid payload length".to_string())?;
-        let records = user_data::DataRecords::new(data, Some(header));
+        let records = user_data::DataRecords::new(data, Some(header))
+            .with_plaintext_vif_order(plaintext_vif_order);
         Ok(render_variable(header, Some(&records)))
     }
     #[cfg(not(feature = "decryption"))]
     {
-        let _ = (user_data, wireless_id, key);
+        let _ = (user_data, wireless_id, key, plaintext_vif_order);
         Err("this build does not include decryption support".to_string())
     }
 }
 
 fn render_wireless_with_optional_decryption(
     parsed: &MbusData<wireless::WirelessFrame<'_>>,
+    records: Option<&user_data::DataRecords<'_>>,
     access: Option<u8>,
     status: Option<u8>,
     signature: Option<u16>,
     key: Option<&[u8; 16]>,
+    plaintext_vif_order: PlaintextVifOrder,
 ) -> Result<String, String> {
     let encrypted = match &parsed.user_data {
         Some(user_data::UserDataBlock::VariableDataStructureWithShortTplHeader {
@@ -193,7 +218,7 @@ fn render_wireless_with_optional_decryption(
             access,
             status,
             signature,
-            parsed.data_records.as_ref(),
+            records,
         ));
     }
     let Some(key) = key else {
@@ -222,7 +247,8 @@ fn render_wireless_with_optional_decryption(
         let data = decrypted
             .get(..len)
             .ok_or_else(|| "decryption returned an invalid payload length".to_string())?;
-        let records = user_data::DataRecords::new(data, None);
+        let records =
+            user_data::DataRecords::new(data, None).with_plaintext_vif_order(plaintext_vif_order);
         Ok(render_wireless_variable(
             &parsed.frame.manufacturer_id,
             access,
@@ -233,7 +259,7 @@ fn render_wireless_with_optional_decryption(
     }
     #[cfg(not(feature = "decryption"))]
     {
-        let _ = key;
+        let _ = (key, plaintext_vif_order);
         Err("this build does not include decryption support".to_string())
     }
 }
@@ -34,8 +34,8 @@ fn test_lsb_frame() {
     ];
 
     let frames = [
-        (lsb_frame, 9670106, Some(DataType::Number(808732.0))),
-        (non_lsb_frame, 72237356, Some(DataType::Number(568714.0))),
+        (lsb_frame, 9670106, Some(DataType::Integer(808732))),
+        (non_lsb_frame, 72237356, Some(DataType::Integer(568714))),
     ];
 
     for (frame, expected_iden_nr, data_record_value) in frames {
@@ -114,7 +114,7 @@ fn test_manufacturer_specific_data() {
             );
             assert_eq!(
                 data_records.pop().unwrap().data.value,
-                Some(DataType::Number(2571.0))
+                Some(DataType::Integer(2571))
             );
         }
     }
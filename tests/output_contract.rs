@@ -80,17 +80,14 @@ fn canonical_json_has_a_versioned_stable_root() {
     }
     assert_eq!(first_record["data_hex"], "00 00 00 00");
     assert!(!first_record.contains_key("record_hex"));
-    assert_eq!(value["records"][7]["value"]["value"], "0012-01-12");
+    assert_eq!(value["records"][7]["value"]["value"], "2012-01-12");
 }
 
 #[test]
 fn enrichment_is_optional_without_changing_the_schema() {
     let decoded = decode_hex(
         WIRED_FRAME,
-        &DecodeOptions {
-            key: None,
-            include_enrichment: false,
-        },
+        &DecodeOptions::default().with_enrichment(false),
     )
     .unwrap();
     let value = serde_json::to_value(decoded).unwrap();
@@ -118,6 +115,12 @@ fn formats_and_compatibility_aliases_share_one_parser() {
         "one input frame must produce one CSV data row"
     );
     assert!(csv.lines().next().unwrap().contains("record_0_value"));
+    assert!(csv
+        .lines()
+        .next()
+        .unwrap()
+        .contains("record_0_quantities,record_0_value_type"));
+    assert!(csv.lines().next().unwrap().ends_with("record_9_modifiers"));
     for duplicate in [
         "value_decimal",
         "raw_value",
@@ -184,13 +184,10 @@ fn wireless_encrypted_xml_is_header_only_without_key_and_decodes_with_key() {
         input,
         OutputFormat::Xml,
         &RenderOptions {
-            decode: DecodeOptions {
-                key: Some([
-                    0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
-                    0x0E, 0x0F, 0x11,
-                ]),
-                include_enrichment: true,
-            },
+            decode: DecodeOptions::default().with_key(Some([
+                0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+                0x0F, 0x11,
+            ])),
             ..RenderOptions::default()
         },
     )
@@ -153,4 +153,51 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_rscada_records_decode_without_unimplemented_vifs() {
+        /* every VIF in the corpus is covered, records may still be skipped for other reasons. */
+        use m_bus_parser::user_data::{data_information::DataInformationError, DataRecordError};
+
+        for entry in WalkDir::new("./tests/rscada/test-frames")
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "hex"))
+        {
+            let contents = fs::read_to_string(entry.path()).unwrap();
+            let bytes = hex::decode(contents.trim().replace(' ', "")).unwrap();
+            let WiredFrame::LongFrame { data, .. } =
+                WiredFrame::try_from(bytes.as_slice()).unwrap()
+            else {
+                panic!("Frame is not a long frame");
+            };
+            let Ok(UserDataBlock::VariableDataStructureWithLongTplHeader {
+                long_tpl_header,
+                variable_data_block,
+                ..
+            }) = UserDataBlock::try_from(data)
+            else {
+                continue;
+            };
+
+            let records = m_bus_parser::user_data::parse_data_records_with_header(
+                variable_data_block,
+                &long_tpl_header,
+            )
+            .recovering();
+            for skipped in records.filter_map(Result::err) {
+                assert!(
+                    !matches!(
+                        skipped.error,
+                        DataRecordError::DataInformationError(
+                            DataInformationError::Unimplemented { .. }
+                        )
+                    ),
+                    "{}: {:?}",
+                    entry.path().display(),
+                    skipped
+                );
+            }
+        }
+    }
 }
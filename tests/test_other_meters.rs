@@ -79,7 +79,7 @@ mod tests {
                 "record {i} exponent"
             );
             assert_eq!(
-                rec["data"]["value"]["Number"].as_f64().unwrap(),
+                rec["data"]["value"]["Integer"].as_f64().unwrap(),
                 *value,
                 "record {i} value"
             );
@@ -2,6 +2,9 @@
 
 use m_bus_core::DeviceType;
 use m_bus_parser::mbus_data::MbusData;
+use m_bus_parser::user_data::manufacturer_specific::{
+    DecoderRegistry, ManufacturerPayload, MeterIdentity, BUILTIN_DECODERS,
+};
 use m_bus_parser::user_data::UserDataBlock;
 use m_bus_parser::WirelessFrame;
 use serde::Deserialize;
@@ -37,6 +40,17 @@ struct ExpectedDataRecord {
     function: String,
 }
 
+/// Returns the expected value in the SI units `ScaledValue::to_si` uses.
+fn expected_si_value(record: &ExpectedDataRecord) -> f64 {
+    let value = record.value as f64 * 10f64.powi(record.exponent);
+    match record.unit.as_str() {
+        "°C" => value + 273.15,
+        "Wh" => value * 3.6e3,
+        "kWh" => value * 3.6e6,
+        _ => value,
+    }
+}
+
 fn device_type_from_str(device_type: &str) -> DeviceType {
     match device_type {
         "Other" => DeviceType::Other,
@@ -365,6 +379,92 @@ mod tests {
                     println!("✓ Test {} passed", index + 1);
                     passed += 1;
                 }
+                Some(UserDataBlock::ManufacturerSpecific {
+                    control_information,
+                    manufacturer_data,
+                    ..
+                }) => {
+                    let manufacturer_id = &mbus_data.frame.manufacturer_id;
+                    assert_eq!(
+                        manufacturer_id.identification_number.number,
+                        test_vector.expected.identification_number,
+                        "Test {}: Identification number mismatch",
+                        index + 1
+                    );
+                    assert_eq!(
+                        manufacturer_id
+                            .manufacturer_code
+                            .code
+                            .iter()
+                            .collect::<String>(),
+                        test_vector.expected.manufacturer,
+                        "Test {}: Manufacturer mismatch",
+                        index + 1
+                    );
+                    assert_eq!(
+                        manufacturer_id.version,
+                        test_vector.expected.version,
+                        "Test {}: Version mismatch",
+                        index + 1
+                    );
+                    assert_eq!(
+                        manufacturer_id.device_type,
+                        device_type_from_str(&test_vector.expected.medium),
+                        "Test {}: Medium mismatch",
+                        index + 1
+                    );
+                    // The vectors take the access number and status from the
+                    // first payload bytes, after the leading byte of CI 0xA0.
+                    let header = usize::from(control_information == 0xA0);
+                    assert_eq!(
+                        manufacturer_data.get(header).copied(),
+                        Some(test_vector.expected.access_number),
+                        "Test {}: Access number mismatch",
+                        index + 1
+                    );
+                    assert_eq!(
+                        manufacturer_data.get(header + 1).copied(),
+                        Some(test_vector.expected.status),
+                        "Test {}: Status mismatch",
+                        index + 1
+                    );
+                    let meter = MeterIdentity::new(
+                        manufacturer_id.manufacturer_code,
+                        manufacturer_id.version,
+                        manufacturer_id.device_type,
+                    );
+                    let payload = ManufacturerPayload::new(control_information, manufacturer_data);
+                    let values = BUILTIN_DECODERS
+                        .decode_payload(&meter, &payload)
+                        .unwrap_or_else(|| {
+                            panic!(
+                                "Test {}: Manufacturer specific payload not decoded",
+                                index + 1
+                            )
+                        });
+                    // dates have no counterpart in the expected records
+                    let quantities: Vec<_> = values
+                        .iter()
+                        .filter_map(|value| Some((value.storage_number, value.scaled_value()?)))
+                        .collect();
+                    assert!(!quantities.is_empty(), "Test {}: No values", index + 1);
+                    for (storage_number, scaled) in quantities {
+                        let value = scaled.to_si().value.to_f64();
+                        assert!(
+                            test_vector.expected.data_records.iter().any(|expected| {
+                                u64::from(expected.storage_number) == storage_number
+                                    && (expected_si_value(expected) - value).abs()
+                                        <= value.abs() * 1e-6
+                            }),
+                            "Test {}: {:?} with storage number {} not expected",
+                            index + 1,
+                            scaled.value,
+                            storage_number
+                        );
+                    }
+                    println!("✓ Test {} passed", index + 1);
+                    passed += 1;
+                }
                 _ => {
                     println!(
                         "⚠️  Test {} skipped: Data structure not yet supported (got {:?})",
@@ -60,26 +60,41 @@ fn parse_key(key_hex: Option<&str>) -> Result<Option<[u8; 16]>, OutputError> {
         })
 }
 
+fn check_utc_offset(utc_offset: Option<i16>) -> Result<Option<i16>, OutputError> {
+    match utc_offset {
+        Some(minutes) if !(-14 * 60..=14 * 60).contains(&minutes) => {
+            Err(OutputError::InvalidOption {
+                option: "utcOffset",
+                message: format!("must be within 14 hours of UTC, received {minutes} minutes"),
+            })
+        }
+        utc_offset => Ok(utc_offset),
+    }
+}
+
 fn decode_options(
     key_hex: Option<&str>,
     include_enrichment: Option<bool>,
+    utc_offset: Option<i16>,
 ) -> Result<DecodeOptions, OutputError> {
-    Ok(DecodeOptions {
-        key: parse_key(key_hex)?,
-        include_enrichment: include_enrichment.unwrap_or(true),
-    })
+    Ok(DecodeOptions::default()
+        .with_key(parse_key(key_hex)?)
+        .with_enrichment(include_enrichment.unwrap_or(true))
+        .with_utc_offset(check_utc_offset(utc_offset)?))
 }
 
 /// Decode into the canonical schema as a native JavaScript object.
+/// Timestamps are rendered with the offset to UTC in minutes if given.
 #[wasm_bindgen]
 pub fn m_bus_decode(
     data: &str,
     key_hex: Option<String>,
     include_enrichment: Option<bool>,
+    utc_offset: Option<i16>,
 ) -> Result<JsValue, JsValue> {
     let decoded = decode_hex(
         data,
-        &decode_options(key_hex.as_deref(), include_enrichment).map_err(error_to_js)?,
+        &decode_options(key_hex.as_deref(), include_enrichment, utc_offset).map_err(error_to_js)?,
     )
     .map_err(error_to_js)?;
     serde_wasm_bindgen::to_value(&decoded).map_err(|error| {
@@ -98,10 +113,12 @@ pub fn m_bus_render(
     key_hex: Option<String>,
     width: Option<usize>,
     include_enrichment: Option<bool>,
+    utc_offset: Option<i16>,
 ) -> Result<String, JsValue> {
     let format = OutputFormat::from_str(format).map_err(error_to_js)?;
     let options = RenderOptions {
-        decode: decode_options(key_hex.as_deref(), include_enrichment).map_err(error_to_js)?,
+        decode: decode_options(key_hex.as_deref(), include_enrichment, utc_offset)
+            .map_err(error_to_js)?,
         table_width: width,
     };
     render_hex(data, format, &options).map_err(error_to_js)
@@ -110,7 +127,7 @@ pub fn m_bus_render(
 /// Compatibility wrapper returning errors as text. Prefer `m_bus_render`.
 #[wasm_bindgen]
 pub fn m_bus_parse(data: &str, format: &str) -> String {
-    m_bus_render(data, format, None, None, None).unwrap_or_else(|value| {
+    m_bus_render(data, format, None, None, None, None).unwrap_or_else(|value| {
         js_sys::JSON::stringify(&value)
             .ok()
             .and_then(|value| value.as_string())
@@ -121,12 +138,14 @@ pub fn m_bus_parse(data: &str, format: &str) -> String {
 /// Compatibility wrapper returning errors as text. Prefer `m_bus_render`.
 #[wasm_bindgen]
 pub fn m_bus_parse_with_key(data: &str, format: &str, key_hex: &str) -> String {
-    m_bus_render(data, format, Some(key_hex.to_string()), None, None).unwrap_or_else(|value| {
-        js_sys::JSON::stringify(&value)
-            .ok()
-            .and_then(|value| value.as_string())
-            .unwrap_or_else(|| "M-Bus rendering failed".to_string())
-    })
+    m_bus_render(data, format, Some(key_hex.to_string()), None, None, None).unwrap_or_else(
+        |value| {
+            js_sys::JSON::stringify(&value)
+                .ok()
+                .and_then(|value| value.as_string())
+                .unwrap_or_else(|| "M-Bus rendering failed".to_string())
+        },
+    )
 }
 
 #[cfg(test)]
@@ -139,4 +158,10 @@ mod tests {
         assert!(parse_key(Some("00")).is_err());
         assert!(parse_key(Some("00112233445566778899AABBCCDDEEFF")).is_ok());
     }
+
+    #[test]
+    fn utc_offset_is_limited_to_fourteen_hours() {
+        assert_eq!(check_utc_offset(Some(-330)).ok(), Some(Some(-330)));
+        assert!(check_utc_offset(Some(15 * 60)).is_err());
+    }
 }
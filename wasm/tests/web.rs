@@ -28,7 +28,7 @@ fn test_m_bus_parse_table() {
 fn decode_returns_a_native_versioned_object() {
     let input = "68 3D 3D 68 08 01 72 00 51 20 02 82 4D 02 04 00 88 00 00 04 07 00 00 00 00 0C 15 03 00 00 00 0B 2E 00 00 00 0B 3B 00 00 00 0A 5A 88 12 0A 5E 16 05 0B 61 23 77 00 02 6C 8C 11 02 27 37 0D 0F 60 00 67 16";
     let decoded =
-        m_bus_parser_wasm_pack::m_bus_decode(input, None, Some(false)).expect("valid frame");
+        m_bus_parser_wasm_pack::m_bus_decode(input, None, Some(false), None).expect("valid frame");
 
     assert_eq!(
         Reflect::get(&decoded, &JsValue::from_str("schema_version"))
@@ -130,8 +130,14 @@ fn test_m_bus_parse_with_key_preserves_utf8_text() {
 #[wasm_bindgen_test]
 fn typed_render_rejects_invalid_keys_without_panicking() {
     let input = "68 03 03 68 08 01 00 09 16";
-    let result =
-        m_bus_parser_wasm_pack::m_bus_render(input, "json", Some("123".to_string()), None, None);
+    let result = m_bus_parser_wasm_pack::m_bus_render(
+        input,
+        "json",
+        Some("123".to_string()),
+        None,
+        None,
+        None,
+    );
     assert!(result.is_err());
 }
 